@@ -0,0 +1,24 @@
+use std::fmt::Display;
+
+/// Returned when a ciphertext, its associated data or its tag has been modified. No part of the
+/// plaintext is released in that case.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct AuthenticationError;
+
+impl Display for AuthenticationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("authentication failed")
+    }
+}
+
+impl std::error::Error for AuthenticationError {}
+
+/// Splits a ciphertext into the ciphertext proper and the 16 byte tag that follows it.
+pub(crate) fn split_tag(ciphertext: &[u8]) -> Result<(&[u8], &[u8; 16]), AuthenticationError> {
+    let tag_start = ciphertext
+        .len()
+        .checked_sub(16)
+        .ok_or(AuthenticationError)?;
+    let (ciphertext, tag) = ciphertext.split_at(tag_start);
+    Ok((ciphertext, tag.try_into().unwrap()))
+}
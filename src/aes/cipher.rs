@@ -1,8 +1,11 @@
+use crate::key::KeyError;
+use crate::zeroize::Zeroize;
 use crate::{Block, BlockCipher};
 
 use super::expander::AesKeyExpander;
 use super::field::AesField;
 use super::sbox::SBOX;
+use super::{Key128, Key192, Key256};
 
 struct State(Block<16>);
 
@@ -134,6 +137,19 @@ pub struct Aes {
 }
 
 impl Aes {
+    /// Picks AES-128, AES-192 or AES-256 depending on the length of the key.
+    pub fn new(key: &[u8]) -> Result<Aes, KeyError> {
+        match key.len() {
+            16 => Ok(Key128::try_from(key)?.into()),
+            24 => Ok(Key192::try_from(key)?.into()),
+            32 => Ok(Key256::try_from(key)?.into()),
+            actual => Err(KeyError::InvalidLength {
+                expected: "16, 24 or 32 bytes",
+                actual,
+            }),
+        }
+    }
+
     pub fn with_128_bit_key(key: Block<16>) -> Aes {
         let expander = AesKeyExpander::new();
         let keys = expander.expand_key::<16, 4>(key, 11);
@@ -156,6 +172,20 @@ impl Aes {
     }
 }
 
+impl Drop for Aes {
+    fn drop(&mut self) {
+        self.keys.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Aes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Aes")
+            .field("rounds", &self.rounds)
+            .finish_non_exhaustive()
+    }
+}
+
 impl BlockCipher<16> for Aes {
     fn encrypt(&self, plaintext: Block<16>) -> Block<16> {
         let mut state = State(plaintext);
@@ -1,5 +1,6 @@
 use std::ops::{BitXor, Deref, DerefMut};
 
+use crate::zeroize::Zeroize;
 use crate::Block;
 
 use super::{
@@ -64,7 +65,7 @@ impl AesKeyExpander {
             .collect();
         let key_array = key_words.as_slice().try_into().unwrap();
 
-        let result = self.expand_key_using_words::<W>(key_array, num_keys);
+        let mut result = self.expand_key_using_words::<W>(key_array, num_keys);
 
         let mut expanded_keys: Vec<Block<16>> = Vec::with_capacity(16 * num_keys);
         for words in result.chunks(4) {
@@ -77,6 +78,10 @@ impl AesKeyExpander {
             expanded_keys.push(block);
         }
 
+        for w in &mut result {
+            w.0.zeroize();
+        }
+
         expanded_keys
     }
 
@@ -1,15 +1,53 @@
+use crate::key::{decode_base64, decode_hex, fixed_length, KeyError};
+use crate::zeroize::Zeroize;
 use crate::Block;
 
 mod expander;
 mod field;
-mod sbox;
+pub(crate) mod sbox;
 mod cipher;
 
 pub use cipher::Aes;
 
-pub struct Key128(Block<16>);
-pub struct Key192(Block<24>);
-pub struct Key256(Block<32>);
+pub struct Key128(pub(crate) Block<16>);
+pub struct Key192(pub(crate) Block<24>);
+pub struct Key256(pub(crate) Block<32>);
+
+impl Drop for Key128 {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for Key192 {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for Key256 {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Key128 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Key128(<redacted>)")
+    }
+}
+
+impl std::fmt::Debug for Key192 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Key192(<redacted>)")
+    }
+}
+
+impl std::fmt::Debug for Key256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Key256(<redacted>)")
+    }
+}
 
 impl From<[u64; 2]> for Key128 {
     fn from(values: [u64; 2]) -> Self {
@@ -29,6 +67,60 @@ impl From<[u64; 4]> for Key256 {
     }
 }
 
+impl TryFrom<&[u8]> for Key128 {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self(fixed_length(key, "16 bytes")?))
+    }
+}
+
+impl TryFrom<&[u8]> for Key192 {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self(fixed_length(key, "24 bytes")?))
+    }
+}
+
+impl TryFrom<&[u8]> for Key256 {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self(fixed_length(key, "32 bytes")?))
+    }
+}
+
+impl Key128 {
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+}
+
+impl Key192 {
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+}
+
+impl Key256 {
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+}
+
 impl From<Key128> for Aes {
     fn from(key: Key128) -> Self {
         Self::with_128_bit_key(key.0)
@@ -54,3 +146,24 @@ fn copy_u64s<const V: usize, const N: usize>(values: [u64; V]) -> [u8; N] {
     }
     key
 }
+
+#[cfg(test)]
+mod tests {
+    use std::mem::ManuallyDrop;
+
+    use super::*;
+
+    #[test]
+    fn key_is_zeroized_on_drop() {
+        let mut key = ManuallyDrop::new(Key256::from([1, 2, 3, 4]));
+        unsafe { ManuallyDrop::drop(&mut key) };
+        assert_eq!(key.0, [0; 32]);
+    }
+
+    #[test]
+    fn debug_output_is_redacted() {
+        let key = Key128::from([0x1234567890123456, 0x7890123456789012]);
+        assert_eq!(format!("{:?}", key), "Key128(<redacted>)");
+        assert_eq!(format!("{:?}", Aes::from(key)), "Aes { rounds: 10, .. }");
+    }
+}
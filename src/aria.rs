@@ -0,0 +1,268 @@
+use crate::aes::sbox::SBOX;
+use crate::aes::{Key128, Key192, Key256};
+use crate::key::{decode_base64, decode_hex, KeyError};
+use crate::zeroize::Zeroize;
+use crate::{Block, BlockCipher};
+
+// SB1 and SB3 are the AES S-box and its inverse. SB2 is an affine transformation of x^247 in the
+// AES field, and SB4 is its inverse.
+const SB1: [u8; 256] = SBOX.forward;
+const SB3: [u8; 256] = SBOX.backward;
+
+#[rustfmt::skip]
+const SB2: [u8; 256] = [
+    0xe2, 0x4e, 0x54, 0xfc, 0x94, 0xc2, 0x4a, 0xcc, 0x62, 0x0d, 0x6a, 0x46, 0x3c, 0x4d, 0x8b, 0xd1,
+    0x5e, 0xfa, 0x64, 0xcb, 0xb4, 0x97, 0xbe, 0x2b, 0xbc, 0x77, 0x2e, 0x03, 0xd3, 0x19, 0x59, 0xc1,
+    0x1d, 0x06, 0x41, 0x6b, 0x55, 0xf0, 0x99, 0x69, 0xea, 0x9c, 0x18, 0xae, 0x63, 0xdf, 0xe7, 0xbb,
+    0x00, 0x73, 0x66, 0xfb, 0x96, 0x4c, 0x85, 0xe4, 0x3a, 0x09, 0x45, 0xaa, 0x0f, 0xee, 0x10, 0xeb,
+    0x2d, 0x7f, 0xf4, 0x29, 0xac, 0xcf, 0xad, 0x91, 0x8d, 0x78, 0xc8, 0x95, 0xf9, 0x2f, 0xce, 0xcd,
+    0x08, 0x7a, 0x88, 0x38, 0x5c, 0x83, 0x2a, 0x28, 0x47, 0xdb, 0xb8, 0xc7, 0x93, 0xa4, 0x12, 0x53,
+    0xff, 0x87, 0x0e, 0x31, 0x36, 0x21, 0x58, 0x48, 0x01, 0x8e, 0x37, 0x74, 0x32, 0xca, 0xe9, 0xb1,
+    0xb7, 0xab, 0x0c, 0xd7, 0xc4, 0x56, 0x42, 0x26, 0x07, 0x98, 0x60, 0xd9, 0xb6, 0xb9, 0x11, 0x40,
+    0xec, 0x20, 0x8c, 0xbd, 0xa0, 0xc9, 0x84, 0x04, 0x49, 0x23, 0xf1, 0x4f, 0x50, 0x1f, 0x13, 0xdc,
+    0xd8, 0xc0, 0x9e, 0x57, 0xe3, 0xc3, 0x7b, 0x65, 0x3b, 0x02, 0x8f, 0x3e, 0xe8, 0x25, 0x92, 0xe5,
+    0x15, 0xdd, 0xfd, 0x17, 0xa9, 0xbf, 0xd4, 0x9a, 0x7e, 0xc5, 0x39, 0x67, 0xfe, 0x76, 0x9d, 0x43,
+    0xa7, 0xe1, 0xd0, 0xf5, 0x68, 0xf2, 0x1b, 0x34, 0x70, 0x05, 0xa3, 0x8a, 0xd5, 0x79, 0x86, 0xa8,
+    0x30, 0xc6, 0x51, 0x4b, 0x1e, 0xa6, 0x27, 0xf6, 0x35, 0xd2, 0x6e, 0x24, 0x16, 0x82, 0x5f, 0xda,
+    0xe6, 0x75, 0xa2, 0xef, 0x2c, 0xb2, 0x1c, 0x9f, 0x5d, 0x6f, 0x80, 0x0a, 0x72, 0x44, 0x9b, 0x6c,
+    0x90, 0x0b, 0x5b, 0x33, 0x7d, 0x5a, 0x52, 0xf3, 0x61, 0xa1, 0xf7, 0xb0, 0xd6, 0x3f, 0x7c, 0x6d,
+    0xed, 0x14, 0xe0, 0xa5, 0x3d, 0x22, 0xb3, 0xf8, 0x89, 0xde, 0x71, 0x1a, 0xaf, 0xba, 0xb5, 0x81,
+];
+
+const SB4: [u8; 256] = {
+    let mut inverse = [0; 256];
+    let mut x = 0;
+    while x < 256 {
+        inverse[SB2[x] as usize] = x as u8;
+        x += 1;
+    }
+    inverse
+};
+
+const C1: u128 = 0x517cc1b727220a94fe13abe8fa9a6ee0;
+const C2: u128 = 0x6db14acc9e21c820ff28b1d5ef5de2b0;
+const C3: u128 = 0xdb92371d2126e9700324977504e8c90e;
+
+/// The rows of the diffusion layer A, which is its own inverse: the bytes of the input that are
+/// XORed into each byte of the output.
+#[rustfmt::skip]
+const DIFFUSION: [[usize; 7]; 16] = [
+    [3, 4, 6, 8, 9, 13, 14],
+    [2, 5, 7, 8, 9, 12, 15],
+    [1, 4, 6, 10, 11, 12, 15],
+    [0, 5, 7, 10, 11, 13, 14],
+    [0, 2, 5, 8, 11, 14, 15],
+    [1, 3, 4, 9, 10, 14, 15],
+    [0, 2, 7, 9, 10, 12, 13],
+    [1, 3, 6, 8, 11, 12, 13],
+    [0, 1, 4, 7, 10, 13, 15],
+    [0, 1, 5, 6, 11, 12, 14],
+    [2, 3, 5, 6, 8, 13, 15],
+    [2, 3, 4, 7, 9, 12, 14],
+    [1, 2, 6, 7, 9, 11, 12],
+    [0, 3, 6, 7, 8, 10, 13],
+    [0, 3, 4, 5, 9, 11, 14],
+    [1, 2, 4, 5, 8, 10, 15],
+];
+
+/// ARIA (RFC 5794) with 128-, 192- or 256-bit keys, using 12, 14 or 16 rounds respectively.
+pub struct Aria {
+    encryption_keys: [u128; 17],
+    decryption_keys: [u128; 17],
+    rounds: usize,
+}
+
+impl Aria {
+    /// Picks the key size depending on the length of the key.
+    pub fn new(key: &[u8]) -> Self {
+        Self::try_from(key).expect("invalid ARIA key")
+    }
+
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+
+    fn expand_key(key: &[u8]) -> Self {
+        let (rounds, [ck1, ck2, ck3]) = match key.len() {
+            16 => (12, [C1, C2, C3]),
+            24 => (14, [C2, C3, C1]),
+            _ => (16, [C3, C1, C2]),
+        };
+
+        let mut padded = [0_u8; 32];
+        padded[..key.len()].copy_from_slice(key);
+        let kl = u128::from_be_bytes(padded[..16].try_into().unwrap());
+        let kr = u128::from_be_bytes(padded[16..].try_into().unwrap());
+
+        let mut w = [0_u128; 4];
+        w[0] = kl;
+        w[1] = fo(w[0], ck1) ^ kr;
+        w[2] = fe(w[1], ck2) ^ w[0];
+        w[3] = fo(w[2], ck3) ^ w[1];
+
+        let mut encryption_keys = [0_u128; 17];
+        for (i, ek) in encryption_keys.iter_mut().enumerate() {
+            let rotated = w[(i + 1) % 4];
+            *ek = w[i % 4]
+                ^ match i / 4 {
+                    0 => rotated.rotate_right(19),
+                    1 => rotated.rotate_right(31),
+                    2 => rotated.rotate_left(61),
+                    3 => rotated.rotate_left(31),
+                    _ => rotated.rotate_left(19),
+                };
+        }
+
+        let mut decryption_keys = [0_u128; 17];
+        decryption_keys[0] = encryption_keys[rounds];
+        for i in 1..rounds {
+            decryption_keys[i] = diffuse(encryption_keys[rounds - i]);
+        }
+        decryption_keys[rounds] = encryption_keys[0];
+
+        padded.zeroize();
+        w.zeroize();
+        Self {
+            encryption_keys,
+            decryption_keys,
+            rounds,
+        }
+    }
+
+    fn crypt(&self, block: Block<16>, keys: &[u128; 17]) -> Block<16> {
+        let mut p = u128::from_be_bytes(block);
+        for (i, &key) in keys[..(self.rounds - 1)].iter().enumerate() {
+            p = if i % 2 == 0 { fo(p, key) } else { fe(p, key) };
+        }
+        let c = substitute(p ^ keys[self.rounds - 1], [&SB3, &SB4, &SB1, &SB2]) ^ keys[self.rounds];
+        c.to_be_bytes()
+    }
+}
+
+/// The odd round function, with substitution layer type 1.
+fn fo(d: u128, key: u128) -> u128 {
+    diffuse(substitute(d ^ key, [&SB1, &SB2, &SB3, &SB4]))
+}
+
+/// The even round function, with substitution layer type 2.
+fn fe(d: u128, key: u128) -> u128 {
+    diffuse(substitute(d ^ key, [&SB3, &SB4, &SB1, &SB2]))
+}
+
+fn substitute(x: u128, sboxes: [&[u8; 256]; 4]) -> u128 {
+    let mut bytes = x.to_be_bytes();
+    for (i, b) in bytes.iter_mut().enumerate() {
+        *b = sboxes[i % 4][*b as usize];
+    }
+    u128::from_be_bytes(bytes)
+}
+
+fn diffuse(x: u128) -> u128 {
+    let x = x.to_be_bytes();
+    let y = DIFFUSION.map(|row| row.iter().fold(0, |y, &j| y ^ x[j]));
+    u128::from_be_bytes(y)
+}
+
+/// Accepts 128-, 192- and 256-bit keys.
+impl TryFrom<&[u8]> for Aria {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        match key.len() {
+            16 | 24 | 32 => Ok(Self::expand_key(key)),
+            actual => Err(KeyError::InvalidLength {
+                expected: "16, 24 or 32 bytes",
+                actual,
+            }),
+        }
+    }
+}
+
+impl From<Key128> for Aria {
+    fn from(key: Key128) -> Self {
+        Self::expand_key(&key.0)
+    }
+}
+
+impl From<Key192> for Aria {
+    fn from(key: Key192) -> Self {
+        Self::expand_key(&key.0)
+    }
+}
+
+impl From<Key256> for Aria {
+    fn from(key: Key256) -> Self {
+        Self::expand_key(&key.0)
+    }
+}
+
+impl Drop for Aria {
+    fn drop(&mut self) {
+        self.encryption_keys.zeroize();
+        self.decryption_keys.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Aria {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Aria")
+            .field("rounds", &self.rounds)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BlockCipher<16> for Aria {
+    fn encrypt(&self, plaintext: Block<16>) -> Block<16> {
+        self.crypt(plaintext, &self.encryption_keys)
+    }
+
+    fn decrypt(&self, ciphertext: Block<16>) -> Block<16> {
+        self.crypt(ciphertext, &self.decryption_keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::ManuallyDrop;
+
+    use super::*;
+
+    #[test]
+    fn sboxes() {
+        // RFC 5794, section 2.4.2
+        assert_eq!(SB2[0x00..0x04], [0xe2, 0x4e, 0x54, 0xfc]);
+        assert_eq!(SB4[0x00..0x04], [0x30, 0x68, 0x99, 0x1b]);
+    }
+
+    #[test]
+    fn diffusion_layer_is_an_involution() {
+        let x = 0x00112233445566778899aabbccddeeff;
+        assert_ne!(diffuse(x), x);
+        assert_eq!(diffuse(diffuse(x)), x);
+    }
+
+    #[test]
+    fn key_length_is_validated() {
+        assert!(matches!(
+            Aria::try_from(&[0; 8][..]),
+            Err(KeyError::InvalidLength { actual: 8, .. })
+        ));
+    }
+
+    #[test]
+    fn subkeys_are_zeroized_on_drop() {
+        let mut cipher = ManuallyDrop::new(Aria::new(&[1; 24]));
+        unsafe { ManuallyDrop::drop(&mut cipher) };
+        assert_eq!(cipher.encryption_keys, [0; 17]);
+        assert_eq!(cipher.decryption_keys, [0; 17]);
+    }
+
+    #[test]
+    fn debug_output_is_redacted() {
+        assert_eq!(format!("{:?}", Aria::new(&[0; 32])), "Aria { rounds: 16, .. }");
+    }
+}
@@ -0,0 +1,373 @@
+use crate::aead::{split_tag, AuthenticationError};
+use crate::key::{decode_base64, decode_hex, fixed_length, KeyError};
+use crate::zeroize::Zeroize;
+
+use super::permute;
+
+/// The parameters that distinguish the AEAD variants. The variants of the Ascon v1.2 submission
+/// read words in big-endian order, while SP 800-232 reads them in little-endian order, which also
+/// moves the padding and domain separation bits.
+struct Variant {
+    iv: u64,
+    rate: usize,
+    rounds: usize,
+    little_endian: bool,
+}
+
+/// Ascon-128 from the Ascon v1.2 submission, with a 64-bit rate.
+const ASCON_128: Variant = Variant {
+    iv: 0x80400c0600000000,
+    rate: 8,
+    rounds: 6,
+    little_endian: false,
+};
+
+/// Ascon-128a from the Ascon v1.2 submission, with a 128-bit rate.
+const ASCON_128A: Variant = Variant {
+    iv: 0x80800c0800000000,
+    rate: 16,
+    rounds: 8,
+    little_endian: false,
+};
+
+/// Ascon-AEAD128 from SP 800-232.
+const ASCON_AEAD128: Variant = Variant {
+    iv: 0x00001000808c0001,
+    rate: 16,
+    rounds: 8,
+    little_endian: true,
+};
+
+impl Variant {
+    fn load(&self, bytes: &[u8]) -> u64 {
+        let bytes = bytes.try_into().unwrap();
+        if self.little_endian {
+            u64::from_le_bytes(bytes)
+        } else {
+            u64::from_be_bytes(bytes)
+        }
+    }
+
+    fn store(&self, word: u64) -> [u8; 8] {
+        if self.little_endian {
+            word.to_le_bytes()
+        } else {
+            word.to_be_bytes()
+        }
+    }
+
+    /// The position of byte `i` of the state as (word, shift).
+    fn position(&self, i: usize) -> (usize, usize) {
+        let shift = if self.little_endian {
+            8 * (i % 8)
+        } else {
+            56 - 8 * (i % 8)
+        };
+        (i / 8, shift)
+    }
+
+    fn state_byte(&self, state: &[u64; 5], i: usize) -> u8 {
+        let (word, shift) = self.position(i);
+        (state[word] >> shift) as u8
+    }
+
+    fn xor_state_byte(&self, state: &mut [u64; 5], i: usize, b: u8) {
+        let (word, shift) = self.position(i);
+        state[word] ^= (b as u64) << shift;
+    }
+
+    /// Pads a block by setting the bit that follows `len` bytes of data.
+    fn pad(&self, state: &mut [u64; 5], len: usize) {
+        self.xor_state_byte(state, len, if self.little_endian { 0x01 } else { 0x80 });
+    }
+
+    /// Separates the associated data from the message by flipping the last bit of the state.
+    fn separate_domains(&self, state: &mut [u64; 5]) {
+        self.xor_state_byte(state, 39, if self.little_endian { 0x80 } else { 0x01 });
+    }
+
+    fn initialize(&self, key: &[u8; 16], nonce: &[u8; 16], associated_data: &[u8]) -> [u64; 5] {
+        let mut k = [self.load(&key[..8]), self.load(&key[8..])];
+        let mut state = [
+            self.iv,
+            k[0],
+            k[1],
+            self.load(&nonce[..8]),
+            self.load(&nonce[8..]),
+        ];
+        permute(&mut state, 12);
+        state[3] ^= k[0];
+        state[4] ^= k[1];
+        k.zeroize();
+
+        // empty associated data is skipped entirely rather than padded to a block
+        if !associated_data.is_empty() {
+            let blocks = associated_data.chunks_exact(self.rate);
+            let last = blocks.remainder();
+            for block in blocks {
+                self.absorb(&mut state, block);
+                permute(&mut state, self.rounds);
+            }
+            self.absorb(&mut state, last);
+            self.pad(&mut state, last.len());
+            permute(&mut state, self.rounds);
+        }
+        self.separate_domains(&mut state);
+        state
+    }
+
+    fn absorb(&self, state: &mut [u64; 5], data: &[u8]) {
+        for (i, &b) in data.iter().enumerate() {
+            self.xor_state_byte(state, i, b);
+        }
+    }
+
+    /// Encrypts the message in `buffer`, padding the last (possibly empty) block in the state.
+    fn encrypt(&self, state: &mut [u64; 5], buffer: &mut [u8]) {
+        let mut blocks = buffer.chunks_exact_mut(self.rate);
+        for block in &mut blocks {
+            self.encrypt_block(state, block);
+            permute(state, self.rounds);
+        }
+        let last = blocks.into_remainder();
+        self.encrypt_block(state, last);
+        self.pad(state, last.len());
+    }
+
+    fn encrypt_block(&self, state: &mut [u64; 5], block: &mut [u8]) {
+        for (i, b) in block.iter_mut().enumerate() {
+            self.xor_state_byte(state, i, *b);
+            *b = self.state_byte(state, i);
+        }
+    }
+
+    /// Decrypts the ciphertext in `buffer`. The ciphertext replaces the rate part of the state,
+    /// so the state ends up the same as after encrypting the plaintext.
+    fn decrypt(&self, state: &mut [u64; 5], buffer: &mut [u8]) {
+        let mut blocks = buffer.chunks_exact_mut(self.rate);
+        for block in &mut blocks {
+            self.decrypt_block(state, block);
+            permute(state, self.rounds);
+        }
+        let last = blocks.into_remainder();
+        self.decrypt_block(state, last);
+        self.pad(state, last.len());
+    }
+
+    fn decrypt_block(&self, state: &mut [u64; 5], block: &mut [u8]) {
+        for (i, b) in block.iter_mut().enumerate() {
+            let plaintext = self.state_byte(state, i) ^ *b;
+            self.xor_state_byte(state, i, plaintext);
+            *b = plaintext;
+        }
+    }
+
+    fn finalize(&self, state: &mut [u64; 5], key: &[u8; 16]) -> [u8; 16] {
+        let mut k = [self.load(&key[..8]), self.load(&key[8..])];
+        state[self.rate / 8] ^= k[0];
+        state[self.rate / 8 + 1] ^= k[1];
+        permute(state, 12);
+
+        let mut tag = [0_u8; 16];
+        tag[..8].copy_from_slice(&self.store(state[3] ^ k[0]));
+        tag[8..].copy_from_slice(&self.store(state[4] ^ k[1]));
+        k.zeroize();
+        state.zeroize();
+        tag
+    }
+
+    fn seal(
+        &self,
+        key: &[u8; 16],
+        nonce: &[u8; 16],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> [u8; 16] {
+        let mut state = self.initialize(key, nonce, associated_data);
+        self.encrypt(&mut state, buffer);
+        self.finalize(&mut state, key)
+    }
+
+    fn open(
+        &self,
+        key: &[u8; 16],
+        nonce: &[u8; 16],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        tag: &[u8; 16],
+    ) -> Result<(), AuthenticationError> {
+        let mut state = self.initialize(key, nonce, associated_data);
+        self.decrypt(&mut state, buffer);
+        if !crate::ct::bytes_eq(&self.finalize(&mut state, key), tag) {
+            // encrypting the released plaintext again restores the ciphertext
+            self.seal(key, nonce, associated_data, buffer);
+            return Err(AuthenticationError);
+        }
+        Ok(())
+    }
+}
+
+macro_rules! ascon_aead {
+    ($(#[$doc:meta])* $name:ident, $variant:expr) => {
+        $(#[$doc])*
+        pub struct $name {
+            key: [u8; 16],
+        }
+
+        impl $name {
+            pub fn new(key: &[u8; 16]) -> Self {
+                Self { key: *key }
+            }
+
+            pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+                Self::try_from(decode_hex(hex)?.as_slice())
+            }
+
+            pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+                Self::try_from(decode_base64(base64)?.as_slice())
+            }
+
+            /// Returns the ciphertext followed by the 16 byte tag.
+            pub fn encrypt(&self, nonce: &[u8; 16], associated_data: &[u8], plaintext: &[u8]) -> Vec<u8> {
+                let mut output = plaintext.to_vec();
+                let tag = self.encrypt_in_place_detached(nonce, associated_data, &mut output);
+                output.extend_from_slice(&tag);
+                output
+            }
+
+            /// Expects the ciphertext followed by the 16 byte tag, as returned by `encrypt`.
+            pub fn decrypt(
+                &self,
+                nonce: &[u8; 16],
+                associated_data: &[u8],
+                ciphertext: &[u8],
+            ) -> Result<Vec<u8>, AuthenticationError> {
+                let (ciphertext, tag) = split_tag(ciphertext)?;
+                let mut output = ciphertext.to_vec();
+                self.decrypt_in_place_detached(nonce, associated_data, &mut output, tag)?;
+                Ok(output)
+            }
+
+            pub fn encrypt_in_place_detached(
+                &self,
+                nonce: &[u8; 16],
+                associated_data: &[u8],
+                buffer: &mut [u8],
+            ) -> [u8; 16] {
+                $variant.seal(&self.key, nonce, associated_data, buffer)
+            }
+
+            /// Leaves `buffer` unchanged if the tag is invalid.
+            pub fn decrypt_in_place_detached(
+                &self,
+                nonce: &[u8; 16],
+                associated_data: &[u8],
+                buffer: &mut [u8],
+                tag: &[u8; 16],
+            ) -> Result<(), AuthenticationError> {
+                $variant.open(&self.key, nonce, associated_data, buffer, tag)
+            }
+        }
+
+        impl TryFrom<&[u8]> for $name {
+            type Error = KeyError;
+
+            fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+                Ok(Self {
+                    key: fixed_length(key, "16 bytes")?,
+                })
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                self.key.zeroize();
+            }
+        }
+
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!($name)).finish_non_exhaustive()
+            }
+        }
+    };
+}
+
+ascon_aead!(
+    /// Ascon-AEAD128 as standardized in NIST SP 800-232, with a 128-bit key, nonce and tag. It is
+    /// the standardized form of Ascon-128a, but not compatible with it, since words are read in
+    /// little-endian order. A nonce must never be reused with the same key.
+    AsconAead128,
+    ASCON_AEAD128
+);
+
+ascon_aead!(
+    /// Ascon-128 from the Ascon v1.2 submission to the NIST lightweight cryptography competition,
+    /// which was the primary choice of the CAESAR portfolio. Prefer `AsconAead128` for new
+    /// protocols.
+    Ascon128,
+    ASCON_128
+);
+
+ascon_aead!(
+    /// Ascon-128a from the Ascon v1.2 submission, which processes twice as much data per
+    /// permutation as Ascon-128. Prefer `AsconAead128` for new protocols.
+    Ascon128a,
+    ASCON_128A
+);
+
+#[cfg(test)]
+mod tests {
+    use std::mem::ManuallyDrop;
+
+    use super::*;
+
+    #[test]
+    fn byte_positions_follow_the_word_order() {
+        let mut state = [0; 5];
+        ASCON_128.xor_state_byte(&mut state, 9, 0xab);
+        assert_eq!(state[1], 0x00ab000000000000);
+        assert_eq!(ASCON_128.state_byte(&state, 9), 0xab);
+
+        let mut state = [0; 5];
+        ASCON_AEAD128.xor_state_byte(&mut state, 9, 0xab);
+        assert_eq!(state[1], 0x000000000000ab00);
+        assert_eq!(ASCON_AEAD128.state_byte(&state, 9), 0xab);
+    }
+
+    #[test]
+    fn domain_separation_flips_the_last_bit() {
+        let mut state = [0; 5];
+        ASCON_128.separate_domains(&mut state);
+        assert_eq!(state, [0, 0, 0, 0, 1]);
+
+        let mut state = [0; 5];
+        ASCON_AEAD128.separate_domains(&mut state);
+        assert_eq!(state, [0, 0, 0, 0, 1 << 63]);
+    }
+
+    #[test]
+    fn too_short_ciphertext_is_rejected() {
+        let aead = AsconAead128::new(&[0; 16]);
+        assert_eq!(
+            aead.decrypt(&[0; 16], b"", &[0; 15]),
+            Err(AuthenticationError)
+        );
+    }
+
+    #[test]
+    fn key_is_zeroized_on_drop() {
+        let mut aead = ManuallyDrop::new(Ascon128a::new(&[1; 16]));
+        unsafe { ManuallyDrop::drop(&mut aead) };
+        assert_eq!(aead.key, [0; 16]);
+    }
+
+    #[test]
+    fn debug_output_is_redacted() {
+        assert_eq!(
+            format!("{:?}", AsconAead128::new(&[0; 16])),
+            "AsconAead128 { .. }"
+        );
+        assert_eq!(format!("{:?}", Ascon128::new(&[0; 16])), "Ascon128 { .. }");
+    }
+}
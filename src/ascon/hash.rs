@@ -0,0 +1,240 @@
+use crate::sha::Hash256;
+use crate::zeroize::Zeroize;
+use crate::Digest;
+
+use super::permute;
+
+const HASH256_IV: u64 = 0x0000080100cc0002;
+const XOF128_IV: u64 = 0x0000080000cc0003;
+
+/// The rate of the hash functions, which absorb and squeeze one word at a time.
+const RATE: usize = 8;
+
+/// The absorbing phase shared by Ascon-Hash256 and Ascon-XOF128, which only differ in the
+/// initialization value and in the output length.
+#[derive(Clone)]
+struct Sponge {
+    state: [u64; 5],
+    buffer: [u8; RATE],
+    buffer_len: usize,
+}
+
+impl Sponge {
+    fn new(iv: u64) -> Self {
+        let mut state = [iv, 0, 0, 0, 0];
+        permute(&mut state, 12);
+        Self {
+            state,
+            buffer: [0; RATE],
+            buffer_len: 0,
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        if self.buffer_len > 0 {
+            let n = data.len().min(RATE - self.buffer_len);
+            self.buffer[self.buffer_len..][..n].copy_from_slice(&data[..n]);
+            self.buffer_len += n;
+            data = &data[n..];
+            if self.buffer_len < RATE {
+                return;
+            }
+            let block = self.buffer;
+            self.absorb(&block);
+            self.buffer_len = 0;
+        }
+
+        let blocks = data.chunks_exact(RATE);
+        let rest = blocks.remainder();
+        for block in blocks {
+            self.absorb(block.try_into().unwrap());
+        }
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffer_len = rest.len();
+    }
+
+    fn absorb(&mut self, block: &[u8; RATE]) {
+        self.state[0] ^= u64::from_le_bytes(*block);
+        permute(&mut self.state, 12);
+    }
+
+    fn finalize(mut self) -> AsconXofReader {
+        let mut block = [0_u8; RATE];
+        block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        block[self.buffer_len] = 0x01;
+        self.absorb(&block);
+        block.zeroize();
+
+        AsconXofReader {
+            state: self.state,
+            position: 0,
+        }
+    }
+}
+
+impl Drop for Sponge {
+    fn drop(&mut self) {
+        self.state.zeroize();
+        self.buffer.zeroize();
+    }
+}
+
+#[derive(Clone)]
+pub struct AsconHash256 {
+    sponge: Sponge,
+}
+
+impl AsconHash256 {
+    pub fn new() -> Self {
+        Self {
+            sponge: Sponge::new(HASH256_IV),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.sponge.update(data);
+    }
+
+    pub fn finalize(self) -> Hash256 {
+        let mut output = [0_u8; 32];
+        self.sponge.finalize().read(&mut output);
+        Hash256::from(output)
+    }
+}
+
+impl Default for AsconHash256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for AsconHash256 {
+    type Output = Hash256;
+
+    const OUTPUT_SIZE: usize = 32;
+    const BLOCK_SIZE: usize = RATE;
+    const NAME: &'static str = "Ascon-Hash256";
+    const OID: Option<&'static [u8]> = None;
+
+    fn new() -> Self {
+        Self::new()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.update(data);
+    }
+
+    fn finalize(self) -> Self::Output {
+        self.finalize()
+    }
+}
+
+impl std::fmt::Debug for AsconHash256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsconHash256").finish_non_exhaustive()
+    }
+}
+
+/// Ascon-XOF128 from SP 800-232, an extendable-output function with 128-bit security. The
+/// output is read from the `AsconXofReader` returned by `finalize`.
+#[derive(Clone)]
+pub struct AsconXof128 {
+    sponge: Sponge,
+}
+
+impl AsconXof128 {
+    pub fn new() -> Self {
+        Self {
+            sponge: Sponge::new(XOF128_IV),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.sponge.update(data);
+    }
+
+    pub fn finalize(self) -> AsconXofReader {
+        self.sponge.finalize()
+    }
+}
+
+impl Default for AsconXof128 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for AsconXof128 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsconXof128").finish_non_exhaustive()
+    }
+}
+
+pub struct AsconXofReader {
+    state: [u64; 5],
+    /// The number of bytes of the first word that have been read.
+    position: usize,
+}
+
+impl AsconXofReader {
+    pub fn read(&mut self, output: &mut [u8]) {
+        for b in output {
+            if self.position == RATE {
+                permute(&mut self.state, 12);
+                self.position = 0;
+            }
+            *b = (self.state[0] >> (8 * self.position)) as u8;
+            self.position += 1;
+        }
+    }
+}
+
+impl Drop for AsconXofReader {
+    fn drop(&mut self) {
+        self.state.zeroize();
+    }
+}
+
+impl std::fmt::Debug for AsconXofReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsconXofReader").finish_non_exhaustive()
+    }
+}
+
+pub fn ascon_hash256(message: &[u8]) -> Hash256 {
+    let mut hasher = AsconHash256::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+pub fn ascon_xof128(message: &[u8], output: &mut [u8]) {
+    let mut xof = AsconXof128::new();
+    xof.update(message);
+    xof.finalize().read(output);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn updates_are_buffered_until_a_block_is_full() {
+        let mut one_block = AsconXof128::new();
+        one_block.update(&[7; RATE]);
+        let mut split = AsconXof128::new();
+        split.update(&[7; 3]);
+        split.update(&[7; RATE - 3]);
+
+        let (mut a, mut b) = ([0; 16], [0; 16]);
+        one_block.finalize().read(&mut a);
+        split.finalize().read(&mut b);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn state_is_zeroized_on_drop() {
+        let mut reader = std::mem::ManuallyDrop::new(AsconXof128::new().finalize());
+        unsafe { std::mem::ManuallyDrop::drop(&mut reader) };
+        assert_eq!(reader.state, [0; 5]);
+    }
+}
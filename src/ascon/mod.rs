@@ -0,0 +1,80 @@
+//! Ascon by Dobraunig, Eichlseder, Mendel and Schläffer, the lightweight AEAD and hash family
+//! standardized in NIST SP 800-232. Every mode is a sponge over the same 320-bit permutation,
+//! which is exposed as [`permute`] for other constructions.
+
+mod aead;
+mod hash;
+
+pub use aead::{Ascon128, Ascon128a, AsconAead128};
+pub use hash::{ascon_hash256, ascon_xof128, AsconHash256, AsconXof128, AsconXofReader};
+
+/// The round constants of the 12 rounds. A permutation with fewer rounds uses the last ones.
+const ROUND_CONSTANTS: [u64; 12] = [
+    0xf0, 0xe1, 0xd2, 0xc3, 0xb4, 0xa5, 0x96, 0x87, 0x78, 0x69, 0x5a, 0x4b,
+];
+
+/// The Ascon permutation p^`rounds` on five 64-bit words, where `rounds` is at most 12. The modes
+/// use 12 rounds for initialization and finalization and 6 or 8 rounds in between.
+pub fn permute(state: &mut [u64; 5], rounds: usize) {
+    assert!(rounds <= 12, "Ascon has at most 12 rounds");
+    for &c in &ROUND_CONSTANTS[(12 - rounds)..] {
+        round(state, c);
+    }
+}
+
+fn round(s: &mut [u64; 5], c: u64) {
+    let [mut x0, mut x1, mut x2, mut x3, mut x4] = *s;
+
+    // the 5-bit S-box applied to the bits at the same position in each word
+    x2 ^= c;
+    x0 ^= x4;
+    x4 ^= x3;
+    x2 ^= x1;
+    let t = [!x0 & x1, !x1 & x2, !x2 & x3, !x3 & x4, !x4 & x0];
+    x0 ^= t[1];
+    x1 ^= t[2];
+    x2 ^= t[3];
+    x3 ^= t[4];
+    x4 ^= t[0];
+    x1 ^= x0;
+    x0 ^= x4;
+    x3 ^= x2;
+    x2 = !x2;
+
+    // the linear diffusion layer, with different rotations for each word
+    *s = [
+        x0 ^ x0.rotate_right(19) ^ x0.rotate_right(28),
+        x1 ^ x1.rotate_right(61) ^ x1.rotate_right(39),
+        x2 ^ x2.rotate_right(1) ^ x2.rotate_right(6),
+        x3 ^ x3.rotate_right(10) ^ x3.rotate_right(17),
+        x4 ^ x4.rotate_right(7) ^ x4.rotate_right(41),
+    ];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permutation_of_hash_initialization() {
+        // the initial state of Ascon-Hash256, which the reference implementation precomputes
+        let mut state = [0x0000080100cc0002, 0, 0, 0, 0];
+        permute(&mut state, 12);
+        assert_eq!(
+            state,
+            [
+                0x9b1e5494e934d681,
+                0x4bc3a01e333751d2,
+                0xae65396c6b34b81a,
+                0x3c7fd4a4d56a4db3,
+                0x1a5c464906c5976d
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 12 rounds")]
+    fn too_many_rounds_panics() {
+        permute(&mut [0; 5], 13);
+    }
+}
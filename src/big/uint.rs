@@ -3,6 +3,8 @@ use std::{
     ops::{AddAssign, RemAssign, ShlAssign, ShrAssign, Sub, SubAssign},
 };
 
+use crate::zeroize::Zeroize;
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct BigUint {
     bytes: Vec<u8>,
@@ -113,6 +115,12 @@ impl BigUint {
     }
 }
 
+impl Zeroize for BigUint {
+    fn zeroize(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
 impl From<u128> for BigUint {
     fn from(value: u128) -> Self {
         Self::from_u128(value)
@@ -405,4 +413,11 @@ mod tests {
             BigUint::from_be_bytes([13, 12, 10, 255])
         );
     }
+
+    #[test]
+    fn zeroize_clears_the_number() {
+        let mut a = BigUint::from(0x1234_5678_9012_3456_7890_u128);
+        a.zeroize();
+        assert_eq!(a, BigUint::from(0));
+    }
 }
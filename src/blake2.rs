@@ -0,0 +1,536 @@
+//! BLAKE2b and BLAKE2s by Aumasson, Neves, Wilcox-O'Hearn and Winnerlein, as specified in
+//! RFC 7693. BLAKE2b works on 64-bit words and BLAKE2s on 32-bit words, otherwise they only
+//! differ in their constants. The output length `N` is a type parameter, and keys, salts,
+//! personalization strings and the parameters for tree hashing are set at construction.
+
+use crate::key::{check_length, KeyError};
+use crate::sha::HashValue;
+use crate::zeroize::Zeroize;
+use crate::Digest;
+
+/// The permutations of the message words used by the rounds, in turn.
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// The initial hash of SHA-512.
+const IV_B: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+/// The initial hash of SHA-256.
+const IV_S: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// The parameter block of BLAKE2b, apart from the output and key lengths. The defaults are
+/// those of sequential hashing without salt and personalization.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blake2bParams {
+    pub salt: [u8; 16],
+    pub personalization: [u8; 16],
+    /// The maximal number of children of a node in the tree, or 0 for no limit.
+    pub fanout: u8,
+    /// The maximal depth of the tree, or 255 for no limit.
+    pub max_depth: u8,
+    /// The length of the leaves in bytes, or 0 for no limit.
+    pub leaf_length: u32,
+    /// The position of the node in its level, starting from 0.
+    pub node_offset: u64,
+    /// The level of the node in the tree, 0 for the leaves.
+    pub node_depth: u8,
+    /// The length of the hashes of the children that an inner node hashes.
+    pub inner_length: u8,
+    /// Whether this is the last node of its level, which changes the final compression.
+    pub last_node: bool,
+}
+
+impl Default for Blake2bParams {
+    fn default() -> Self {
+        Self {
+            salt: [0; 16],
+            personalization: [0; 16],
+            fanout: 1,
+            max_depth: 1,
+            leaf_length: 0,
+            node_offset: 0,
+            node_depth: 0,
+            inner_length: 0,
+            last_node: false,
+        }
+    }
+}
+
+impl Blake2bParams {
+    fn words(&self, output_len: usize, key_len: usize) -> Result<[u64; 8], KeyError> {
+        if self.max_depth == 0 {
+            return Err(KeyError::InvalidParameter("BLAKE2 depth must be positive"));
+        }
+
+        let mut block = [0_u8; 64];
+        block[0] = output_len as u8;
+        block[1] = key_len as u8;
+        block[2] = self.fanout;
+        block[3] = self.max_depth;
+        block[4..8].copy_from_slice(&self.leaf_length.to_le_bytes());
+        block[8..16].copy_from_slice(&self.node_offset.to_le_bytes());
+        block[16] = self.node_depth;
+        block[17] = self.inner_length;
+        block[32..48].copy_from_slice(&self.salt);
+        block[48..64].copy_from_slice(&self.personalization);
+        Ok(std::array::from_fn(|i| {
+            u64::from_le_bytes(block[(8 * i)..][..8].try_into().unwrap())
+        }))
+    }
+}
+
+/// The parameter block of BLAKE2s, apart from the output and key lengths. The defaults are
+/// those of sequential hashing without salt and personalization.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blake2sParams {
+    pub salt: [u8; 8],
+    pub personalization: [u8; 8],
+    /// The maximal number of children of a node in the tree, or 0 for no limit.
+    pub fanout: u8,
+    /// The maximal depth of the tree, or 255 for no limit.
+    pub max_depth: u8,
+    /// The length of the leaves in bytes, or 0 for no limit.
+    pub leaf_length: u32,
+    /// The position of the node in its level, starting from 0. It must fit in 48 bits.
+    pub node_offset: u64,
+    /// The level of the node in the tree, 0 for the leaves.
+    pub node_depth: u8,
+    /// The length of the hashes of the children that an inner node hashes.
+    pub inner_length: u8,
+    /// Whether this is the last node of its level, which changes the final compression.
+    pub last_node: bool,
+}
+
+impl Default for Blake2sParams {
+    fn default() -> Self {
+        Self {
+            salt: [0; 8],
+            personalization: [0; 8],
+            fanout: 1,
+            max_depth: 1,
+            leaf_length: 0,
+            node_offset: 0,
+            node_depth: 0,
+            inner_length: 0,
+            last_node: false,
+        }
+    }
+}
+
+impl Blake2sParams {
+    fn words(&self, output_len: usize, key_len: usize) -> Result<[u32; 8], KeyError> {
+        if self.max_depth == 0 {
+            return Err(KeyError::InvalidParameter("BLAKE2 depth must be positive"));
+        }
+        if self.node_offset >= 1 << 48 {
+            return Err(KeyError::InvalidParameter(
+                "BLAKE2s node offset must fit in 48 bits",
+            ));
+        }
+
+        let mut block = [0_u8; 32];
+        block[0] = output_len as u8;
+        block[1] = key_len as u8;
+        block[2] = self.fanout;
+        block[3] = self.max_depth;
+        block[4..8].copy_from_slice(&self.leaf_length.to_le_bytes());
+        block[8..14].copy_from_slice(&self.node_offset.to_le_bytes()[..6]);
+        block[14] = self.node_depth;
+        block[15] = self.inner_length;
+        block[16..24].copy_from_slice(&self.salt);
+        block[24..32].copy_from_slice(&self.personalization);
+        Ok(std::array::from_fn(|i| {
+            u32::from_le_bytes(block[(4 * i)..][..4].try_into().unwrap())
+        }))
+    }
+}
+
+macro_rules! blake2 {
+    (
+        $(#[$doc:meta])*
+        $name:ident,
+        $params:ident,
+        $compress:ident,
+        $word:ty,
+        $iv:expr,
+        $rounds:literal,
+        $rotations:expr,
+        $max_len:literal,
+        $key_length:literal
+    ) => {
+        $(#[$doc])*
+        #[derive(Clone)]
+        pub struct $name<const N: usize> {
+            hash: [$word; 8],
+            /// The hash after the parameter block, which `reset` restores.
+            initial_hash: [$word; 8],
+            /// The key padded to a block, which is hashed before the message.
+            key_block: Option<[u8; 16 * size_of::<$word>()]>,
+            last_node: bool,
+            buffer: [u8; 16 * size_of::<$word>()],
+            buffer_len: usize,
+            /// The number of bytes compressed so far.
+            counter: u128,
+        }
+
+        impl<const N: usize> $name<N> {
+            const BLOCK_SIZE: usize = 16 * size_of::<$word>();
+
+            pub fn new() -> Self {
+                Self::with_params(&[], &$params::default()).unwrap()
+            }
+
+            /// Starts a keyed hash, which is a MAC. The key is between 1 and
+            #[doc = concat!(stringify!($max_len), " bytes long.")]
+            pub fn with_key(key: &[u8]) -> Result<Self, KeyError> {
+                check_length(key, 1..=$max_len, $key_length)?;
+                Self::with_params(key, &$params::default())
+            }
+
+            /// Starts a hash with the given parameters and an optional key, which is at most
+            #[doc = concat!(stringify!($max_len), " bytes long.")]
+            pub fn with_params(key: &[u8], params: &$params) -> Result<Self, KeyError> {
+                const { assert!(N >= 1 && N <= $max_len, "invalid BLAKE2 output length") };
+                check_length(key, 0..=$max_len, $key_length)?;
+
+                let words = params.words(N, key.len())?;
+                let initial_hash: [$word; 8] = std::array::from_fn(|i| $iv[i] ^ words[i]);
+                let key_block = (!key.is_empty()).then(|| {
+                    let mut block = [0; 16 * size_of::<$word>()];
+                    block[..key.len()].copy_from_slice(key);
+                    block
+                });
+
+                let mut hasher = Self {
+                    hash: initial_hash,
+                    initial_hash,
+                    key_block,
+                    last_node: params.last_node,
+                    buffer: [0; 16 * size_of::<$word>()],
+                    buffer_len: 0,
+                    counter: 0,
+                };
+                hasher.reset();
+                Ok(hasher)
+            }
+
+            pub fn update(&mut self, mut data: &[u8]) {
+                while !data.is_empty() {
+                    // a full buffer is only compressed once more data follows, since the last
+                    // block is compressed differently
+                    if self.buffer_len == Self::BLOCK_SIZE {
+                        self.counter += Self::BLOCK_SIZE as u128;
+                        $compress(&mut self.hash, &self.buffer, self.counter, false, false);
+                        self.buffer_len = 0;
+                    }
+
+                    if self.buffer_len == 0 {
+                        while data.len() > Self::BLOCK_SIZE {
+                            let (block, rest) = data.split_at(Self::BLOCK_SIZE);
+                            self.counter += Self::BLOCK_SIZE as u128;
+                            $compress(&mut self.hash, block.try_into().unwrap(), self.counter, false, false);
+                            data = rest;
+                        }
+                    }
+
+                    let n = data.len().min(Self::BLOCK_SIZE - self.buffer_len);
+                    self.buffer[self.buffer_len..][..n].copy_from_slice(&data[..n]);
+                    self.buffer_len += n;
+                    data = &data[n..];
+                }
+            }
+
+            pub fn finalize(mut self) -> HashValue<N> {
+                self.counter += self.buffer_len as u128;
+                self.buffer[self.buffer_len..].fill(0);
+                $compress(&mut self.hash, &self.buffer, self.counter, true, self.last_node);
+
+                let mut output = [0_u8; N];
+                for (chunk, word) in output.chunks_mut(size_of::<$word>()).zip(self.hash) {
+                    chunk.copy_from_slice(&word.to_le_bytes()[..chunk.len()]);
+                }
+                output.into()
+            }
+
+            /// Keeps the key and parameters.
+            pub fn reset(&mut self) {
+                self.hash = self.initial_hash;
+                self.counter = 0;
+                match self.key_block {
+                    Some(block) => {
+                        self.buffer = block;
+                        self.buffer_len = Self::BLOCK_SIZE;
+                    }
+                    None => {
+                        self.buffer.zeroize();
+                        self.buffer_len = 0;
+                    }
+                }
+            }
+        }
+
+        impl<const N: usize> Default for $name<N> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<const N: usize> Drop for $name<N> {
+            fn drop(&mut self) {
+                self.hash.zeroize();
+                self.buffer.zeroize();
+                if let Some(block) = &mut self.key_block {
+                    block.zeroize();
+                }
+            }
+        }
+
+        impl<const N: usize> std::fmt::Debug for $name<N> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!($name)).finish_non_exhaustive()
+            }
+        }
+
+        fn $compress(
+            hash: &mut [$word; 8],
+            block: &[u8; 16 * size_of::<$word>()],
+            counter: u128,
+            last_block: bool,
+            last_node: bool,
+        ) {
+            let m: [$word; 16] = std::array::from_fn(|i| {
+                let bytes = &block[(i * size_of::<$word>())..][..size_of::<$word>()];
+                <$word>::from_le_bytes(bytes.try_into().unwrap())
+            });
+
+            let mut v = [0; 16];
+            v[..8].copy_from_slice(hash);
+            v[8..].copy_from_slice(&$iv);
+            v[12] ^= counter as $word;
+            v[13] ^= (counter >> <$word>::BITS) as $word;
+            if last_block {
+                v[14] = !v[14];
+            }
+            if last_node {
+                v[15] = !v[15];
+            }
+
+            let [r1, r2, r3, r4] = $rotations;
+            let mut g = |a: usize, b: usize, c: usize, d: usize, x: $word, y: $word| {
+                v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+                v[d] = (v[d] ^ v[a]).rotate_right(r1);
+                v[c] = v[c].wrapping_add(v[d]);
+                v[b] = (v[b] ^ v[c]).rotate_right(r2);
+                v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+                v[d] = (v[d] ^ v[a]).rotate_right(r3);
+                v[c] = v[c].wrapping_add(v[d]);
+                v[b] = (v[b] ^ v[c]).rotate_right(r4);
+            };
+
+            for round in 0..$rounds {
+                let s = &SIGMA[round % 10];
+                // the columns, then the diagonals
+                g(0, 4, 8, 12, m[s[0]], m[s[1]]);
+                g(1, 5, 9, 13, m[s[2]], m[s[3]]);
+                g(2, 6, 10, 14, m[s[4]], m[s[5]]);
+                g(3, 7, 11, 15, m[s[6]], m[s[7]]);
+                g(0, 5, 10, 15, m[s[8]], m[s[9]]);
+                g(1, 6, 11, 12, m[s[10]], m[s[11]]);
+                g(2, 7, 8, 13, m[s[12]], m[s[13]]);
+                g(3, 4, 9, 14, m[s[14]], m[s[15]]);
+            }
+
+            for i in 0..8 {
+                hash[i] ^= v[i] ^ v[i + 8];
+            }
+            v.zeroize();
+        }
+    };
+}
+
+blake2!(
+    /// BLAKE2b with an output of `N` bytes, between 1 and 64.
+    Blake2b,
+    Blake2bParams,
+    compress_b,
+    u64,
+    IV_B,
+    12,
+    [32, 24, 16, 63],
+    64,
+    "1 to 64 bytes"
+);
+blake2!(
+    /// BLAKE2s with an output of `N` bytes, between 1 and 32.
+    Blake2s,
+    Blake2sParams,
+    compress_s,
+    u32,
+    IV_S,
+    10,
+    [16, 12, 8, 7],
+    32,
+    "1 to 32 bytes"
+);
+
+pub type Blake2b160 = Blake2b<20>;
+pub type Blake2b256 = Blake2b<32>;
+pub type Blake2b384 = Blake2b<48>;
+pub type Blake2b512 = Blake2b<64>;
+pub type Blake2s128 = Blake2s<16>;
+pub type Blake2s160 = Blake2s<20>;
+pub type Blake2s224 = Blake2s<28>;
+pub type Blake2s256 = Blake2s<32>;
+
+macro_rules! blake2_digest {
+    ($name:ident, $output_size:literal, $block_size:literal, $algorithm:literal, $oid_arc:literal) => {
+        impl Digest for $name<$output_size> {
+            type Output = HashValue<$output_size>;
+
+            const OUTPUT_SIZE: usize = $output_size;
+            const BLOCK_SIZE: usize = $block_size;
+            const NAME: &'static str = $algorithm;
+            // 1.3.6.1.4.1.1722.12.2 from RFC 7693, then 1 for BLAKE2b or 2 for BLAKE2s and the
+            // output length in 32-bit words
+            const OID: Option<&'static [u8]> = Some(&[
+                0x2b,
+                0x06,
+                0x01,
+                0x04,
+                0x01,
+                0x8d,
+                0x3a,
+                0x0c,
+                0x02,
+                $oid_arc,
+                $output_size / 4,
+            ]);
+
+            fn new() -> Self {
+                Self::new()
+            }
+
+            fn update(&mut self, data: &[u8]) {
+                self.update(data);
+            }
+
+            fn finalize(self) -> Self::Output {
+                self.finalize()
+            }
+
+            fn reset(&mut self) {
+                self.reset();
+            }
+        }
+    };
+}
+
+blake2_digest!(Blake2b, 20, 128, "BLAKE2b-160", 1);
+blake2_digest!(Blake2b, 32, 128, "BLAKE2b-256", 1);
+blake2_digest!(Blake2b, 48, 128, "BLAKE2b-384", 1);
+blake2_digest!(Blake2b, 64, 128, "BLAKE2b-512", 1);
+blake2_digest!(Blake2s, 16, 64, "BLAKE2s-128", 2);
+blake2_digest!(Blake2s, 20, 64, "BLAKE2s-160", 2);
+blake2_digest!(Blake2s, 28, 64, "BLAKE2s-224", 2);
+blake2_digest!(Blake2s, 32, 64, "BLAKE2s-256", 2);
+
+pub fn blake2b<const N: usize>(message: &[u8]) -> HashValue<N> {
+    let mut hasher = Blake2b::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+pub fn blake2s<const N: usize>(message: &[u8]) -> HashValue<N> {
+    let mut hasher = Blake2s::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_length_is_validated() {
+        assert!(matches!(
+            Blake2b512::with_key(&[]),
+            Err(KeyError::InvalidLength {
+                expected: "1 to 64 bytes",
+                actual: 0
+            })
+        ));
+        assert!(matches!(
+            Blake2s256::with_key(&[0; 33]),
+            Err(KeyError::InvalidLength {
+                expected: "1 to 32 bytes",
+                actual: 33
+            })
+        ));
+        assert!(Blake2b512::with_key(&[0; 64]).is_ok());
+        assert!(Blake2s256::with_params(&[], &Blake2sParams::default()).is_ok());
+    }
+
+    #[test]
+    fn parameters_are_validated() {
+        let params = Blake2sParams {
+            node_offset: 1 << 48,
+            ..Default::default()
+        };
+        assert!(matches!(
+            Blake2s256::with_params(&[], &params),
+            Err(KeyError::InvalidParameter(_))
+        ));
+        let params = Blake2bParams {
+            max_depth: 0,
+            ..Default::default()
+        };
+        assert!(matches!(
+            Blake2b512::with_params(&[], &params),
+            Err(KeyError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn parameter_block_of_sequential_hashing() {
+        // the first word of the parameter block is 0x01010000 ^ (key length << 8) ^ output length
+        let words = Blake2bParams::default().words(64, 0).unwrap();
+        assert_eq!(words, [0x01010040, 0, 0, 0, 0, 0, 0, 0]);
+        let words = Blake2sParams::default().words(32, 16).unwrap();
+        assert_eq!(words, [0x01011020, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn key_is_zeroized_on_drop() {
+        let mut hasher = std::mem::ManuallyDrop::new(Blake2b512::with_key(&[1; 32]).unwrap());
+        unsafe { std::mem::ManuallyDrop::drop(&mut hasher) };
+        assert_eq!(hasher.key_block, Some([0; 128]));
+        assert_eq!(hasher.buffer, [0; 128]);
+    }
+
+    #[test]
+    fn debug_output_is_redacted() {
+        let hasher = Blake2s256::with_key(&[0; 32]).unwrap();
+        assert_eq!(format!("{hasher:?}"), "Blake2s { .. }");
+    }
+}
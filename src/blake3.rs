@@ -0,0 +1,596 @@
+use crate::ct;
+use crate::sha::Hash256;
+use crate::zeroize::Zeroize;
+use crate::Digest;
+
+const BLOCK_LEN: usize = 64;
+const CHUNK_LEN: usize = 1024;
+
+/// The number of chaining values on the stack of a hasher, enough for inputs of 2^64 bytes.
+const MAX_DEPTH: usize = 54;
+
+const MIN_PARALLEL_LEN: usize = 16 * CHUNK_LEN;
+
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+const PARENT: u32 = 1 << 2;
+const ROOT: u32 = 1 << 3;
+const KEYED_HASH: u32 = 1 << 4;
+const DERIVE_KEY_CONTEXT: u32 = 1 << 5;
+const DERIVE_KEY_MATERIAL: u32 = 1 << 6;
+
+/// The initial hash of SHA-256, which is the key of the unkeyed modes.
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const MSG_PERMUTATION: [usize; 16] = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+pub type ChainingValue = [u8; 32];
+
+fn g(v: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize, x: u32, y: u32) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(12);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(8);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(7);
+}
+
+/// Returns the whole state, of which the first half is the new chaining value.
+fn compress(
+    cv: &[u32; 8],
+    block: &[u8; BLOCK_LEN],
+    counter: u64,
+    len: usize,
+    flags: u32,
+) -> [u32; 16] {
+    let mut m: [u32; 16] =
+        std::array::from_fn(|i| u32::from_le_bytes(block[(4 * i)..][..4].try_into().unwrap()));
+
+    let mut v = [0; 16];
+    v[..8].copy_from_slice(cv);
+    v[8..12].copy_from_slice(&IV[..4]);
+    v[12] = counter as u32;
+    v[13] = (counter >> 32) as u32;
+    v[14] = len as u32;
+    v[15] = flags;
+
+    for round in 0..7 {
+        // the columns, then the diagonals
+        g(&mut v, 0, 4, 8, 12, m[0], m[1]);
+        g(&mut v, 1, 5, 9, 13, m[2], m[3]);
+        g(&mut v, 2, 6, 10, 14, m[4], m[5]);
+        g(&mut v, 3, 7, 11, 15, m[6], m[7]);
+        g(&mut v, 0, 5, 10, 15, m[8], m[9]);
+        g(&mut v, 1, 6, 11, 12, m[10], m[11]);
+        g(&mut v, 2, 7, 8, 13, m[12], m[13]);
+        g(&mut v, 3, 4, 9, 14, m[14], m[15]);
+        if round < 6 {
+            m = MSG_PERMUTATION.map(|i| m[i]);
+        }
+    }
+
+    for i in 0..8 {
+        v[i] ^= v[i + 8];
+        v[i + 8] ^= cv[i];
+    }
+    m.zeroize();
+    v
+}
+
+fn first_half(words: [u32; 16]) -> [u32; 8] {
+    words[..8].try_into().unwrap()
+}
+
+fn words_to_bytes(cv: &[u32; 8]) -> ChainingValue {
+    let mut bytes = [0; 32];
+    for (chunk, word) in bytes.chunks_exact_mut(4).zip(cv) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    bytes
+}
+
+fn bytes_to_words(bytes: &ChainingValue) -> [u32; 8] {
+    std::array::from_fn(|i| u32::from_le_bytes(bytes[(4 * i)..][..4].try_into().unwrap()))
+}
+
+#[derive(Clone)]
+struct Output {
+    input_cv: [u32; 8],
+    block: [u8; BLOCK_LEN],
+    counter: u64,
+    block_len: usize,
+    flags: u32,
+}
+
+impl Output {
+    fn chaining_value(&self) -> [u32; 8] {
+        first_half(compress(
+            &self.input_cv,
+            &self.block,
+            self.counter,
+            self.block_len,
+            self.flags,
+        ))
+    }
+
+    fn into_reader(self) -> Blake3Reader {
+        Blake3Reader {
+            output: self,
+            block_counter: 0,
+            position: BLOCK_LEN,
+            buffer: [0; BLOCK_LEN],
+        }
+    }
+}
+
+impl Drop for Output {
+    fn drop(&mut self) {
+        self.input_cv.zeroize();
+        self.block.zeroize();
+    }
+}
+
+fn parent_output(left: &[u32; 8], right: &[u32; 8], key: &[u32; 8], flags: u32) -> Output {
+    let mut block = [0; BLOCK_LEN];
+    block[..32].copy_from_slice(&words_to_bytes(left));
+    block[32..].copy_from_slice(&words_to_bytes(right));
+    Output {
+        input_cv: *key,
+        block,
+        counter: 0,
+        block_len: BLOCK_LEN,
+        flags: flags | PARENT,
+    }
+}
+
+#[derive(Clone)]
+struct ChunkState {
+    cv: [u32; 8],
+    chunk_counter: u64,
+    block: [u8; BLOCK_LEN],
+    block_len: usize,
+    blocks_compressed: usize,
+    flags: u32,
+}
+
+impl ChunkState {
+    fn new(key: &[u32; 8], chunk_counter: u64, flags: u32) -> Self {
+        Self {
+            cv: *key,
+            chunk_counter,
+            block: [0; BLOCK_LEN],
+            block_len: 0,
+            blocks_compressed: 0,
+            flags,
+        }
+    }
+
+    fn len(&self) -> usize {
+        BLOCK_LEN * self.blocks_compressed + self.block_len
+    }
+
+    fn start_flag(&self) -> u32 {
+        if self.blocks_compressed == 0 {
+            CHUNK_START
+        } else {
+            0
+        }
+    }
+
+    fn update(&mut self, mut input: &[u8]) {
+        while !input.is_empty() {
+            // a full block is only compressed once more input follows, since the last block of
+            // the chunk is compressed with other flags
+            if self.block_len == BLOCK_LEN {
+                let flags = self.flags | self.start_flag();
+                self.cv = first_half(compress(
+                    &self.cv,
+                    &self.block,
+                    self.chunk_counter,
+                    BLOCK_LEN,
+                    flags,
+                ));
+                self.blocks_compressed += 1;
+                self.block = [0; BLOCK_LEN];
+                self.block_len = 0;
+            }
+
+            let n = input.len().min(BLOCK_LEN - self.block_len);
+            self.block[self.block_len..][..n].copy_from_slice(&input[..n]);
+            self.block_len += n;
+            input = &input[n..];
+        }
+    }
+
+    fn output(&self) -> Output {
+        Output {
+            input_cv: self.cv,
+            block: self.block,
+            counter: self.chunk_counter,
+            block_len: self.block_len,
+            flags: self.flags | self.start_flag() | CHUNK_END,
+        }
+    }
+}
+
+impl Drop for ChunkState {
+    fn drop(&mut self) {
+        self.cv.zeroize();
+        self.block.zeroize();
+    }
+}
+
+/// The largest power of two that is smaller than `chunks`.
+fn left_subtree_chunks(chunks: u64) -> u64 {
+    debug_assert!(chunks > 1);
+    1 << (63 - (chunks - 1).leading_zeros())
+}
+
+fn subtree_cv(
+    input: &[u8],
+    chunk_counter: u64,
+    key: &[u32; 8],
+    flags: u32,
+    threads: usize,
+) -> [u32; 8] {
+    if input.len() <= CHUNK_LEN {
+        let mut chunk = ChunkState::new(key, chunk_counter, flags);
+        chunk.update(input);
+        return chunk.output().chaining_value();
+    }
+
+    let left_chunks = left_subtree_chunks(input.len().div_ceil(CHUNK_LEN) as u64);
+    let (left, right) = input.split_at(left_chunks as usize * CHUNK_LEN);
+    let right_counter = chunk_counter + left_chunks;
+    let (left_cv, right_cv) = if threads > 1 && input.len() >= MIN_PARALLEL_LEN {
+        std::thread::scope(|scope| {
+            let left_cv = scope.spawn(|| subtree_cv(left, chunk_counter, key, flags, threads / 2));
+            let right_cv = subtree_cv(right, right_counter, key, flags, threads - threads / 2);
+            (left_cv.join().unwrap(), right_cv)
+        })
+    } else {
+        (
+            subtree_cv(left, chunk_counter, key, flags, 1),
+            subtree_cv(right, right_counter, key, flags, 1),
+        )
+    };
+    parent_output(&left_cv, &right_cv, key, flags).chaining_value()
+}
+
+#[derive(Clone)]
+pub struct Blake3 {
+    key: [u32; 8],
+    flags: u32,
+    chunk: ChunkState,
+    /// The chaining values of the complete subtrees to the left of the current chunk, one for
+    /// each bit that is set in the number of chunks hashed so far.
+    stack: [[u32; 8]; MAX_DEPTH],
+    stack_len: usize,
+}
+
+impl Blake3 {
+    fn with_key_words(key: [u32; 8], flags: u32) -> Self {
+        Self {
+            key,
+            flags,
+            chunk: ChunkState::new(&key, 0, flags),
+            stack: [[0; 8]; MAX_DEPTH],
+            stack_len: 0,
+        }
+    }
+
+    pub fn new() -> Self {
+        Self::with_key_words(IV, 0)
+    }
+
+    pub fn with_key(key: &[u8; 32]) -> Self {
+        Self::with_key_words(bytes_to_words(key), KEYED_HASH)
+    }
+
+    pub fn with_context(context: &str) -> Self {
+        let mut context_hasher = Self::with_key_words(IV, DERIVE_KEY_CONTEXT);
+        context_hasher.update(context.as_bytes());
+        let context_key = context_hasher.root_output().chaining_value();
+        Self::with_key_words(context_key, DERIVE_KEY_MATERIAL)
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.update_with_threads(data, 1);
+    }
+
+    /// Uses as many threads as there are processors.
+    pub fn update_parallel(&mut self, data: &[u8]) {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        self.update_with_threads(data, threads);
+    }
+
+    fn update_with_threads(&mut self, mut data: &[u8], threads: usize) {
+        if self.chunk.len() > 0 {
+            let n = data.len().min(CHUNK_LEN - self.chunk.len());
+            self.chunk.update(&data[..n]);
+            data = &data[n..];
+            if data.is_empty() {
+                return;
+            }
+            // the chunk is complete and is not the root, since more input follows
+            let cv = self.chunk.output().chaining_value();
+            let chunk_counter = self.chunk.chunk_counter + 1;
+            self.push_subtree(cv, 0, chunk_counter);
+            self.chunk = ChunkState::new(&self.key, chunk_counter, self.flags);
+        }
+
+        // whole subtrees, which leave at least one byte for the current chunk, so that none of
+        // them can be the root
+        while data.len() > CHUNK_LEN {
+            let chunk_counter = self.chunk.chunk_counter;
+            let max_chunks = ((data.len() - 1) / CHUNK_LEN) as u64;
+            let mut chunks = 1 << (63 - max_chunks.leading_zeros());
+            while !chunk_counter.is_multiple_of(chunks) {
+                chunks /= 2;
+            }
+
+            let (subtree, rest) = data.split_at(chunks as usize * CHUNK_LEN);
+            let cv = subtree_cv(subtree, chunk_counter, &self.key, self.flags, threads);
+            self.push_subtree(cv, chunks.trailing_zeros(), chunk_counter + chunks);
+            self.chunk = ChunkState::new(&self.key, chunk_counter + chunks, self.flags);
+            data = rest;
+        }
+
+        self.chunk.update(data);
+    }
+
+    /// `total` is the number of chunks hashed after the subtree of 2^`level` chunks.
+    fn push_subtree(&mut self, mut cv: [u32; 8], level: u32, total: u64) {
+        let mut total = total >> level;
+        while total & 1 == 0 {
+            self.stack_len -= 1;
+            cv = parent_output(&self.stack[self.stack_len], &cv, &self.key, self.flags)
+                .chaining_value();
+            total >>= 1;
+        }
+        self.stack[self.stack_len] = cv;
+        self.stack_len += 1;
+    }
+
+    fn root_output(&self) -> Output {
+        let mut output = self.chunk.output();
+        for cv in self.stack[..self.stack_len].iter().rev() {
+            output = parent_output(cv, &output.chaining_value(), &self.key, self.flags);
+        }
+        output.flags |= ROOT;
+        output
+    }
+
+    pub fn finalize(self) -> Hash256 {
+        let mut output = [0_u8; 32];
+        self.finalize_xof().read(&mut output);
+        output.into()
+    }
+
+    pub fn finalize_xof(self) -> Blake3Reader {
+        self.root_output().into_reader()
+    }
+
+    pub fn reset(&mut self) {
+        self.chunk = ChunkState::new(&self.key, 0, self.flags);
+        self.stack.zeroize();
+        self.stack_len = 0;
+    }
+
+    /// A subtree of `n` chunks must start at a multiple of `n` rounded up to a power of two, and
+    /// only the last subtree of a message can end in the middle of a chunk.
+    pub fn subtree_chaining_value(&self, first_chunk: u64, input: &[u8]) -> ChainingValue {
+        let chunks = input.len().div_ceil(CHUNK_LEN).max(1) as u64;
+        assert!(
+            first_chunk.is_multiple_of(chunks.next_power_of_two()),
+            "subtree of {chunks} chunks cannot start at chunk {first_chunk}"
+        );
+        words_to_bytes(&subtree_cv(input, first_chunk, &self.key, self.flags, 1))
+    }
+
+    /// Not for the root, whose output is `root_hash`.
+    pub fn parent_chaining_value(
+        &self,
+        left: &ChainingValue,
+        right: &ChainingValue,
+    ) -> ChainingValue {
+        let output = parent_output(
+            &bytes_to_words(left),
+            &bytes_to_words(right),
+            &self.key,
+            self.flags,
+        );
+        words_to_bytes(&output.chaining_value())
+    }
+
+    pub fn root_hash(&self, left: &ChainingValue, right: &ChainingValue) -> Hash256 {
+        let mut output = parent_output(
+            &bytes_to_words(left),
+            &bytes_to_words(right),
+            &self.key,
+            self.flags,
+        );
+        output.flags |= ROOT;
+        let mut hash = [0_u8; 32];
+        output.into_reader().read(&mut hash);
+        hash.into()
+    }
+
+    /// Compares in constant time.
+    pub fn verify_subtree(&self, first_chunk: u64, input: &[u8], expected: &ChainingValue) -> bool {
+        let mut cv = self.subtree_chaining_value(first_chunk, input);
+        let result = ct::bytes_eq(&cv, expected);
+        cv.zeroize();
+        result
+    }
+}
+
+impl Default for Blake3 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Blake3 {
+    fn drop(&mut self) {
+        self.key.zeroize();
+        self.stack.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Blake3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Blake3").finish_non_exhaustive()
+    }
+}
+
+impl Digest for Blake3 {
+    type Output = Hash256;
+
+    const OUTPUT_SIZE: usize = 32;
+    const BLOCK_SIZE: usize = BLOCK_LEN;
+    const NAME: &'static str = "BLAKE3";
+    const OID: Option<&'static [u8]> = None;
+
+    fn new() -> Self {
+        Self::new()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.update(data);
+    }
+
+    fn finalize(self) -> Self::Output {
+        self.finalize()
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+}
+
+pub struct Blake3Reader {
+    output: Output,
+    block_counter: u64,
+    position: usize,
+    buffer: [u8; BLOCK_LEN],
+}
+
+impl Blake3Reader {
+    pub fn read(&mut self, output: &mut [u8]) {
+        for b in output {
+            if self.position == BLOCK_LEN {
+                let words = compress(
+                    &self.output.input_cv,
+                    &self.output.block,
+                    self.block_counter,
+                    self.output.block_len,
+                    self.output.flags,
+                );
+                for (chunk, word) in self.buffer.chunks_exact_mut(4).zip(words) {
+                    chunk.copy_from_slice(&word.to_le_bytes());
+                }
+                self.block_counter += 1;
+                self.position = 0;
+            }
+            *b = self.buffer[self.position];
+            self.position += 1;
+        }
+    }
+}
+
+impl Drop for Blake3Reader {
+    fn drop(&mut self) {
+        self.buffer.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Blake3Reader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Blake3Reader").finish_non_exhaustive()
+    }
+}
+
+pub fn blake3(message: &[u8]) -> Hash256 {
+    let mut hasher = Blake3::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+pub fn keyed_hash(key: &[u8; 32], message: &[u8]) -> Hash256 {
+    let mut hasher = Blake3::with_key(key);
+    hasher.update(message);
+    hasher.finalize()
+}
+
+pub fn derive_key(context: &str, key_material: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake3::with_context(context);
+    hasher.update(key_material);
+    let mut key = [0; 32];
+    hasher.finalize_xof().read(&mut key);
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn threads_do_not_change_the_hash() {
+        for len in [
+            MIN_PARALLEL_LEN - 1,
+            MIN_PARALLEL_LEN,
+            37 * CHUNK_LEN + 5,
+            64 * CHUNK_LEN,
+        ] {
+            let input = message(len);
+            for threads in [2, 3, 8] {
+                let mut hasher = Blake3::new();
+                hasher.update_with_threads(&input, threads);
+                assert_eq!(
+                    hasher.finalize(),
+                    blake3(&input),
+                    "{len} bytes on {threads} threads"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn left_subtrees_are_powers_of_two() {
+        assert_eq!(left_subtree_chunks(2), 1);
+        assert_eq!(left_subtree_chunks(3), 2);
+        assert_eq!(left_subtree_chunks(4), 2);
+        assert_eq!(left_subtree_chunks(5), 4);
+        assert_eq!(left_subtree_chunks(1 << 40), 1 << 39);
+    }
+
+    #[test]
+    fn key_is_zeroized_on_drop() {
+        let mut hasher = std::mem::ManuallyDrop::new(Blake3::with_key(&[1; 32]));
+        hasher.update(&[2; 3000]);
+        unsafe { std::mem::ManuallyDrop::drop(&mut hasher) };
+        assert_eq!(hasher.key, [0; 8]);
+        assert_eq!(hasher.stack[0], [0; 8]);
+        assert_eq!(hasher.chunk.block, [0; BLOCK_LEN]);
+    }
+
+    #[test]
+    fn debug_output_is_redacted() {
+        assert_eq!(format!("{:?}", Blake3::with_key(&[0; 32])), "Blake3 { .. }");
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot start at chunk 2")]
+    fn misaligned_subtree_panics() {
+        Blake3::new().subtree_chaining_value(2, &[0; 3 * CHUNK_LEN]);
+    }
+}
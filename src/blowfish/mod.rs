@@ -0,0 +1,170 @@
+use crate::key::{check_length, decode_base64, decode_hex, KeyError};
+use crate::zeroize::Zeroize;
+use crate::{Block, BlockCipher};
+
+mod tables;
+
+use tables::{INITIAL_P, INITIAL_S};
+
+pub struct Blowfish {
+    p: [u32; 18],
+    s: [[u32; 256]; 4],
+}
+
+impl Blowfish {
+    pub fn new(key: &[u8]) -> Self {
+        Self::try_from(key).expect("invalid Blowfish key")
+    }
+
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+
+    fn expand_key(key: &[u8]) -> Self {
+        let mut cipher = Self {
+            p: INITIAL_P,
+            s: INITIAL_S,
+        };
+
+        let mut key_bytes = key.iter().cycle();
+        for p in cipher.p.iter_mut() {
+            let mut word = 0_u32;
+            for _ in 0..4 {
+                word = (word << 8) | *key_bytes.next().unwrap() as u32;
+            }
+            *p ^= word;
+        }
+
+        let mut data = [0_u32; 2];
+        for i in (0..18).step_by(2) {
+            data = cipher.encrypt_words(data);
+            cipher.p[i..(i + 2)].copy_from_slice(&data);
+        }
+        for b in 0..4 {
+            for i in (0..256).step_by(2) {
+                data = cipher.encrypt_words(data);
+                cipher.s[b][i..(i + 2)].copy_from_slice(&data);
+            }
+        }
+
+        cipher
+    }
+
+    fn f(&self, x: u32) -> u32 {
+        let [a, b, c, d] = x.to_be_bytes();
+        (self.s[0][a as usize].wrapping_add(self.s[1][b as usize]) ^ self.s[2][c as usize])
+            .wrapping_add(self.s[3][d as usize])
+    }
+
+    fn encrypt_words(&self, [mut l, mut r]: [u32; 2]) -> [u32; 2] {
+        for i in 0..16 {
+            l ^= self.p[i];
+            r ^= self.f(l);
+            (l, r) = (r, l);
+        }
+        (l, r) = (r, l);
+        r ^= self.p[16];
+        l ^= self.p[17];
+        [l, r]
+    }
+
+    fn decrypt_words(&self, [mut l, mut r]: [u32; 2]) -> [u32; 2] {
+        for i in (2..18).rev() {
+            l ^= self.p[i];
+            r ^= self.f(l);
+            (l, r) = (r, l);
+        }
+        (l, r) = (r, l);
+        r ^= self.p[1];
+        l ^= self.p[0];
+        [l, r]
+    }
+}
+
+/// Accepts keys from 8 to 448 bits.
+impl TryFrom<&[u8]> for Blowfish {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        check_length(key, 1..=56, "1 to 56 bytes")?;
+        Ok(Self::expand_key(key))
+    }
+}
+
+impl Drop for Blowfish {
+    fn drop(&mut self) {
+        self.p.zeroize();
+        self.s.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Blowfish {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Blowfish").finish_non_exhaustive()
+    }
+}
+
+impl BlockCipher<8> for Blowfish {
+    fn encrypt(&self, plaintext: Block<8>) -> Block<8> {
+        let l = u32::from_be_bytes(<[u8; 4]>::try_from(&plaintext[..4]).unwrap());
+        let r = u32::from_be_bytes(<[u8; 4]>::try_from(&plaintext[4..]).unwrap());
+
+        let [l, r] = self.encrypt_words([l, r]);
+
+        let mut res = [0_u8; 8];
+        res[..4].copy_from_slice(&l.to_be_bytes());
+        res[4..].copy_from_slice(&r.to_be_bytes());
+        res
+    }
+
+    fn decrypt(&self, ciphertext: Block<8>) -> Block<8> {
+        let l = u32::from_be_bytes(<[u8; 4]>::try_from(&ciphertext[..4]).unwrap());
+        let r = u32::from_be_bytes(<[u8; 4]>::try_from(&ciphertext[4..]).unwrap());
+
+        let [l, r] = self.decrypt_words([l, r]);
+
+        let mut res = [0_u8; 8];
+        res[..4].copy_from_slice(&l.to_be_bytes());
+        res[4..].copy_from_slice(&r.to_be_bytes());
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initial_tables_are_digits_of_pi() {
+        assert_eq!(INITIAL_P[0], 0x243f6a88);
+        assert_eq!(INITIAL_P[17], 0x8979fb1b);
+        assert_eq!(INITIAL_S[0][0], 0xd1310ba6);
+        assert_eq!(INITIAL_S[3][255], 0x3ac372e6);
+    }
+
+    #[test]
+    fn key_length_is_validated() {
+        assert!(matches!(
+            Blowfish::try_from(&[][..]),
+            Err(KeyError::InvalidLength { actual: 0, .. })
+        ));
+        assert!(matches!(
+            Blowfish::try_from(&[0; 57][..]),
+            Err(KeyError::InvalidLength { actual: 57, .. })
+        ));
+        assert!(Blowfish::try_from(&[0; 1][..]).is_ok());
+        assert!(Blowfish::try_from(&[0; 56][..]).is_ok());
+    }
+
+    #[test]
+    fn tables_are_zeroized_on_drop() {
+        let mut cipher = std::mem::ManuallyDrop::new(Blowfish::new(b"abcd"));
+        unsafe { std::mem::ManuallyDrop::drop(&mut cipher) };
+        assert_eq!(cipher.p, [0; 18]);
+        assert_eq!(cipher.s, [[0; 256]; 4]);
+    }
+}
@@ -0,0 +1,357 @@
+use crate::aes::{Key128, Key192, Key256};
+use crate::key::{decode_base64, decode_hex, KeyError};
+use crate::zeroize::Zeroize;
+use crate::{Block, BlockCipher};
+
+#[rustfmt::skip]
+const SBOX1: [u8; 256] = [
+    0x70, 0x82, 0x2c, 0xec, 0xb3, 0x27, 0xc0, 0xe5, 0xe4, 0x85, 0x57, 0x35, 0xea, 0x0c, 0xae, 0x41,
+    0x23, 0xef, 0x6b, 0x93, 0x45, 0x19, 0xa5, 0x21, 0xed, 0x0e, 0x4f, 0x4e, 0x1d, 0x65, 0x92, 0xbd,
+    0x86, 0xb8, 0xaf, 0x8f, 0x7c, 0xeb, 0x1f, 0xce, 0x3e, 0x30, 0xdc, 0x5f, 0x5e, 0xc5, 0x0b, 0x1a,
+    0xa6, 0xe1, 0x39, 0xca, 0xd5, 0x47, 0x5d, 0x3d, 0xd9, 0x01, 0x5a, 0xd6, 0x51, 0x56, 0x6c, 0x4d,
+    0x8b, 0x0d, 0x9a, 0x66, 0xfb, 0xcc, 0xb0, 0x2d, 0x74, 0x12, 0x2b, 0x20, 0xf0, 0xb1, 0x84, 0x99,
+    0xdf, 0x4c, 0xcb, 0xc2, 0x34, 0x7e, 0x76, 0x05, 0x6d, 0xb7, 0xa9, 0x31, 0xd1, 0x17, 0x04, 0xd7,
+    0x14, 0x58, 0x3a, 0x61, 0xde, 0x1b, 0x11, 0x1c, 0x32, 0x0f, 0x9c, 0x16, 0x53, 0x18, 0xf2, 0x22,
+    0xfe, 0x44, 0xcf, 0xb2, 0xc3, 0xb5, 0x7a, 0x91, 0x24, 0x08, 0xe8, 0xa8, 0x60, 0xfc, 0x69, 0x50,
+    0xaa, 0xd0, 0xa0, 0x7d, 0xa1, 0x89, 0x62, 0x97, 0x54, 0x5b, 0x1e, 0x95, 0xe0, 0xff, 0x64, 0xd2,
+    0x10, 0xc4, 0x00, 0x48, 0xa3, 0xf7, 0x75, 0xdb, 0x8a, 0x03, 0xe6, 0xda, 0x09, 0x3f, 0xdd, 0x94,
+    0x87, 0x5c, 0x83, 0x02, 0xcd, 0x4a, 0x90, 0x33, 0x73, 0x67, 0xf6, 0xf3, 0x9d, 0x7f, 0xbf, 0xe2,
+    0x52, 0x9b, 0xd8, 0x26, 0xc8, 0x37, 0xc6, 0x3b, 0x81, 0x96, 0x6f, 0x4b, 0x13, 0xbe, 0x63, 0x2e,
+    0xe9, 0x79, 0xa7, 0x8c, 0x9f, 0x6e, 0xbc, 0x8e, 0x29, 0xf5, 0xf9, 0xb6, 0x2f, 0xfd, 0xb4, 0x59,
+    0x78, 0x98, 0x06, 0x6a, 0xe7, 0x46, 0x71, 0xba, 0xd4, 0x25, 0xab, 0x42, 0x88, 0xa2, 0x8d, 0xfa,
+    0x72, 0x07, 0xb9, 0x55, 0xf8, 0xee, 0xac, 0x0a, 0x36, 0x49, 0x2a, 0x68, 0x3c, 0x38, 0xf1, 0xa4,
+    0x40, 0x28, 0xd3, 0x7b, 0xbb, 0xc9, 0x43, 0xc1, 0x15, 0xe3, 0xad, 0xf4, 0x77, 0xc7, 0x80, 0x9e,
+];
+
+const SBOX2: [u8; 256] = rotate_sbox(1);
+const SBOX3: [u8; 256] = rotate_sbox(7);
+const SBOX4: [u8; 256] = {
+    let mut sbox = [0; 256];
+    let mut x = 0;
+    while x < 256 {
+        sbox[x] = SBOX1[(x as u8).rotate_left(1) as usize];
+        x += 1;
+    }
+    sbox
+};
+
+const SIGMA: [u64; 6] = [
+    0xa09e667f3bcc908b,
+    0xb67ae8584caa73b2,
+    0xc6ef372fe94f82be,
+    0x54ff53a5f1d36f1c,
+    0x10e527fade682d1d,
+    0xb05688c2b3e6c1fd,
+];
+
+const fn rotate_sbox(n: u32) -> [u8; 256] {
+    let mut sbox = [0; 256];
+    let mut x = 0;
+    while x < 256 {
+        sbox[x] = SBOX1[x].rotate_left(n);
+        x += 1;
+    }
+    sbox
+}
+
+/// Camellia (RFC 3713) with 128-, 192- or 256-bit keys. It uses 18 rounds for 128-bit keys and
+/// 24 rounds otherwise, with an FL-layer after every sixth round.
+pub struct Camellia {
+    kw: [u64; 4], // whitening keys
+    k: [u64; 24], // round keys, of which 18 are used for 128-bit keys
+    ke: [u64; 6], // FL-layer keys, of which 4 are used for 128-bit keys
+    rounds: usize,
+}
+
+impl Camellia {
+    /// Picks the key size depending on the length of the key.
+    pub fn new(key: &[u8]) -> Self {
+        Self::try_from(key).expect("invalid Camellia key")
+    }
+
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+
+    fn expand_key(key: &[u8]) -> Self {
+        let kl = u128::from_be_bytes(key[..16].try_into().unwrap());
+        let kr = match key.len() {
+            16 => 0,
+            24 => {
+                let right = u64::from_be_bytes(key[16..].try_into().unwrap());
+                ((right as u128) << 64) | !right as u128
+            }
+            _ => u128::from_be_bytes(key[16..].try_into().unwrap()),
+        };
+
+        let mut d1 = ((kl ^ kr) >> 64) as u64;
+        let mut d2 = (kl ^ kr) as u64;
+        d2 ^= f(d1, SIGMA[0]);
+        d1 ^= f(d2, SIGMA[1]);
+        d1 ^= (kl >> 64) as u64;
+        d2 ^= kl as u64;
+        d2 ^= f(d1, SIGMA[2]);
+        d1 ^= f(d2, SIGMA[3]);
+        let ka = ((d1 as u128) << 64) | d2 as u128;
+
+        d1 = ((ka ^ kr) >> 64) as u64;
+        d2 = (ka ^ kr) as u64;
+        d2 ^= f(d1, SIGMA[4]);
+        d1 ^= f(d2, SIGMA[5]);
+        let kb = ((d1 as u128) << 64) | d2 as u128;
+
+        let halves = |x: u128, n: u32| {
+            let x = x.rotate_left(n);
+            [(x >> 64) as u64, x as u64]
+        };
+
+        let (kw, mut k, mut ke) = if key.len() == 16 {
+            (
+                [halves(kl, 0), halves(ka, 111)],
+                [
+                    halves(ka, 0),
+                    halves(kl, 15),
+                    halves(ka, 15),
+                    halves(kl, 45),
+                    [halves(ka, 45)[0], halves(kl, 60)[1]],
+                    halves(ka, 60),
+                    halves(kl, 94),
+                    halves(ka, 94),
+                    halves(kl, 111),
+                ]
+                .concat(),
+                [halves(ka, 30), halves(kl, 77)].concat(),
+            )
+        } else {
+            (
+                [halves(kl, 0), halves(kb, 111)],
+                [
+                    halves(kb, 0),
+                    halves(kr, 15),
+                    halves(ka, 15),
+                    halves(kb, 30),
+                    halves(kl, 45),
+                    halves(ka, 45),
+                    halves(kr, 60),
+                    halves(kb, 60),
+                    halves(kl, 77),
+                    halves(kr, 94),
+                    halves(ka, 94),
+                    halves(kl, 111),
+                ]
+                .concat(),
+                [halves(kr, 30), halves(kl, 60), halves(ka, 77)].concat(),
+            )
+        };
+
+        let mut cipher = Self {
+            kw: kw.concat().try_into().unwrap(),
+            k: [0; 24],
+            ke: [0; 6],
+            rounds: k.len(),
+        };
+        cipher.k[..k.len()].copy_from_slice(&k);
+        cipher.ke[..ke.len()].copy_from_slice(&ke);
+
+        d1.zeroize();
+        d2.zeroize();
+        k.zeroize();
+        ke.zeroize();
+        cipher
+    }
+
+    fn round_keys(&self) -> &[u64] {
+        &self.k[..self.rounds]
+    }
+
+    fn fl_keys(&self) -> &[u64] {
+        &self.ke[..(self.rounds / 3 - 2)]
+    }
+
+    /// Decryption is encryption with the subkeys in reverse order.
+    fn crypt(
+        block: Block<16>,
+        kw: [u64; 4],
+        mut k: impl Iterator<Item = u64>,
+        mut ke: impl Iterator<Item = u64>,
+    ) -> Block<16> {
+        let mut d1 = u64::from_be_bytes(block[..8].try_into().unwrap()) ^ kw[0];
+        let mut d2 = u64::from_be_bytes(block[8..].try_into().unwrap()) ^ kw[1];
+
+        loop {
+            for _ in 0..3 {
+                d2 ^= f(d1, k.next().unwrap());
+                d1 ^= f(d2, k.next().unwrap());
+            }
+            let Some(ke1) = ke.next() else {
+                break;
+            };
+            d1 = fl(d1, ke1);
+            d2 = fl_inv(d2, ke.next().unwrap());
+        }
+
+        d2 ^= kw[2];
+        d1 ^= kw[3];
+
+        let mut res = [0_u8; 16];
+        res[..8].copy_from_slice(&d2.to_be_bytes());
+        res[8..].copy_from_slice(&d1.to_be_bytes());
+        res
+    }
+}
+
+fn f(x: u64, k: u64) -> u64 {
+    let [t1, t2, t3, t4, t5, t6, t7, t8] = (x ^ k).to_be_bytes();
+    let t1 = SBOX1[t1 as usize];
+    let t2 = SBOX2[t2 as usize];
+    let t3 = SBOX3[t3 as usize];
+    let t4 = SBOX4[t4 as usize];
+    let t5 = SBOX2[t5 as usize];
+    let t6 = SBOX3[t6 as usize];
+    let t7 = SBOX4[t7 as usize];
+    let t8 = SBOX1[t8 as usize];
+
+    u64::from_be_bytes([
+        t1 ^ t3 ^ t4 ^ t6 ^ t7 ^ t8,
+        t1 ^ t2 ^ t4 ^ t5 ^ t7 ^ t8,
+        t1 ^ t2 ^ t3 ^ t5 ^ t6 ^ t8,
+        t2 ^ t3 ^ t4 ^ t5 ^ t6 ^ t7,
+        t1 ^ t2 ^ t6 ^ t7 ^ t8,
+        t2 ^ t3 ^ t5 ^ t7 ^ t8,
+        t3 ^ t4 ^ t5 ^ t6 ^ t8,
+        t1 ^ t4 ^ t5 ^ t6 ^ t7,
+    ])
+}
+
+fn fl(x: u64, k: u64) -> u64 {
+    let (mut x1, mut x2) = ((x >> 32) as u32, x as u32);
+    let (k1, k2) = ((k >> 32) as u32, k as u32);
+    x2 ^= (x1 & k1).rotate_left(1);
+    x1 ^= x2 | k2;
+    ((x1 as u64) << 32) | x2 as u64
+}
+
+fn fl_inv(y: u64, k: u64) -> u64 {
+    let (mut y1, mut y2) = ((y >> 32) as u32, y as u32);
+    let (k1, k2) = ((k >> 32) as u32, k as u32);
+    y1 ^= y2 | k2;
+    y2 ^= (y1 & k1).rotate_left(1);
+    ((y1 as u64) << 32) | y2 as u64
+}
+
+/// Accepts 128-, 192- and 256-bit keys.
+impl TryFrom<&[u8]> for Camellia {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        match key.len() {
+            16 | 24 | 32 => Ok(Self::expand_key(key)),
+            actual => Err(KeyError::InvalidLength {
+                expected: "16, 24 or 32 bytes",
+                actual,
+            }),
+        }
+    }
+}
+
+impl From<Key128> for Camellia {
+    fn from(key: Key128) -> Self {
+        Self::expand_key(&key.0)
+    }
+}
+
+impl From<Key192> for Camellia {
+    fn from(key: Key192) -> Self {
+        Self::expand_key(&key.0)
+    }
+}
+
+impl From<Key256> for Camellia {
+    fn from(key: Key256) -> Self {
+        Self::expand_key(&key.0)
+    }
+}
+
+impl Drop for Camellia {
+    fn drop(&mut self) {
+        self.kw.zeroize();
+        self.k.zeroize();
+        self.ke.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Camellia {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Camellia")
+            .field("rounds", &self.rounds)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BlockCipher<16> for Camellia {
+    fn encrypt(&self, plaintext: Block<16>) -> Block<16> {
+        let (k, ke) = (self.round_keys(), self.fl_keys());
+        Self::crypt(plaintext, self.kw, k.iter().copied(), ke.iter().copied())
+    }
+
+    fn decrypt(&self, ciphertext: Block<16>) -> Block<16> {
+        let [kw1, kw2, kw3, kw4] = self.kw;
+        let (k, ke) = (self.round_keys(), self.fl_keys());
+        Self::crypt(
+            ciphertext,
+            [kw3, kw4, kw1, kw2],
+            k.iter().rev().copied(),
+            ke.iter().rev().copied(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::ManuallyDrop;
+
+    use super::*;
+
+    #[test]
+    fn derived_sboxes() {
+        // RFC 3713, section 2.4.4
+        assert_eq!(SBOX2[0x00..0x04], [0xe0, 0x05, 0x58, 0xd9]);
+        assert_eq!(SBOX3[0x00..0x04], [0x38, 0x41, 0x16, 0x76]);
+        assert_eq!(SBOX4[0x00..0x04], [0x70, 0x2c, 0xb3, 0xc0]);
+    }
+
+    #[test]
+    fn fl_inv_inverts_fl() {
+        let k = 0x0123456789abcdef;
+        for x in [0, 1, u64::MAX, 0xfedcba9876543210] {
+            assert_eq!(fl_inv(fl(x, k), k), x);
+        }
+    }
+
+    #[test]
+    fn key_length_is_validated() {
+        assert!(matches!(
+            Camellia::try_from(&[0; 20][..]),
+            Err(KeyError::InvalidLength { actual: 20, .. })
+        ));
+    }
+
+    #[test]
+    fn subkeys_are_zeroized_on_drop() {
+        let mut cipher = ManuallyDrop::new(Camellia::new(&[1; 32]));
+        unsafe { ManuallyDrop::drop(&mut cipher) };
+        assert_eq!(cipher.kw, [0; 4]);
+        assert_eq!(cipher.k, [0; 24]);
+        assert_eq!(cipher.ke, [0; 6]);
+    }
+
+    #[test]
+    fn debug_output_is_redacted() {
+        assert_eq!(format!("{:?}", Camellia::new(&[0; 16])), "Camellia { rounds: 18, .. }");
+        assert_eq!(format!("{:?}", Camellia::new(&[0; 24])), "Camellia { rounds: 24, .. }");
+    }
+}
@@ -0,0 +1,216 @@
+use crate::key::{check_length, decode_base64, decode_hex, KeyError};
+use crate::zeroize::Zeroize;
+use crate::{Block, BlockCipher};
+
+mod tables;
+
+use tables::{S1, S2, S3, S4, S5, S6, S7, S8};
+
+// For each of the four steps of a subkey generation: the bytes of z or x (alternating) that are
+// looked up in S5 to S8, followed by the byte looked up in S5, S6, S7 or S8 respectively.
+#[rustfmt::skip]
+const SUBKEY_BYTES: [[[usize; 5]; 4]; 4] = [
+    [[0x8, 0x9, 0x7, 0x6, 0x2], [0xa, 0xb, 0x5, 0x4, 0x6], [0xc, 0xd, 0x3, 0x2, 0x9], [0xe, 0xf, 0x1, 0x0, 0xc]],
+    [[0x3, 0x2, 0xc, 0xd, 0x8], [0x1, 0x0, 0xe, 0xf, 0xd], [0x7, 0x6, 0x8, 0x9, 0x3], [0x5, 0x4, 0xa, 0xb, 0x7]],
+    [[0x3, 0x2, 0xc, 0xd, 0x9], [0x1, 0x0, 0xe, 0xf, 0xc], [0x7, 0x6, 0x8, 0x9, 0x2], [0x5, 0x4, 0xa, 0xb, 0x6]],
+    [[0x8, 0x9, 0x7, 0x6, 0x3], [0xa, 0xb, 0x5, 0x4, 0x7], [0xc, 0xd, 0x3, 0x2, 0x8], [0xe, 0xf, 0x1, 0x0, 0xd]],
+];
+
+/// CAST-128 (RFC 2144), also known as CAST5 in OpenPGP. Keys from 40 to 128 bits are accepted,
+/// and keys of at most 80 bits use 12 instead of 16 rounds.
+pub struct Cast128 {
+    masking: [u32; 16],
+    rotation: [u8; 16],
+    rounds: usize,
+}
+
+impl Cast128 {
+    pub fn new(key: &[u8]) -> Self {
+        Self::try_from(key).expect("invalid CAST-128 key")
+    }
+
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+
+    fn expand_key(key: &[u8]) -> Self {
+        // shorter keys are padded with zeros to 128 bits
+        let mut x = [0_u8; 16];
+        x[..key.len()].copy_from_slice(key);
+        let mut z = [0_u8; 16];
+
+        let mut subkeys = [0_u32; 32];
+        for keys in subkeys.chunks_exact_mut(16) {
+            for (step, keys) in keys.chunks_exact_mut(4).enumerate() {
+                let source = if step % 2 == 0 {
+                    mix(&x, &mut z, [0, 2, 3, 1], [0xd, 0xf, 0xc, 0xe], [0x8, 0xa, 0x9, 0xb]);
+                    &z
+                } else {
+                    mix(&z, &mut x, [2, 0, 1, 3], [0x5, 0x7, 0x4, 0x6], [0x0, 0x2, 0x1, 0x3]);
+                    &x
+                };
+                for (j, (k, &[a, b, c, d, e])) in keys.iter_mut().zip(&SUBKEY_BYTES[step]).enumerate() {
+                    let s = |i: usize| source[i] as usize;
+                    *k = S5[s(a)] ^ S6[s(b)] ^ S7[s(c)] ^ S8[s(d)] ^ [&S5, &S6, &S7, &S8][j][s(e)];
+                }
+            }
+        }
+
+        let cipher = Self {
+            masking: subkeys[..16].try_into().unwrap(),
+            rotation: std::array::from_fn(|i| (subkeys[16 + i] & 0x1f) as u8),
+            rounds: if key.len() <= 10 { 12 } else { 16 },
+        };
+
+        x.zeroize();
+        z.zeroize();
+        subkeys.zeroize();
+        cipher
+    }
+
+    fn f(&self, round: usize, d: u32) -> u32 {
+        let (km, kr) = (self.masking[round], self.rotation[round] as u32);
+        match round % 3 {
+            0 => {
+                let [a, b, c, d] = km.wrapping_add(d).rotate_left(kr).to_be_bytes();
+                (S1[a as usize] ^ S2[b as usize])
+                    .wrapping_sub(S3[c as usize])
+                    .wrapping_add(S4[d as usize])
+            }
+            1 => {
+                let [a, b, c, d] = (km ^ d).rotate_left(kr).to_be_bytes();
+                S1[a as usize]
+                    .wrapping_sub(S2[b as usize])
+                    .wrapping_add(S3[c as usize])
+                    ^ S4[d as usize]
+            }
+            _ => {
+                let [a, b, c, d] = km.wrapping_sub(d).rotate_left(kr).to_be_bytes();
+                (S1[a as usize].wrapping_add(S2[b as usize]) ^ S3[c as usize])
+                    .wrapping_sub(S4[d as usize])
+            }
+        }
+    }
+}
+
+/// Computes the four words of `output` from `input` (RFC 2144, section 2.4). Each word is a word
+/// of `input`, XORed with S5 to S8 applied to four bytes and with one more S-box lookup of a byte
+/// of `input`. The first word looks up bytes of `input`, and the following words look up bytes of
+/// the words of `output` that were just computed.
+fn mix(input: &[u8; 16], output: &mut [u8; 16], words: [usize; 4], first: [usize; 4], extra: [usize; 4]) {
+    const FOLLOWING: [[usize; 4]; 3] = [[0x0, 0x2, 0x1, 0x3], [0x7, 0x6, 0x5, 0x4], [0xa, 0x9, 0xb, 0x8]];
+    const EXTRA_SBOXES: [&[u32; 256]; 4] = [&S7, &S8, &S5, &S6];
+
+    for i in 0..4 {
+        let (bytes, [a, b, c, d]) = if i == 0 {
+            (input, first)
+        } else {
+            (&*output, FOLLOWING[i - 1])
+        };
+        let [a, b, c, d] = [bytes[a], bytes[b], bytes[c], bytes[d]].map(usize::from);
+        let word = u32::from_be_bytes(input[(4 * words[i])..][..4].try_into().unwrap())
+            ^ S5[a]
+            ^ S6[b]
+            ^ S7[c]
+            ^ S8[d]
+            ^ EXTRA_SBOXES[i][input[extra[i]] as usize];
+        output[(4 * i)..][..4].copy_from_slice(&word.to_be_bytes());
+    }
+}
+
+/// Accepts keys from 40 to 128 bits.
+impl TryFrom<&[u8]> for Cast128 {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        check_length(key, 5..=16, "5 to 16 bytes")?;
+        Ok(Self::expand_key(key))
+    }
+}
+
+impl Drop for Cast128 {
+    fn drop(&mut self) {
+        self.masking.zeroize();
+        self.rotation.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Cast128 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cast128")
+            .field("rounds", &self.rounds)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BlockCipher<8> for Cast128 {
+    fn encrypt(&self, plaintext: Block<8>) -> Block<8> {
+        let mut l = u32::from_be_bytes(plaintext[..4].try_into().unwrap());
+        let mut r = u32::from_be_bytes(plaintext[4..].try_into().unwrap());
+
+        for round in 0..self.rounds {
+            (l, r) = (r, l ^ self.f(round, r));
+        }
+
+        let mut res = [0_u8; 8];
+        res[..4].copy_from_slice(&r.to_be_bytes());
+        res[4..].copy_from_slice(&l.to_be_bytes());
+        res
+    }
+
+    fn decrypt(&self, ciphertext: Block<8>) -> Block<8> {
+        let mut l = u32::from_be_bytes(ciphertext[..4].try_into().unwrap());
+        let mut r = u32::from_be_bytes(ciphertext[4..].try_into().unwrap());
+
+        for round in (0..self.rounds).rev() {
+            (l, r) = (r, l ^ self.f(round, r));
+        }
+
+        let mut res = [0_u8; 8];
+        res[..4].copy_from_slice(&r.to_be_bytes());
+        res[4..].copy_from_slice(&l.to_be_bytes());
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_length_is_validated() {
+        assert!(matches!(
+            Cast128::try_from(&[0; 4][..]),
+            Err(KeyError::InvalidLength { actual: 4, .. })
+        ));
+        assert!(matches!(
+            Cast128::try_from(&[0; 17][..]),
+            Err(KeyError::InvalidLength { actual: 17, .. })
+        ));
+        assert!(Cast128::try_from(&[0; 5][..]).is_ok());
+        assert!(Cast128::try_from(&[0; 16][..]).is_ok());
+    }
+
+    #[test]
+    fn short_keys_use_fewer_rounds() {
+        assert_eq!(Cast128::new(&[1; 10]).rounds, 12);
+        assert_eq!(Cast128::new(&[1; 11]).rounds, 16);
+    }
+
+    #[test]
+    fn subkeys_are_zeroized_on_drop() {
+        let mut cipher = std::mem::ManuallyDrop::new(Cast128::new(b"abcdefgh"));
+        unsafe { std::mem::ManuallyDrop::drop(&mut cipher) };
+        assert_eq!(cipher.masking, [0; 16]);
+        assert_eq!(cipher.rotation, [0; 16]);
+    }
+
+    #[test]
+    fn debug_output_is_redacted() {
+        assert_eq!(format!("{:?}", Cast128::new(&[0; 16])), "Cast128 { rounds: 16, .. }");
+    }
+}
@@ -0,0 +1,267 @@
+use crate::aead::{split_tag, AuthenticationError};
+use crate::key::{decode_base64, decode_hex, fixed_length, KeyError};
+use crate::poly1305::{Poly1305, Tag};
+use crate::zeroize::Zeroize;
+
+use super::{ChaCha20, XChaCha20};
+
+/// The ChaCha20-Poly1305 AEAD construction from RFC 8439. A nonce must never be reused with the
+/// same key, which makes the 96-bit nonce a counter rather than a random value in practice.
+pub struct ChaCha20Poly1305 {
+    key: [u8; 32],
+}
+
+/// ChaCha20-Poly1305 with a 192-bit nonce (draft-irtf-cfrg-xchacha), which is large enough to be
+/// chosen at random for every message.
+pub struct XChaCha20Poly1305 {
+    key: [u8; 32],
+}
+
+impl ChaCha20Poly1305 {
+    pub fn new(key: &[u8; 32]) -> Self {
+        Self { key: *key }
+    }
+
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+
+    /// Returns the ciphertext followed by the 16 byte tag.
+    pub fn encrypt(&self, nonce: &[u8; 12], associated_data: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let mut output = plaintext.to_vec();
+        let tag = self.encrypt_in_place_detached(nonce, associated_data, &mut output);
+        output.extend_from_slice(&tag);
+        output
+    }
+
+    /// Expects the ciphertext followed by the 16 byte tag, as returned by `encrypt`.
+    pub fn decrypt(
+        &self,
+        nonce: &[u8; 12],
+        associated_data: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, AuthenticationError> {
+        let (ciphertext, tag) = split_tag(ciphertext)?;
+        let mut output = ciphertext.to_vec();
+        self.decrypt_in_place_detached(nonce, associated_data, &mut output, tag)?;
+        Ok(output)
+    }
+
+    pub fn encrypt_in_place_detached(
+        &self,
+        nonce: &[u8; 12],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> Tag {
+        seal(ChaCha20::new(&self.key, nonce), associated_data, buffer)
+    }
+
+    /// Leaves `buffer` unchanged if the tag is invalid.
+    pub fn decrypt_in_place_detached(
+        &self,
+        nonce: &[u8; 12],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        tag: &Tag,
+    ) -> Result<(), AuthenticationError> {
+        open(ChaCha20::new(&self.key, nonce), associated_data, buffer, tag)
+    }
+}
+
+impl XChaCha20Poly1305 {
+    pub fn new(key: &[u8; 32]) -> Self {
+        Self { key: *key }
+    }
+
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+
+    /// Returns the ciphertext followed by the 16 byte tag.
+    pub fn encrypt(&self, nonce: &[u8; 24], associated_data: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let mut output = plaintext.to_vec();
+        let tag = self.encrypt_in_place_detached(nonce, associated_data, &mut output);
+        output.extend_from_slice(&tag);
+        output
+    }
+
+    /// Expects the ciphertext followed by the 16 byte tag, as returned by `encrypt`.
+    pub fn decrypt(
+        &self,
+        nonce: &[u8; 24],
+        associated_data: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, AuthenticationError> {
+        let (ciphertext, tag) = split_tag(ciphertext)?;
+        let mut output = ciphertext.to_vec();
+        self.decrypt_in_place_detached(nonce, associated_data, &mut output, tag)?;
+        Ok(output)
+    }
+
+    pub fn encrypt_in_place_detached(
+        &self,
+        nonce: &[u8; 24],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> Tag {
+        seal(self.stream(nonce), associated_data, buffer)
+    }
+
+    /// Leaves `buffer` unchanged if the tag is invalid.
+    pub fn decrypt_in_place_detached(
+        &self,
+        nonce: &[u8; 24],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        tag: &Tag,
+    ) -> Result<(), AuthenticationError> {
+        open(self.stream(nonce), associated_data, buffer, tag)
+    }
+
+    /// The same keystream as `XChaCha20`, but as a plain `ChaCha20` so that it can be seeked.
+    fn stream(&self, nonce: &[u8; 24]) -> ChaCha20 {
+        XChaCha20::new(&self.key, nonce).inner
+    }
+}
+
+/// The first keystream block gives the one-time Poly1305 key, and the rest encrypts the message.
+fn authenticator(stream: &mut ChaCha20) -> Poly1305 {
+    let mut block = [0_u8; 64];
+    stream.apply_keystream(&mut block);
+    let mac = Poly1305::new(block[..32].try_into().unwrap());
+    block.zeroize();
+
+    stream.seek(1);
+    mac
+}
+
+/// Authenticates the associated data and the ciphertext, each padded to 16 bytes, followed by
+/// their lengths (RFC 8439, section 2.8).
+fn compute_tag(mut mac: Poly1305, associated_data: &[u8], ciphertext: &[u8]) -> Poly1305 {
+    mac.update(associated_data);
+    mac.pad_to_block();
+    mac.update(ciphertext);
+    mac.pad_to_block();
+    mac.update(&(associated_data.len() as u64).to_le_bytes());
+    mac.update(&(ciphertext.len() as u64).to_le_bytes());
+    mac
+}
+
+fn seal(mut stream: ChaCha20, associated_data: &[u8], buffer: &mut [u8]) -> Tag {
+    let mac = authenticator(&mut stream);
+    stream.apply_keystream(buffer);
+    compute_tag(mac, associated_data, buffer).finalize()
+}
+
+fn open(
+    mut stream: ChaCha20,
+    associated_data: &[u8],
+    buffer: &mut [u8],
+    tag: &Tag,
+) -> Result<(), AuthenticationError> {
+    let mac = authenticator(&mut stream);
+    if !compute_tag(mac, associated_data, buffer).verify(tag) {
+        return Err(AuthenticationError);
+    }
+    stream.apply_keystream(buffer);
+    Ok(())
+}
+
+impl TryFrom<&[u8]> for ChaCha20Poly1305 {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            key: fixed_length(key, "32 bytes")?,
+        })
+    }
+}
+
+impl TryFrom<&[u8]> for XChaCha20Poly1305 {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            key: fixed_length(key, "32 bytes")?,
+        })
+    }
+}
+
+impl Drop for ChaCha20Poly1305 {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+impl Drop for XChaCha20Poly1305 {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+impl std::fmt::Debug for ChaCha20Poly1305 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChaCha20Poly1305").finish_non_exhaustive()
+    }
+}
+
+impl std::fmt::Debug for XChaCha20Poly1305 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("XChaCha20Poly1305").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::ManuallyDrop;
+
+    use super::*;
+
+    #[test]
+    fn poly1305_key_generation() {
+        // RFC 8439, section 2.6.2
+        let key = std::array::from_fn(|i| 0x80 + i as u8);
+        let nonce = hex::decode("000000000001020304050607").unwrap();
+        let mut stream = ChaCha20::new(&key, nonce.as_slice().try_into().unwrap());
+
+        let mut block = [0_u8; 64];
+        stream.apply_keystream(&mut block);
+        assert_eq!(
+            hex::encode(&block[..32]),
+            "8ad5a08b905f81cc815040274ab29471a833b637e3fd0da508dbb8e2fdd1a646"
+        );
+    }
+
+    #[test]
+    fn too_short_ciphertext_is_rejected() {
+        let aead = ChaCha20Poly1305::new(&[0; 32]);
+        assert_eq!(aead.decrypt(&[0; 12], b"", &[0; 15]), Err(AuthenticationError));
+    }
+
+    #[test]
+    fn key_is_zeroized_on_drop() {
+        let mut aead = ManuallyDrop::new(XChaCha20Poly1305::new(&[1; 32]));
+        unsafe { ManuallyDrop::drop(&mut aead) };
+        assert_eq!(aead.key, [0; 32]);
+    }
+
+    #[test]
+    fn debug_output_is_redacted() {
+        assert_eq!(
+            format!("{:?}", ChaCha20Poly1305::new(&[0; 32])),
+            "ChaCha20Poly1305 { .. }"
+        );
+        assert_eq!(
+            format!("{:?}", XChaCha20Poly1305::new(&[0; 32])),
+            "XChaCha20Poly1305 { .. }"
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! The ChaCha block function, computed for several consecutive counter values at once. Every
+//! state word is stored as an array of lanes (one per block), so that each step of the quarter
+//! round is the same operation on all lanes, which the compiler can turn into SIMD instructions.
+
+pub(super) const SIGMA: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+
+type Lanes<const B: usize> = [[u32; B]; 16];
+
+#[inline(always)]
+#[allow(clippy::needless_range_loop)]
+fn quarter_round<const B: usize>(x: &mut Lanes<B>, a: usize, b: usize, c: usize, d: usize) {
+    for l in 0..B {
+        x[a][l] = x[a][l].wrapping_add(x[b][l]);
+        x[d][l] = (x[d][l] ^ x[a][l]).rotate_left(16);
+        x[c][l] = x[c][l].wrapping_add(x[d][l]);
+        x[b][l] = (x[b][l] ^ x[c][l]).rotate_left(12);
+        x[a][l] = x[a][l].wrapping_add(x[b][l]);
+        x[d][l] = (x[d][l] ^ x[a][l]).rotate_left(8);
+        x[c][l] = x[c][l].wrapping_add(x[d][l]);
+        x[b][l] = (x[b][l] ^ x[c][l]).rotate_left(7);
+    }
+}
+
+/// The 20 rounds of ChaCha20 (10 column rounds and 10 diagonal rounds).
+fn permute<const B: usize>(x: &mut Lanes<B>) {
+    for _ in 0..10 {
+        quarter_round(x, 0, 4, 8, 12);
+        quarter_round(x, 1, 5, 9, 13);
+        quarter_round(x, 2, 6, 10, 14);
+        quarter_round(x, 3, 7, 11, 15);
+
+        quarter_round(x, 0, 5, 10, 15);
+        quarter_round(x, 1, 6, 11, 12);
+        quarter_round(x, 2, 7, 8, 13);
+        quarter_round(x, 3, 4, 9, 14);
+    }
+}
+
+/// Writes `B` keystream blocks to `output`, where block `l` uses the counter `state[12] + l`.
+#[allow(clippy::needless_range_loop)]
+pub(super) fn blocks<const B: usize>(state: &[u32; 16], output: &mut [u8]) {
+    let mut input: Lanes<B> = [[0; B]; 16];
+    for (lanes, &word) in input.iter_mut().zip(state) {
+        *lanes = [word; B];
+    }
+    for l in 0..B {
+        input[12][l] = state[12].wrapping_add(l as u32);
+    }
+
+    let mut x = input;
+    permute(&mut x);
+
+    for l in 0..B {
+        for i in 0..16 {
+            let word = x[i][l].wrapping_add(input[i][l]);
+            output[(64 * l + 4 * i)..][..4].copy_from_slice(&word.to_le_bytes());
+        }
+    }
+}
+
+/// The ChaCha20 rounds without the final addition of the input, as used by HChaCha20.
+pub(super) fn permute_single(state: &[u32; 16]) -> [u32; 16] {
+    let mut x: Lanes<1> = state.map(|w| [w]);
+    permute(&mut x);
+    x.map(|[w]| w)
+}
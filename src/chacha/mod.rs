@@ -0,0 +1,324 @@
+use crate::key::{fixed_length, KeyError};
+use crate::zeroize::Zeroize;
+use crate::StreamCipher;
+
+mod aead;
+mod block;
+
+pub use aead::{ChaCha20Poly1305, XChaCha20Poly1305};
+
+use block::SIGMA;
+
+/// Number of blocks generated per call to the block function.
+const PARALLEL_BLOCKS: usize = 4;
+const BUFFER_LEN: usize = 64 * PARALLEL_BLOCKS;
+
+/// The ChaCha20 keystream as defined in RFC 8439, with a 256-bit key, a 96-bit nonce and a
+/// 32-bit block counter. The keystream ends after 2^32 blocks (256 GiB).
+pub struct ChaCha20 {
+    state: [u32; 16],
+    remaining_blocks: u64,
+    buffer: [u8; BUFFER_LEN],
+    buffer_pos: usize,
+    buffer_len: usize,
+}
+
+impl ChaCha20 {
+    pub fn new(key: &[u8; 32], nonce: &[u8; 12]) -> Self {
+        let mut state = [0_u32; 16];
+        state[..4].copy_from_slice(&SIGMA);
+        for (i, word) in key.chunks_exact(4).enumerate() {
+            state[4 + i] = u32::from_le_bytes(word.try_into().unwrap());
+        }
+        for (i, word) in nonce.chunks_exact(4).enumerate() {
+            state[13 + i] = u32::from_le_bytes(word.try_into().unwrap());
+        }
+
+        Self {
+            state,
+            remaining_blocks: 1 << 32,
+            buffer: [0; BUFFER_LEN],
+            buffer_pos: 0,
+            buffer_len: 0,
+        }
+    }
+
+    pub fn try_new(key: &[u8], nonce: &[u8]) -> Result<Self, KeyError> {
+        let mut key: [u8; 32] = fixed_length(key, "32 bytes")?;
+        let nonce: [u8; 12] = <[u8; 12]>::try_from(nonce)
+            .map_err(|_| KeyError::InvalidParameter("nonce must be 12 bytes"))?;
+
+        let cipher = Self::new(&key, &nonce);
+        key.zeroize();
+        Ok(cipher)
+    }
+
+    /// Moves to the start of the block with the given counter value. RFC 8439 starts at block 0
+    /// for plain encryption and at block 1 when the first block is used for a Poly1305 key.
+    pub fn seek(&mut self, block_counter: u32) {
+        self.state[12] = block_counter;
+        self.remaining_blocks = (1 << 32) - block_counter as u64;
+        self.buffer_pos = 0;
+        self.buffer_len = 0;
+    }
+
+    /// XORs the keystream into `data`, processing whole blocks at a time.
+    pub fn apply_keystream(&mut self, data: &mut [u8]) {
+        let mut data = data;
+        while !data.is_empty() {
+            if self.buffer_pos == self.buffer_len {
+                assert!(self.refill(), "ChaCha20 keystream exhausted");
+            }
+
+            let n = data.len().min(self.buffer_len - self.buffer_pos);
+            let (head, tail) = data.split_at_mut(n);
+            for (d, k) in head.iter_mut().zip(&self.buffer[self.buffer_pos..]) {
+                *d ^= k;
+            }
+            self.buffer_pos += n;
+            data = tail;
+        }
+    }
+
+    pub fn encrypt(self, plaintext: &[u8]) -> Vec<u8> {
+        StreamCipher::new(self)
+            .encrypt(plaintext.iter().cloned())
+            .collect::<Vec<_>>()
+    }
+
+    pub fn decrypt(self, ciphertext: &[u8]) -> Vec<u8> {
+        self.encrypt(ciphertext)
+    }
+
+    fn refill(&mut self) -> bool {
+        if self.remaining_blocks == 0 {
+            return false;
+        }
+
+        block::blocks::<PARALLEL_BLOCKS>(&self.state, &mut self.buffer);
+
+        let num_blocks = self.remaining_blocks.min(PARALLEL_BLOCKS as u64);
+        self.state[12] = self.state[12].wrapping_add(num_blocks as u32);
+        self.remaining_blocks -= num_blocks;
+        self.buffer_pos = 0;
+        self.buffer_len = 64 * num_blocks as usize;
+        true
+    }
+}
+
+impl Drop for ChaCha20 {
+    fn drop(&mut self) {
+        self.state.zeroize();
+        self.buffer.zeroize();
+    }
+}
+
+impl std::fmt::Debug for ChaCha20 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChaCha20").finish_non_exhaustive()
+    }
+}
+
+impl Iterator for ChaCha20 {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer_pos == self.buffer_len && !self.refill() {
+            return None;
+        }
+        let b = self.buffer[self.buffer_pos];
+        self.buffer_pos += 1;
+        Some(b)
+    }
+}
+
+/// Derives a subkey from a key and the first 128 bits of an extended nonce, as described in the
+/// XChaCha20 draft (draft-irtf-cfrg-xchacha).
+pub fn hchacha20(key: &[u8; 32], input: &[u8; 16]) -> [u8; 32] {
+    let mut state = [0_u32; 16];
+    state[..4].copy_from_slice(&SIGMA);
+    for (i, word) in key.chunks_exact(4).enumerate() {
+        state[4 + i] = u32::from_le_bytes(word.try_into().unwrap());
+    }
+    for (i, word) in input.chunks_exact(4).enumerate() {
+        state[12 + i] = u32::from_le_bytes(word.try_into().unwrap());
+    }
+
+    let mut x = block::permute_single(&state);
+
+    let mut subkey = [0_u8; 32];
+    for (i, &word) in x[..4].iter().chain(&x[12..]).enumerate() {
+        subkey[(4 * i)..][..4].copy_from_slice(&word.to_le_bytes());
+    }
+
+    state.zeroize();
+    x.zeroize();
+    subkey
+}
+
+/// ChaCha20 with a 192-bit nonce, which is large enough to be chosen at random. The first 128
+/// bits of the nonce are used to derive a subkey with HChaCha20, and the rest is used as the
+/// nonce for ChaCha20.
+pub struct XChaCha20 {
+    inner: ChaCha20,
+}
+
+impl XChaCha20 {
+    pub fn new(key: &[u8; 32], nonce: &[u8; 24]) -> Self {
+        let mut subkey = hchacha20(key, nonce[..16].try_into().unwrap());
+
+        let mut inner_nonce = [0_u8; 12];
+        inner_nonce[4..].copy_from_slice(&nonce[16..]);
+
+        let inner = ChaCha20::new(&subkey, &inner_nonce);
+        subkey.zeroize();
+        Self { inner }
+    }
+
+    pub fn try_new(key: &[u8], nonce: &[u8]) -> Result<Self, KeyError> {
+        let mut key: [u8; 32] = fixed_length(key, "32 bytes")?;
+        let nonce: [u8; 24] = <[u8; 24]>::try_from(nonce)
+            .map_err(|_| KeyError::InvalidParameter("nonce must be 24 bytes"))?;
+
+        let cipher = Self::new(&key, &nonce);
+        key.zeroize();
+        Ok(cipher)
+    }
+
+    pub fn seek(&mut self, block_counter: u32) {
+        self.inner.seek(block_counter);
+    }
+
+    pub fn apply_keystream(&mut self, data: &mut [u8]) {
+        self.inner.apply_keystream(data);
+    }
+
+    pub fn encrypt(self, plaintext: &[u8]) -> Vec<u8> {
+        StreamCipher::new(self)
+            .encrypt(plaintext.iter().cloned())
+            .collect::<Vec<_>>()
+    }
+
+    pub fn decrypt(self, ciphertext: &[u8]) -> Vec<u8> {
+        self.encrypt(ciphertext)
+    }
+}
+
+impl std::fmt::Debug for XChaCha20 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("XChaCha20").finish_non_exhaustive()
+    }
+}
+
+impl Iterator for XChaCha20 {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::ManuallyDrop;
+
+    use super::*;
+
+    fn rfc_key() -> [u8; 32] {
+        std::array::from_fn(|i| i as u8)
+    }
+
+    #[test]
+    fn block_function() {
+        // RFC 8439, section 2.3.2
+        let nonce = hex::decode("000000090000004a00000000").unwrap();
+        let mut cipher = ChaCha20::try_new(&rfc_key(), &nonce).unwrap();
+        cipher.seek(1);
+
+        let block = cipher.take(64).collect::<Vec<_>>();
+        assert_eq!(
+            hex::encode(block),
+            "10f1e7e4d13b5915500fdd1fa32071c4c7d1f4c733c068030422aa9ac3d46c4e\
+             d2826446079faa0914c2d705d98b02a2b5129cd1de164eb9cbd083e8a2503c4e"
+        );
+    }
+
+    #[test]
+    fn hchacha20_example() {
+        // draft-irtf-cfrg-xchacha-03, section 2.2.1
+        let input = hex::decode("000000090000004a0000000031415927").unwrap();
+        let subkey = hchacha20(&rfc_key(), input.as_slice().try_into().unwrap());
+        assert_eq!(
+            hex::encode(subkey),
+            "82413b4227b27bfed30e42508a877d73a0f9e4d58a74a853c12ec41326d3ecdc"
+        );
+    }
+
+    #[test]
+    fn seeking_matches_skipping_blocks() {
+        let nonce = [7; 12];
+        let skipped = ChaCha20::new(&rfc_key(), &nonce)
+            .skip(5 * 64)
+            .take(100)
+            .collect::<Vec<_>>();
+
+        let mut cipher = ChaCha20::new(&rfc_key(), &nonce);
+        cipher.next();
+        cipher.seek(5);
+        assert_eq!(cipher.take(100).collect::<Vec<_>>(), skipped);
+    }
+
+    #[test]
+    fn keystream_ends_after_last_block() {
+        let mut cipher = ChaCha20::new(&rfc_key(), &[0; 12]);
+        cipher.seek(u32::MAX - 1);
+        assert_eq!(cipher.by_ref().count(), 2 * 64);
+        assert_eq!(cipher.next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "ChaCha20 keystream exhausted")]
+    fn applying_keystream_past_the_end_panics() {
+        let mut cipher = ChaCha20::new(&rfc_key(), &[0; 12]);
+        cipher.seek(u32::MAX);
+        cipher.apply_keystream(&mut [0; 65]);
+    }
+
+    #[test]
+    fn rejects_invalid_key_and_nonce_lengths() {
+        assert!(matches!(
+            ChaCha20::try_new(&[0; 16], &[0; 12]),
+            Err(KeyError::InvalidLength { actual: 16, .. })
+        ));
+        assert_eq!(
+            ChaCha20::try_new(&[0; 32], &[0; 8]).unwrap_err(),
+            KeyError::InvalidParameter("nonce must be 12 bytes")
+        );
+        assert_eq!(
+            XChaCha20::try_new(&[0; 32], &[0; 12]).unwrap_err(),
+            KeyError::InvalidParameter("nonce must be 24 bytes")
+        );
+    }
+
+    #[test]
+    fn state_is_zeroized_on_drop() {
+        let mut cipher = ManuallyDrop::new(ChaCha20::new(&rfc_key(), &[1; 12]));
+        cipher.next();
+
+        unsafe { ManuallyDrop::drop(&mut cipher) };
+        assert_eq!(cipher.state, [0; 16]);
+        assert_eq!(cipher.buffer, [0; BUFFER_LEN]);
+    }
+
+    #[test]
+    fn debug_output_is_redacted() {
+        assert_eq!(
+            format!("{:?}", ChaCha20::new(&rfc_key(), &[0; 12])),
+            "ChaCha20 { .. }"
+        );
+        assert_eq!(
+            format!("{:?}", XChaCha20::new(&rfc_key(), &[0; 24])),
+            "XChaCha20 { .. }"
+        );
+    }
+}
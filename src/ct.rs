@@ -0,0 +1,31 @@
+//! Comparisons whose running time does not depend on the secret data being compared.
+
+use std::hint::black_box;
+
+/// Returns whether `a` and `b` are equal, looking at every byte even after a difference is found.
+/// Only the lengths are compared in variable time, since they are not secret.
+pub(crate) fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut diff = 0_u8;
+    for (x, y) in a.iter().zip(b) {
+        diff |= black_box(x ^ y);
+    }
+    black_box(diff) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_contents_and_lengths() {
+        assert!(bytes_eq(&[], &[]));
+        assert!(bytes_eq(&[1, 2, 3], &[1, 2, 3]));
+        assert!(!bytes_eq(&[1, 2, 3], &[1, 2, 4]));
+        assert!(!bytes_eq(&[0, 2, 3], &[1, 2, 3]));
+        assert!(!bytes_eq(&[1, 2, 3], &[1, 2]));
+    }
+}
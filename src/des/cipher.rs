@@ -0,0 +1,298 @@
+use crate::key::{decode_base64, decode_hex, fixed_length, KeyError};
+use crate::zeroize::Zeroize;
+use crate::{Block, BlockCipher};
+
+use super::{is_semi_weak_key, is_weak_key};
+
+// All permutation tables use 1-based bit positions counted from the most significant bit,
+// just like in FIPS 46-3.
+
+#[rustfmt::skip]
+const INITIAL_PERMUTATION: [u8; 64] = [
+    58, 50, 42, 34, 26, 18, 10, 2,
+    60, 52, 44, 36, 28, 20, 12, 4,
+    62, 54, 46, 38, 30, 22, 14, 6,
+    64, 56, 48, 40, 32, 24, 16, 8,
+    57, 49, 41, 33, 25, 17,  9, 1,
+    59, 51, 43, 35, 27, 19, 11, 3,
+    61, 53, 45, 37, 29, 21, 13, 5,
+    63, 55, 47, 39, 31, 23, 15, 7,
+];
+
+const FINAL_PERMUTATION: [u8; 64] = invert_permutation(INITIAL_PERMUTATION);
+
+#[rustfmt::skip]
+const EXPANSION: [u8; 48] = [
+    32,  1,  2,  3,  4,  5,
+     4,  5,  6,  7,  8,  9,
+     8,  9, 10, 11, 12, 13,
+    12, 13, 14, 15, 16, 17,
+    16, 17, 18, 19, 20, 21,
+    20, 21, 22, 23, 24, 25,
+    24, 25, 26, 27, 28, 29,
+    28, 29, 30, 31, 32,  1,
+];
+
+#[rustfmt::skip]
+const PERMUTATION: [u8; 32] = [
+    16,  7, 20, 21, 29, 12, 28, 17,
+     1, 15, 23, 26,  5, 18, 31, 10,
+     2,  8, 24, 14, 32, 27,  3,  9,
+    19, 13, 30,  6, 22, 11,  4, 25,
+];
+
+#[rustfmt::skip]
+const PERMUTED_CHOICE_1: [u8; 56] = [
+    57, 49, 41, 33, 25, 17,  9,
+     1, 58, 50, 42, 34, 26, 18,
+    10,  2, 59, 51, 43, 35, 27,
+    19, 11,  3, 60, 52, 44, 36,
+    63, 55, 47, 39, 31, 23, 15,
+     7, 62, 54, 46, 38, 30, 22,
+    14,  6, 61, 53, 45, 37, 29,
+    21, 13,  5, 28, 20, 12,  4,
+];
+
+#[rustfmt::skip]
+const PERMUTED_CHOICE_2: [u8; 48] = [
+    14, 17, 11, 24,  1,  5,
+     3, 28, 15,  6, 21, 10,
+    23, 19, 12,  4, 26,  8,
+    16,  7, 27, 20, 13,  2,
+    41, 52, 31, 37, 47, 55,
+    30, 40, 51, 45, 33, 48,
+    44, 49, 39, 56, 34, 53,
+    46, 42, 50, 36, 29, 32,
+];
+
+const KEY_SHIFTS: [u32; 16] = [1, 1, 2, 2, 2, 2, 2, 2, 1, 2, 2, 2, 2, 2, 2, 1];
+
+#[rustfmt::skip]
+const SBOXES: [[u8; 64]; 8] = [
+    [
+        14,  4, 13,  1,  2, 15, 11,  8,  3, 10,  6, 12,  5,  9,  0,  7,
+         0, 15,  7,  4, 14,  2, 13,  1, 10,  6, 12, 11,  9,  5,  3,  8,
+         4,  1, 14,  8, 13,  6,  2, 11, 15, 12,  9,  7,  3, 10,  5,  0,
+        15, 12,  8,  2,  4,  9,  1,  7,  5, 11,  3, 14, 10,  0,  6, 13,
+    ],
+    [
+        15,  1,  8, 14,  6, 11,  3,  4,  9,  7,  2, 13, 12,  0,  5, 10,
+         3, 13,  4,  7, 15,  2,  8, 14, 12,  0,  1, 10,  6,  9, 11,  5,
+         0, 14,  7, 11, 10,  4, 13,  1,  5,  8, 12,  6,  9,  3,  2, 15,
+        13,  8, 10,  1,  3, 15,  4,  2, 11,  6,  7, 12,  0,  5, 14,  9,
+    ],
+    [
+        10,  0,  9, 14,  6,  3, 15,  5,  1, 13, 12,  7, 11,  4,  2,  8,
+        13,  7,  0,  9,  3,  4,  6, 10,  2,  8,  5, 14, 12, 11, 15,  1,
+        13,  6,  4,  9,  8, 15,  3,  0, 11,  1,  2, 12,  5, 10, 14,  7,
+         1, 10, 13,  0,  6,  9,  8,  7,  4, 15, 14,  3, 11,  5,  2, 12,
+    ],
+    [
+         7, 13, 14,  3,  0,  6,  9, 10,  1,  2,  8,  5, 11, 12,  4, 15,
+        13,  8, 11,  5,  6, 15,  0,  3,  4,  7,  2, 12,  1, 10, 14,  9,
+        10,  6,  9,  0, 12, 11,  7, 13, 15,  1,  3, 14,  5,  2,  8,  4,
+         3, 15,  0,  6, 10,  1, 13,  8,  9,  4,  5, 11, 12,  7,  2, 14,
+    ],
+    [
+         2, 12,  4,  1,  7, 10, 11,  6,  8,  5,  3, 15, 13,  0, 14,  9,
+        14, 11,  2, 12,  4,  7, 13,  1,  5,  0, 15, 10,  3,  9,  8,  6,
+         4,  2,  1, 11, 10, 13,  7,  8, 15,  9, 12,  5,  6,  3,  0, 14,
+        11,  8, 12,  7,  1, 14,  2, 13,  6, 15,  0,  9, 10,  4,  5,  3,
+    ],
+    [
+        12,  1, 10, 15,  9,  2,  6,  8,  0, 13,  3,  4, 14,  7,  5, 11,
+        10, 15,  4,  2,  7, 12,  9,  5,  6,  1, 13, 14,  0, 11,  3,  8,
+         9, 14, 15,  5,  2,  8, 12,  3,  7,  0,  4, 10,  1, 13, 11,  6,
+         4,  3,  2, 12,  9,  5, 15, 10, 11, 14,  1,  7,  6,  0,  8, 13,
+    ],
+    [
+         4, 11,  2, 14, 15,  0,  8, 13,  3, 12,  9,  7,  5, 10,  6,  1,
+        13,  0, 11,  7,  4,  9,  1, 10, 14,  3,  5, 12,  2, 15,  8,  6,
+         1,  4, 11, 13, 12,  3,  7, 14, 10, 15,  6,  8,  0,  5,  9,  2,
+         6, 11, 13,  8,  1,  4, 10,  7,  9,  5,  0, 15, 14,  2,  3, 12,
+    ],
+    [
+        13,  2,  8,  4,  6, 15, 11,  1, 10,  9,  3, 14,  5,  0, 12,  7,
+         1, 15, 13,  8, 10,  3,  7,  4, 12,  5,  6, 11,  0, 14,  9,  2,
+         7, 11,  4,  1,  9, 12, 14,  2,  0,  6, 10, 13, 15,  3,  5,  8,
+         2,  1, 14,  7,  4, 10,  8, 13, 15, 12,  9,  0,  3,  5,  6, 11,
+    ],
+];
+
+const fn invert_permutation(table: [u8; 64]) -> [u8; 64] {
+    let mut inverse = [0; 64];
+    let mut i = 0;
+    while i < 64 {
+        inverse[table[i] as usize - 1] = i as u8 + 1;
+        i += 1;
+    }
+    inverse
+}
+
+fn permute<const N: usize>(input: u64, input_bits: u32, table: &[u8; N]) -> u64 {
+    let mut output = 0;
+    for &position in table {
+        output = (output << 1) | ((input >> (input_bits - position as u32)) & 1);
+    }
+    output
+}
+
+fn feistel(r: u32, subkey: u64) -> u32 {
+    let e = permute(r as u64, 32, &EXPANSION) ^ subkey;
+
+    let mut output = 0_u32;
+    for (i, sbox) in SBOXES.iter().enumerate() {
+        let six = (e >> (42 - 6 * i)) & 0x3f;
+        let row = ((six & 0x20) >> 4) | (six & 1);
+        let col = (six >> 1) & 0xf;
+        output = (output << 4) | sbox[(row * 16 + col) as usize] as u32;
+    }
+
+    permute(output as u64, 32, &PERMUTATION) as u32
+}
+
+pub struct Des {
+    subkeys: [u64; 16], // 48 bits each
+}
+
+impl Des {
+    /// The parity bits (the lowest bit of each byte) are ignored.
+    pub fn new(key: Block<8>) -> Des {
+        Des {
+            subkeys: Des::expand_key(key),
+        }
+    }
+
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+
+    fn expand_key(key: Block<8>) -> [u64; 16] {
+        let mut cd = permute(u64::from_be_bytes(key), 64, &PERMUTED_CHOICE_1);
+
+        let mut subkeys = [0; 16];
+        for (subkey, shift) in subkeys.iter_mut().zip(KEY_SHIFTS) {
+            let c = cd >> 28;
+            let d = cd & 0x0fff_ffff;
+            let c = ((c << shift) | (c >> (28 - shift))) & 0x0fff_ffff;
+            let d = ((d << shift) | (d >> (28 - shift))) & 0x0fff_ffff;
+            cd = (c << 28) | d;
+
+            *subkey = permute(cd, 56, &PERMUTED_CHOICE_2);
+        }
+        cd.zeroize();
+
+        subkeys
+    }
+
+    fn crypt(&self, block: Block<8>, subkeys: impl Iterator<Item = u64>) -> Block<8> {
+        let data = permute(u64::from_be_bytes(block), 64, &INITIAL_PERMUTATION);
+
+        let mut l = (data >> 32) as u32;
+        let mut r = data as u32;
+        for subkey in subkeys {
+            (l, r) = (r, l ^ feistel(r, subkey));
+        }
+
+        let output = ((r as u64) << 32) | l as u64; // the halves are swapped after the last round
+        permute(output, 64, &FINAL_PERMUTATION).to_be_bytes()
+    }
+}
+
+/// Rejects weak and semi-weak keys. Use `Des::new` to create a cipher from any key.
+impl TryFrom<&[u8]> for Des {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        let key: Block<8> = fixed_length(key, "8 bytes")?;
+        if is_weak_key(&key) || is_semi_weak_key(&key) {
+            return Err(KeyError::InvalidParameter("weak or semi-weak DES key"));
+        }
+        Ok(Self::new(key))
+    }
+}
+
+impl Drop for Des {
+    fn drop(&mut self) {
+        self.subkeys.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Des {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Des").finish_non_exhaustive()
+    }
+}
+
+impl BlockCipher<8> for Des {
+    fn encrypt(&self, plaintext: Block<8>) -> Block<8> {
+        self.crypt(plaintext, self.subkeys.iter().copied())
+    }
+
+    fn decrypt(&self, ciphertext: Block<8>) -> Block<8> {
+        self.crypt(ciphertext, self.subkeys.iter().rev().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn final_permutation_is_inverse_of_initial_permutation() {
+        let input = 0x0123456789abcdef_u64;
+        let permuted = permute(input, 64, &INITIAL_PERMUTATION);
+        assert_ne!(permuted, input);
+        assert_eq!(permute(permuted, 64, &FINAL_PERMUTATION), input);
+    }
+
+    #[test]
+    fn sbox_rows_are_permutations() {
+        for sbox in SBOXES {
+            for row in sbox.chunks_exact(16) {
+                let mut sorted = row.to_vec();
+                sorted.sort();
+                assert_eq!(sorted, (0..16).collect::<Vec<u8>>());
+            }
+        }
+    }
+
+    #[test]
+    fn subkeys_of_example_key() {
+        // from the well known DES walkthrough with key 133457799BBCDFF1
+        let subkeys = Des::expand_key(0x133457799bbcdff1_u64.to_be_bytes());
+        assert_eq!(subkeys[0], 0b000110_110000_001011_101111_111111_000111_000001_110010);
+        assert_eq!(subkeys[15], 0b110010_110011_110110_001011_000011_100001_011111_110101);
+    }
+
+    #[test]
+    fn subkeys_do_not_depend_on_parity_bits() {
+        let key = 0x133457799bbcdff1_u64.to_be_bytes();
+        let flipped = key.map(|b| b ^ 1);
+        assert_eq!(Des::expand_key(key), Des::expand_key(flipped));
+    }
+
+    #[test]
+    fn subkeys_are_zeroized_on_drop() {
+        let mut cipher = std::mem::ManuallyDrop::new(Des::new(*b"abcdefgh"));
+        unsafe { std::mem::ManuallyDrop::drop(&mut cipher) };
+        assert_eq!(cipher.subkeys, [0; 16]);
+    }
+
+    #[test]
+    fn weak_keys_are_rejected() {
+        assert!(matches!(
+            Des::from_hex("0101010101010101"),
+            Err(KeyError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            Des::from_hex("01fe01fe01fe01fe"),
+            Err(KeyError::InvalidParameter(_))
+        ));
+        assert!(Des::from_hex("133457799bbcdff1").is_ok());
+    }
+}
@@ -0,0 +1,106 @@
+use crate::Block;
+
+mod cipher;
+mod tdea;
+
+pub use cipher::Des;
+pub use tdea::Tdea;
+
+#[rustfmt::skip]
+const WEAK_KEYS: [u64; 4] = [
+    0x0101010101010101, 0xfefefefefefefefe,
+    0xe0e0e0e0f1f1f1f1, 0x1f1f1f1f0e0e0e0e,
+];
+
+#[rustfmt::skip]
+const SEMI_WEAK_KEYS: [u64; 12] = [
+    0x01fe01fe01fe01fe, 0xfe01fe01fe01fe01,
+    0x1fe01fe00ef10ef1, 0xe01fe01ff10ef10e,
+    0x01e001e001f101f1, 0xe001e001f101f101,
+    0x1ffe1ffe0efe0efe, 0xfe1ffe1ffe0efe0e,
+    0x011f011f010e010e, 0x1f011f010e010e01,
+    0xe0fee0fef1fef1fe, 0xfee0fee0fef1fef1,
+];
+
+/// Each byte of a DES key has 7 key bits and a parity bit (the lowest bit) which makes the
+/// number of set bits odd.
+pub fn has_odd_parity(key: &[u8]) -> bool {
+    key.iter().all(|b| b.count_ones() % 2 == 1)
+}
+
+pub fn set_odd_parity(key: &mut [u8]) {
+    for b in key.iter_mut() {
+        let bits = *b & 0xfe;
+        *b = bits | (bits.count_ones() % 2 == 0) as u8;
+    }
+}
+
+/// Weak keys make encryption and decryption the same operation.
+pub fn is_weak_key(key: &Block<8>) -> bool {
+    WEAK_KEYS.contains(&with_odd_parity(*key))
+}
+
+/// Semi-weak keys come in pairs where one key decrypts what the other key encrypts.
+pub fn is_semi_weak_key(key: &Block<8>) -> bool {
+    SEMI_WEAK_KEYS.contains(&with_odd_parity(*key))
+}
+
+fn with_odd_parity(key: Block<8>) -> u64 {
+    let mut key = key;
+    set_odd_parity(&mut key);
+    u64::from_be_bytes(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BlockCipher;
+
+    use super::*;
+
+    #[test]
+    fn set_parity() {
+        let mut key = [0x00, 0x01, 0x02, 0x03, 0xfe, 0xff, 0x80, 0x81];
+        set_odd_parity(&mut key);
+        assert_eq!(key, [0x01, 0x01, 0x02, 0x02, 0xfe, 0xfe, 0x80, 0x80]);
+        assert!(has_odd_parity(&key));
+    }
+
+    #[test]
+    fn check_parity() {
+        assert!(has_odd_parity(&0x133457799bbcdff1_u64.to_be_bytes()));
+        assert!(!has_odd_parity(&0x133457799bbcdff0_u64.to_be_bytes()));
+        assert!(has_odd_parity(&0x0123456789abcdef_u64.to_be_bytes()));
+        assert!(!has_odd_parity(&[0; 8]));
+    }
+
+    #[test]
+    fn detect_weak_keys_regardless_of_parity() {
+        assert!(is_weak_key(&0x0101010101010101_u64.to_be_bytes()));
+        assert!(is_weak_key(&0x0000000000000000_u64.to_be_bytes()));
+        assert!(is_weak_key(&0xe0e0e0e0f0f0f0f0_u64.to_be_bytes()));
+        assert!(!is_weak_key(&0x133457799bbcdff1_u64.to_be_bytes()));
+        assert!(!is_weak_key(&0x01fe01fe01fe01fe_u64.to_be_bytes()));
+    }
+
+    #[test]
+    fn weak_keys_encrypt_twice_to_the_plaintext() {
+        let plaintext = 0x0123456789abcdef_u64.to_be_bytes();
+        for key in WEAK_KEYS {
+            let cipher = Des::new(key.to_be_bytes());
+            assert_eq!(cipher.encrypt(cipher.encrypt(plaintext)), plaintext);
+        }
+    }
+
+    #[test]
+    fn semi_weak_key_pairs_undo_each_other() {
+        let plaintext = 0x0123456789abcdef_u64.to_be_bytes();
+        for pair in SEMI_WEAK_KEYS.chunks_exact(2) {
+            assert!(is_semi_weak_key(&pair[0].to_be_bytes()));
+            assert!(is_semi_weak_key(&pair[1].to_be_bytes()));
+
+            let first = Des::new(pair[0].to_be_bytes());
+            let second = Des::new(pair[1].to_be_bytes());
+            assert_eq!(second.encrypt(first.encrypt(plaintext)), plaintext);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use crate::key::{decode_base64, decode_hex, KeyError};
+use crate::{Block, BlockCipher};
+
+use super::cipher::Des;
+use super::with_odd_parity;
+
+/// Triple DES (TDEA) in encrypt-decrypt-encrypt form, as specified in NIST SP 800-67.
+#[derive(Debug)]
+pub struct Tdea {
+    k1: Des,
+    k2: Des,
+    k3: Des,
+}
+
+impl Tdea {
+    /// Keying option 1: three independent keys.
+    pub fn new_3key(k1: Block<8>, k2: Block<8>, k3: Block<8>) -> Tdea {
+        Tdea {
+            k1: Des::new(k1),
+            k2: Des::new(k2),
+            k3: Des::new(k3),
+        }
+    }
+
+    /// Keying option 2: the first key is also used as the third key.
+    pub fn new_2key(k1: Block<8>, k2: Block<8>) -> Tdea {
+        Tdea::new_3key(k1, k2, k1)
+    }
+
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+}
+
+/// Accepts a 16 byte (2-key) or 24 byte (3-key) bundle. Bundles where TDEA collapses into
+/// single DES (K1 = K2 or K2 = K3) are rejected.
+impl TryFrom<&[u8]> for Tdea {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        let keys: Vec<Block<8>> = match key.len() {
+            16 | 24 => key
+                .chunks_exact(8)
+                .map(|k| <Block<8>>::try_from(k).unwrap())
+                .collect(),
+            actual => {
+                return Err(KeyError::InvalidLength {
+                    expected: "16 or 24 bytes",
+                    actual,
+                })
+            }
+        };
+        let k1 = keys[0];
+        let k2 = keys[1];
+        let k3 = *keys.get(2).unwrap_or(&k1);
+
+        if with_odd_parity(k1) == with_odd_parity(k2) || with_odd_parity(k2) == with_odd_parity(k3)
+        {
+            return Err(KeyError::InvalidParameter(
+                "TDEA keys must differ, otherwise it degenerates to single DES",
+            ));
+        }
+
+        Ok(Tdea::new_3key(k1, k2, k3))
+    }
+}
+
+impl BlockCipher<8> for Tdea {
+    fn encrypt(&self, plaintext: Block<8>) -> Block<8> {
+        self.k3.encrypt(self.k2.decrypt(self.k1.encrypt(plaintext)))
+    }
+
+    fn decrypt(&self, ciphertext: Block<8>) -> Block<8> {
+        self.k1.decrypt(self.k2.encrypt(self.k3.decrypt(ciphertext)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tdea_with_equal_keys_is_des() {
+        let key = 0x133457799bbcdff1_u64.to_be_bytes();
+        let plaintext = 0x0123456789abcdef_u64.to_be_bytes();
+
+        let tdea = Tdea::new_3key(key, key, key);
+        let des = Des::new(key);
+        assert_eq!(tdea.encrypt(plaintext), des.encrypt(plaintext));
+    }
+
+    #[test]
+    fn degenerate_key_bundles_are_rejected() {
+        assert!(matches!(
+            Tdea::from_hex("0123456789abcdef0123456789abcdef"),
+            Err(KeyError::InvalidParameter(_))
+        ));
+        // K2 = K3
+        assert!(matches!(
+            Tdea::from_hex("0123456789abcdef23456789abcdef0123456789abcdef01"),
+            Err(KeyError::InvalidParameter(_))
+        ));
+        // K1 and K2 only differ in the parity bits
+        assert!(matches!(
+            Tdea::from_hex("0123456789abcdef0022446688aaccee"),
+            Err(KeyError::InvalidParameter(_))
+        ));
+        assert!(Tdea::from_hex("0123456789abcdef23456789abcdef01").is_ok());
+        assert!(matches!(
+            Tdea::try_from(&[0; 8][..]),
+            Err(KeyError::InvalidLength { actual: 8, .. })
+        ));
+    }
+}
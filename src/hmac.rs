@@ -0,0 +1,172 @@
+//! HMAC (RFC 2104), a MAC built from any hash function of the crate. The message is hashed
+//! with the key padded to the block size of the hash, and that hash is hashed again with the
+//! key, so that it is safe even for hash functions that allow length extension.
+
+use crate::ct;
+use crate::zeroize::Zeroize;
+use crate::Digest;
+
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5c;
+
+#[derive(Clone)]
+pub struct Hmac<D: Digest> {
+    inner: D,
+    outer: D,
+}
+
+impl<D: Digest> Hmac<D> {
+    /// The shortest truncated tag, 80 bits as in RFC 2104. Tags of at least half the output of
+    /// the hash are recommended, but RFC 4231 truncates even SHA-512 tags to 128 bits.
+    pub const MIN_TAG_LEN: usize = 10;
+
+    /// Starts a MAC with a key of any length. Keys longer than the block size of the hash are
+    /// hashed first, so they are no stronger than keys as long as the hash. Panics for hash
+    /// functions whose blocks are shorter than their output, such as Ascon-Hash256, for which
+    /// HMAC is not defined.
+    pub fn new(key: &[u8]) -> Self {
+        assert!(
+            D::BLOCK_SIZE >= D::OUTPUT_SIZE,
+            "HMAC is not defined for {}, whose blocks are shorter than its output",
+            D::NAME
+        );
+        let mut block = vec![0; D::BLOCK_SIZE];
+        if key.len() > D::BLOCK_SIZE {
+            let hashed_key = D::digest(key);
+            block[..D::OUTPUT_SIZE].copy_from_slice(hashed_key.as_ref());
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+
+        let mut inner = D::new();
+        let mut outer = D::new();
+        xor_into(&mut block, IPAD);
+        inner.update(&block);
+        xor_into(&mut block, IPAD ^ OPAD);
+        outer.update(&block);
+        block.zeroize();
+
+        Self { inner, outer }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    pub fn finalize(self) -> D::Output {
+        let Self { inner, mut outer } = self;
+        outer.update(inner.finalize().as_ref());
+        outer.finalize()
+    }
+
+    /// Fills `output` with the first `output.len()` bytes of the tag, which must be between
+    /// `MIN_TAG_LEN` and the output size of the hash.
+    pub fn finalize_truncated(self, output: &mut [u8]) {
+        assert!(
+            (Self::MIN_TAG_LEN..=D::OUTPUT_SIZE).contains(&output.len()),
+            "HMAC-{} tags must be {} to {} bytes long",
+            D::NAME,
+            Self::MIN_TAG_LEN,
+            D::OUTPUT_SIZE,
+        );
+        let tag = self.finalize();
+        output.copy_from_slice(&tag.as_ref()[..output.len()]);
+    }
+
+    /// Compares the computed tag with `tag` in constant time. Truncated tags are rejected.
+    pub fn verify(self, tag: &[u8]) -> bool {
+        tag.len() == D::OUTPUT_SIZE && self.verify_truncated(tag)
+    }
+
+    /// Compares the first `tag.len()` bytes of the computed tag with `tag` in constant time.
+    /// Tags shorter than `MIN_TAG_LEN` are never valid.
+    pub fn verify_truncated(self, tag: &[u8]) -> bool {
+        if !(Self::MIN_TAG_LEN..=D::OUTPUT_SIZE).contains(&tag.len()) {
+            return false;
+        }
+        let mut expected = self.finalize().as_ref()[..tag.len()].to_vec();
+        let result = ct::bytes_eq(&expected, tag);
+        expected.zeroize();
+        result
+    }
+}
+
+impl<D: Digest> std::fmt::Debug for Hmac<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Hmac")
+            .field("hash", &D::NAME)
+            .finish_non_exhaustive()
+    }
+}
+
+fn xor_into(block: &mut [u8], pad: u8) {
+    for b in block {
+        *b ^= pad;
+    }
+}
+
+pub fn hmac<D: Digest>(key: &[u8], message: &[u8]) -> D::Output {
+    let mut mac = Hmac::<D>::new(key);
+    mac.update(message);
+    mac.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sha::{Sha1, Sha256};
+
+    #[test]
+    fn long_keys_are_hashed() {
+        let key = [0x42; 65];
+        let hashed_key = Sha256::digest(&key);
+        assert_eq!(
+            hmac::<Sha256>(&key, b"message"),
+            hmac::<Sha256>(hashed_key.as_ref(), b"message")
+        );
+
+        // a key of exactly one block is used as it is
+        let key = [0x42; 64];
+        let hashed_key = Sha256::digest(&key);
+        assert_ne!(
+            hmac::<Sha256>(&key, b"message"),
+            hmac::<Sha256>(hashed_key.as_ref(), b"message")
+        );
+    }
+
+    #[test]
+    fn verify_rejects_truncated_and_too_short_tags() {
+        let mac = Hmac::<Sha256>::new(b"key");
+        let tag = mac.clone().finalize();
+
+        assert!(mac.clone().verify(tag.as_ref()));
+        assert!(!mac.clone().verify(&tag.as_ref()[..10]));
+        assert!(mac.clone().verify_truncated(&tag.as_ref()[..10]));
+        assert!(!mac.clone().verify_truncated(&tag.as_ref()[..9]));
+        assert!(!mac.verify_truncated(&[]));
+    }
+
+    #[test]
+    #[should_panic(expected = "HMAC-SHA-256 tags must be 10 to 32 bytes long")]
+    fn too_short_truncation_panics() {
+        Hmac::<Sha256>::new(b"key").finalize_truncated(&mut [0; 8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "HMAC-SHA-1 tags must be 10 to 20 bytes long")]
+    fn too_long_truncation_panics() {
+        Hmac::<Sha1>::new(b"key").finalize_truncated(&mut [0; 21]);
+    }
+
+    #[test]
+    #[should_panic(expected = "HMAC is not defined for Ascon-Hash256")]
+    fn hash_with_short_blocks_panics() {
+        Hmac::<crate::ascon::AsconHash256>::new(b"key");
+    }
+
+    #[test]
+    fn debug_shows_only_the_hash() {
+        let mac = Hmac::<Sha256>::new(b"secret key");
+        assert_eq!(format!("{mac:?}"), r#"Hmac { hash: "SHA-256", .. }"#);
+    }
+}
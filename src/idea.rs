@@ -0,0 +1,177 @@
+use crate::key::{decode_base64, decode_hex, fixed_length, KeyError};
+use crate::zeroize::Zeroize;
+use crate::{Block, BlockCipher};
+
+const ROUNDS: usize = 8;
+const SUBKEYS: usize = 6 * ROUNDS + 4;
+
+/// IDEA by Lai and Massey, with a 128-bit key. It mixes XOR, addition modulo 2^16 and
+/// multiplication modulo 2^16 + 1 on 16-bit words.
+pub struct Idea {
+    encryption_keys: [u16; SUBKEYS],
+    decryption_keys: [u16; SUBKEYS],
+}
+
+impl Idea {
+    pub fn new(key: &[u8; 16]) -> Self {
+        let mut expanded = *key;
+        let mut encryption_keys = [0_u16; SUBKEYS];
+        for chunk in encryption_keys.chunks_mut(8) {
+            for (k, word) in chunk.iter_mut().zip(expanded.chunks_exact(2)) {
+                *k = u16::from_be_bytes([word[0], word[1]]);
+            }
+            expanded = (u128::from_be_bytes(expanded).rotate_left(25)).to_be_bytes();
+        }
+        expanded.zeroize();
+
+        let e = &encryption_keys;
+        let mut decryption_keys = [0_u16; SUBKEYS];
+        for (round, d) in decryption_keys.chunks_mut(6).enumerate() {
+            // the additive subkeys of the middle rounds are swapped, since the round function
+            // swaps the two middle words
+            let k = 6 * (ROUNDS - round);
+            let (add1, add2) = if round == 0 || round == ROUNDS { (1, 2) } else { (2, 1) };
+            d[0] = mul_inv(e[k]);
+            d[1] = e[k + add1].wrapping_neg();
+            d[2] = e[k + add2].wrapping_neg();
+            d[3] = mul_inv(e[k + 3]);
+            if round < ROUNDS {
+                d[4] = e[k - 2];
+                d[5] = e[k - 1];
+            }
+        }
+
+        Self { encryption_keys, decryption_keys }
+    }
+
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+}
+
+/// Multiplication modulo 2^16 + 1, where 0 represents 2^16.
+fn mul(a: u16, b: u16) -> u16 {
+    let a = if a == 0 { 0x10000 } else { a as u64 };
+    let b = if b == 0 { 0x10000 } else { b as u64 };
+    (a * b % 0x10001) as u16
+}
+
+/// The multiplicative inverse modulo 2^16 + 1, which is prime. By Fermat's little theorem it is
+/// x^(2^16 - 1).
+fn mul_inv(x: u16) -> u16 {
+    let mut result = 1;
+    let mut power = x;
+    for _ in 0..16 {
+        result = mul(result, power);
+        power = mul(power, power);
+    }
+    result
+}
+
+fn crypt(block: Block<8>, keys: &[u16; SUBKEYS]) -> Block<8> {
+    let word = |i: usize| u16::from_be_bytes([block[2 * i], block[2 * i + 1]]);
+    let [mut x1, mut x2, mut x3, mut x4] = [word(0), word(1), word(2), word(3)];
+
+    for k in keys.chunks_exact(6) {
+        x1 = mul(x1, k[0]);
+        x2 = x2.wrapping_add(k[1]);
+        x3 = x3.wrapping_add(k[2]);
+        x4 = mul(x4, k[3]);
+
+        let t0 = mul(k[4], x1 ^ x3);
+        let t1 = mul(k[5], t0.wrapping_add(x2 ^ x4));
+        let t2 = t0.wrapping_add(t1);
+
+        (x1, x2, x3, x4) = (x1 ^ t1, x3 ^ t1, x2 ^ t2, x4 ^ t2);
+    }
+
+    // the output transformation undoes the swap of the middle words in the last round
+    let k = &keys[(6 * ROUNDS)..];
+    let y = [
+        mul(x1, k[0]),
+        x3.wrapping_add(k[1]),
+        x2.wrapping_add(k[2]),
+        mul(x4, k[3]),
+    ];
+
+    let mut res = [0_u8; 8];
+    for (chunk, w) in res.chunks_exact_mut(2).zip(y) {
+        chunk.copy_from_slice(&w.to_be_bytes());
+    }
+    res
+}
+
+impl TryFrom<&[u8]> for Idea {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        let mut key: [u8; 16] = fixed_length(key, "16 bytes")?;
+        let cipher = Self::new(&key);
+        key.zeroize();
+        Ok(cipher)
+    }
+}
+
+impl Drop for Idea {
+    fn drop(&mut self) {
+        self.encryption_keys.zeroize();
+        self.decryption_keys.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Idea {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Idea").finish_non_exhaustive()
+    }
+}
+
+impl BlockCipher<8> for Idea {
+    fn encrypt(&self, plaintext: Block<8>) -> Block<8> {
+        crypt(plaintext, &self.encryption_keys)
+    }
+
+    fn decrypt(&self, ciphertext: Block<8>) -> Block<8> {
+        crypt(ciphertext, &self.decryption_keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiplication_treats_zero_as_two_to_the_sixteenth() {
+        assert_eq!(mul(0, 0), 1);
+        assert_eq!(mul(0, 2), 0xffff);
+        assert_eq!(mul(0x8000, 2), 0);
+        for x in [0, 1, 2, 3, 0x1234, 0x8000, 0xffff] {
+            assert_eq!(mul(x, mul_inv(x)), 1, "x = {x:#x}");
+        }
+    }
+
+    #[test]
+    fn key_length_is_validated() {
+        assert!(matches!(
+            Idea::try_from(&[0; 15][..]),
+            Err(KeyError::InvalidLength { actual: 15, .. })
+        ));
+        assert!(Idea::try_from(&[0; 16][..]).is_ok());
+    }
+
+    #[test]
+    fn subkeys_are_zeroized_on_drop() {
+        let mut cipher = std::mem::ManuallyDrop::new(Idea::new(&[1; 16]));
+        unsafe { std::mem::ManuallyDrop::drop(&mut cipher) };
+        assert_eq!(cipher.encryption_keys, [0; SUBKEYS]);
+        assert_eq!(cipher.decryption_keys, [0; SUBKEYS]);
+    }
+
+    #[test]
+    fn debug_output_is_redacted() {
+        assert_eq!(format!("{:?}", Idea::new(&[0; 16])), "Idea { .. }");
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt::Display;
+
+use base64::prelude::*;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum KeyError {
+    InvalidLength {
+        expected: &'static str,
+        actual: usize,
+    },
+    InvalidParameter(&'static str),
+    InvalidHex(hex::FromHexError),
+    InvalidBase64(base64::DecodeError),
+}
+
+impl Display for KeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyError::InvalidLength { expected, actual } => {
+                write!(f, "invalid key length, expected {expected} but got {actual} bytes")
+            }
+            KeyError::InvalidParameter(message) => write!(f, "invalid parameter: {message}"),
+            KeyError::InvalidHex(e) => write!(f, "invalid hex key: {e}"),
+            KeyError::InvalidBase64(e) => write!(f, "invalid base64 key: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for KeyError {}
+
+pub(crate) fn decode_hex(hex: impl AsRef<[u8]>) -> Result<Vec<u8>, KeyError> {
+    hex::decode(hex).map_err(KeyError::InvalidHex)
+}
+
+pub(crate) fn decode_base64(base64: impl AsRef<[u8]>) -> Result<Vec<u8>, KeyError> {
+    BASE64_STANDARD
+        .decode(base64)
+        .map_err(KeyError::InvalidBase64)
+}
+
+pub(crate) fn fixed_length<const N: usize>(
+    key: &[u8],
+    expected: &'static str,
+) -> Result<[u8; N], KeyError> {
+    <[u8; N]>::try_from(key).map_err(|_| KeyError::InvalidLength {
+        expected,
+        actual: key.len(),
+    })
+}
+
+pub(crate) fn check_length(
+    key: &[u8],
+    range: impl std::ops::RangeBounds<usize>,
+    expected: &'static str,
+) -> Result<(), KeyError> {
+    if range.contains(&key.len()) {
+        Ok(())
+    } else {
+        Err(KeyError::InvalidLength {
+            expected,
+            actual: key.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_length_accepts_exact_length() {
+        assert_eq!(fixed_length::<3>(&[1, 2, 3], "3 bytes"), Ok([1, 2, 3]));
+    }
+
+    #[test]
+    fn fixed_length_rejects_other_lengths() {
+        assert_eq!(
+            fixed_length::<3>(&[1, 2], "3 bytes"),
+            Err(KeyError::InvalidLength {
+                expected: "3 bytes",
+                actual: 2
+            })
+        );
+        assert!(fixed_length::<3>(&[1, 2, 3, 4], "3 bytes").is_err());
+    }
+
+    #[test]
+    fn check_length_uses_inclusive_range() {
+        assert!(check_length(&[1], 1..=2, "1 to 2 bytes").is_ok());
+        assert!(check_length(&[1, 2], 1..=2, "1 to 2 bytes").is_ok());
+        assert!(check_length(&[], 1..=2, "1 to 2 bytes").is_err());
+        assert!(check_length(&[1, 2, 3], 1..=2, "1 to 2 bytes").is_err());
+    }
+
+    #[test]
+    fn decode_hex_reports_invalid_input() {
+        assert_eq!(decode_hex("0aff"), Ok(vec![0x0a, 0xff]));
+        assert!(matches!(decode_hex("0g"), Err(KeyError::InvalidHex(_))));
+    }
+
+    #[test]
+    fn decode_base64_reports_invalid_input() {
+        assert_eq!(decode_base64("c29tZSBkYXRh"), Ok(b"some data".to_vec()));
+        assert!(matches!(decode_base64("c29t!"), Err(KeyError::InvalidBase64(_))));
+    }
+}
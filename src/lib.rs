@@ -1,33 +1,93 @@
+pub mod aead;
 pub mod aes;
+pub mod aria;
+pub mod ascon;
 pub mod big;
+pub mod blake2;
+pub mod blake3;
+pub mod blowfish;
+pub mod camellia;
+pub mod cast;
+pub mod chacha;
+pub mod des;
+pub mod hmac;
+pub mod idea;
+pub mod key;
 pub mod pad;
 pub mod pem;
+pub mod poly1305;
 pub mod rsa;
+pub mod salsa;
+pub mod serpent;
 pub mod sha;
+pub mod sha3;
+pub mod simon;
+pub mod speck;
+pub mod twofish;
+pub mod xtea;
+pub mod zeroize;
 
-mod md {
-    pub mod md2;
-    pub mod md4;
-    pub mod md5;
-}
+mod ct;
+
+mod md;
 mod rc {
     pub mod rc2;
     pub mod rc4;
     pub mod rc5;
+    pub mod rc6;
+    pub mod spritz;
 }
 
 pub use md::md2::hash as md2;
+pub use md::md2::Md2;
 pub use md::md4::hash as md4;
+pub use md::md4::Md4;
 pub use md::md5::hash as md5;
+pub use md::md5::Md5;
+pub use md::ripemd160::hash as ripemd160;
+pub use md::ripemd160::Ripemd160;
+pub use md::whirlpool::hash as whirlpool;
+pub use md::whirlpool::Whirlpool;
 
 pub use rc::rc2::RC2 as rc2;
 pub use rc::rc4::KeyStream as rc4;
+pub use rc::rc4::RC4A as rc4a;
 pub use rc::rc5::RC5 as rc5;
+pub use rc::rc6::RC6 as rc6;
+pub use rc::spritz::Spritz as spritz;
+
+/// Cloning a hasher forks its state, so that messages with a common prefix only need to hash the
+/// prefix once.
+pub trait Digest: Clone {
+    /// The hash value, which is `OUTPUT_SIZE` bytes long.
+    type Output: AsRef<[u8]> + Clone + PartialEq + std::fmt::Debug;
+
+    const OUTPUT_SIZE: usize;
+
+    /// The number of bytes processed at a time, which HMAC pads the key to.
+    const BLOCK_SIZE: usize;
+
+    const NAME: &'static str;
 
-trait HashFunction: Clone {
-    type Output;
+    /// The DER encoding of the algorithm identifier, without tag and length, as it appears in
+    /// the `DigestInfo` of PKCS #1 signatures. Not every hash function has one.
+    const OID: Option<&'static [u8]>;
 
-    fn hash(&self, message: &[u8]) -> Self::Output;
+    fn new() -> Self;
+
+    fn update(&mut self, data: &[u8]);
+
+    fn finalize(self) -> Self::Output;
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn digest(message: &[u8]) -> Self::Output {
+        let mut hasher = Self::new();
+        hasher.update(message);
+        hasher.finalize()
+    }
 }
 
 pub type Block<const N: usize> = [u8; N];
@@ -1,52 +1,62 @@
-pub fn hash<'a>(data: impl IntoIterator<Item = &'a u8>) -> [u8; 16] {
-    let mut hasher = Hasher::new();
-    for b in data.into_iter() {
-        hasher.consume(*b);
-    }
-    hasher.finish()
+use crate::sha::buffer::BlockBuffer;
+use crate::sha::Hash128;
+use crate::zeroize::Zeroize;
+use crate::{Block, Digest};
+
+/// Computes the MD2 digest of the bytes of `data`. Slices are better passed to
+/// [`Md2::update`], which does not need to copy them into blocks first.
+pub fn hash<'a>(data: impl IntoIterator<Item = &'a u8>) -> Hash128 {
+    super::hash_bytes::<Md2>(data)
 }
 
-struct Hasher {
-    chunk: Vec<u8>,
-    checksum: [u8; 16],
-    l: u8,
-    x: [u8; 48],
+#[derive(Clone)]
+pub struct Md2 {
+    state: State,
+    buffer: BlockBuffer<16>,
 }
 
-impl Hasher {
-    fn new() -> Self {
+impl Md2 {
+    pub fn new() -> Self {
         Self {
-            chunk: Vec::with_capacity(16),
-            checksum: [0; 16],
-            l: 0,
-            x: [0; 48],
+            state: State {
+                checksum: [0; 16],
+                l: 0,
+                x: [0; 48],
+            },
+            buffer: BlockBuffer::new(),
         }
     }
 
-    fn consume(&mut self, byte: u8) {
-        self.chunk.push(byte);
-        if self.chunk.len() == 16 {
-            self.process_chunk();
-        }
+    pub fn update(&mut self, data: &[u8]) {
+        let state = &mut self.state;
+        self.buffer.update(data, |chunk| state.process_chunk(chunk));
     }
 
-    fn finish(mut self) -> [u8; 16] {
-        let pad = 16 - self.chunk.len() as u8;
-        for _ in 0..pad {
-            self.chunk.push(pad);
-        }
-        self.process_chunk();
+    pub fn finalize(mut self) -> Hash128 {
+        // every message is padded, with the number of padding bytes
+        let remainder = self.buffer.remainder();
+        let pad = 16 - remainder.len() as u8;
+        let mut last_chunk = [pad; 16];
+        last_chunk[..remainder.len()].copy_from_slice(remainder);
+        self.state.process_chunk(&last_chunk);
 
-        self.chunk.extend_from_slice(&self.checksum);
-        self.process_chunk();
+        let checksum = self.state.checksum;
+        self.state.process_chunk(&checksum);
 
-        <[u8; 16]>::try_from(&self.x[0..16]).unwrap()
+        <[u8; 16]>::try_from(&self.state.x[0..16]).unwrap().into()
     }
+}
 
-    fn process_chunk(&mut self) {
-        let chunk = <[u8; 16]>::try_from(&self.chunk[0..16]).unwrap();
-        self.chunk.clear();
+#[derive(Clone)]
+struct State {
+    checksum: [u8; 16],
+    /// The last byte of the checksum.
+    l: u8,
+    x: [u8; 48],
+}
 
+impl State {
+    fn process_chunk(&mut self, chunk: &Block<16>) {
         for (j, &c) in chunk.iter().enumerate() {
             self.checksum[j] ^= S[(c ^ self.l) as usize];
             self.l = self.checksum[j];
@@ -71,6 +81,48 @@ impl Hasher {
     }
 }
 
+impl Drop for State {
+    fn drop(&mut self) {
+        self.checksum.zeroize();
+        self.l.zeroize();
+        self.x.zeroize();
+    }
+}
+
+impl Default for Md2 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for Md2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Md2").finish_non_exhaustive()
+    }
+}
+
+impl Digest for Md2 {
+    type Output = Hash128;
+
+    const OUTPUT_SIZE: usize = 16;
+    const BLOCK_SIZE: usize = 16;
+    const NAME: &'static str = "MD2";
+    // 1.2.840.113549.2 followed by the number of the hash function
+    const OID: Option<&'static [u8]> = Some(&[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x02, 0x02]);
+
+    fn new() -> Self {
+        Self::new()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.update(data);
+    }
+
+    fn finalize(self) -> Self::Output {
+        self.finalize()
+    }
+}
+
 const S: [u8; 256] = [
     41, 46, 67, 201, 162, 216, 124, 1, 61, 54, 84, 161, 236, 240, 6, 19, 98, 167, 5, 243, 192, 199,
     115, 140, 152, 147, 43, 217, 188, 76, 130, 202, 30, 155, 87, 60, 253, 212, 224, 22, 103, 66,
@@ -86,3 +138,18 @@ const S: [u8; 256] = [
     102, 88, 208, 228, 166, 119, 114, 248, 235, 117, 75, 10, 49, 68, 80, 180, 143, 237, 31, 26,
     219, 153, 141, 51, 159, 17, 131, 20,
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn large_updates_are_not_copied_byte_by_byte() {
+        let mut hasher = Md2::new();
+        hasher.update(&[1; 3]);
+        hasher.update(&[2; 16 * 1000 + 7]);
+        // only the first block, which is completed in the buffer, and the bytes after the last
+        // whole block are copied
+        assert_eq!(hasher.buffer.copied_bytes(), 3 + (16 - 3) + 10);
+    }
+}
@@ -1,78 +1,103 @@
-pub fn hash<'a>(data: impl IntoIterator<Item = &'a u8>) -> [u8; 16] {
-    let mut hasher = Hasher::new();
-
-    for byte in data.into_iter() {
-        hasher.consume(*byte);
-    }
-
-    hasher.finish()
+use crate::sha::buffer::BlockBuffer;
+use crate::sha::pad::md_padding;
+use crate::sha::Hash128;
+use crate::zeroize::Zeroize;
+use crate::Digest;
+
+const INITIAL_HASH: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+/// Computes the MD5 digest of the bytes of `data`. Slices are better passed to
+/// [`Md5::update`], which does not need to copy them into blocks first.
+pub fn hash<'a>(data: impl IntoIterator<Item = &'a u8>) -> Hash128 {
+    super::hash_bytes::<Md5>(data)
 }
 
-struct Hasher {
-    unprocessed_bytes: Vec<u8>,
-    bytes_consumed: usize,
-
-    a: u32,
-    b: u32,
-    c: u32,
-    d: u32,
+#[derive(Clone)]
+pub struct Md5 {
+    hash: [u32; 4],
+    buffer: BlockBuffer<64>,
 }
 
-impl Hasher {
-    fn new() -> Self {
+impl Md5 {
+    pub fn new() -> Self {
         Self {
-            unprocessed_bytes: Vec::with_capacity(64),
-            bytes_consumed: 0,
-
-            a: 0x01234567_u32.swap_bytes(),
-            b: 0x89abcdef_u32.swap_bytes(),
-            c: 0xfedcba98_u32.swap_bytes(),
-            d: 0x76543210_u32.swap_bytes(),
+            hash: INITIAL_HASH,
+            buffer: BlockBuffer::new(),
         }
     }
 
-    fn consume(&mut self, byte: u8) {
-        self.bytes_consumed += 1;
-        self.unprocessed_bytes.push(byte);
-        if self.unprocessed_bytes.len() == 64 {
-            self.process_block();
+    pub fn update(&mut self, data: &[u8]) {
+        let hash = &mut self.hash;
+        self.buffer.update(data, |chunk| process_chunk(hash, chunk));
+    }
+
+    pub fn finalize(mut self) -> Hash128 {
+        let message_length = self.buffer.message_length() as u64;
+        let (last_chunk, extra_chunk) =
+            md_padding(self.buffer.remainder(), &(message_length * 8).to_le_bytes());
+
+        process_chunk(&mut self.hash, &last_chunk);
+        if let Some(extra_chunk) = extra_chunk {
+            process_chunk(&mut self.hash, &extra_chunk);
+        }
+
+        let mut out = [0; 16];
+        for (bytes, word) in out.chunks_exact_mut(4).zip(self.hash) {
+            bytes.copy_from_slice(&word.to_le_bytes());
         }
+        out.into()
     }
+}
 
-    fn process_block(&mut self) {
-        let block = Block::from_slice(&self.unprocessed_bytes);
-        self.unprocessed_bytes.clear();
+impl Default for Md5 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-        let (a, b, c, d) = block.process(self.a, self.b, self.c, self.d);
+impl Drop for Md5 {
+    fn drop(&mut self) {
+        self.hash.zeroize();
+    }
+}
 
-        self.a = self.a.wrapping_add(a);
-        self.b = self.b.wrapping_add(b);
-        self.c = self.c.wrapping_add(c);
-        self.d = self.d.wrapping_add(d);
+impl std::fmt::Debug for Md5 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Md5").finish_non_exhaustive()
     }
+}
 
-    fn finish(mut self) -> [u8; 16] {
-        let bits_consumed = (self.bytes_consumed * 8) as u64;
-        let num_pad_bytes = match self.unprocessed_bytes.len() {
-            56 => 64,
-            a => (64 + 56 - a) % 64,
-        };
+impl Digest for Md5 {
+    type Output = Hash128;
 
-        self.consume(0x80);
-        for _ in 0..num_pad_bytes - 1 {
-            self.consume(0);
-        }
-        for byte in bits_consumed.to_le_bytes() {
-            self.consume(byte);
-        }
+    const OUTPUT_SIZE: usize = 16;
+    const BLOCK_SIZE: usize = 64;
+    const NAME: &'static str = "MD5";
+    // 1.2.840.113549.2 followed by the number of the hash function
+    const OID: Option<&'static [u8]> = Some(&[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x02, 0x05]);
 
-        let mut out = [0; 16];
-        out[0..4].copy_from_slice(&self.a.to_le_bytes());
-        out[4..8].copy_from_slice(&self.b.to_le_bytes());
-        out[8..12].copy_from_slice(&self.c.to_le_bytes());
-        out[12..16].copy_from_slice(&self.d.to_le_bytes());
-        out
+    fn new() -> Self {
+        Self::new()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.update(data);
     }
+
+    fn finalize(self) -> Self::Output {
+        self.finalize()
+    }
+}
+
+/// Adds the result of processing `chunk` to the hash.
+fn process_chunk(hash: &mut [u32; 4], chunk: &[u8; 64]) {
+    let [a, b, c, d] = *hash;
+    let (a, b, c, d) = Block::from_chunk(chunk).process(a, b, c, d);
+
+    hash[0] = hash[0].wrapping_add(a);
+    hash[1] = hash[1].wrapping_add(b);
+    hash[2] = hash[2].wrapping_add(c);
+    hash[3] = hash[3].wrapping_add(d);
 }
 
 struct Block {
@@ -80,11 +105,10 @@ struct Block {
 }
 
 impl Block {
-    fn from_slice(chunk: &[u8]) -> Self {
+    fn from_chunk(chunk: &[u8; 64]) -> Self {
         let mut x = [0_u32; 16];
-        for (j, v) in x.iter_mut().enumerate() {
-            let idx = j * 4;
-            *v = u32::from_le_bytes(<[u8; 4]>::try_from(&chunk[idx..(idx + 4)]).unwrap());
+        for (v, bytes) in x.iter_mut().zip(chunk.chunks_exact(4)) {
+            *v = u32::from_le_bytes(bytes.try_into().unwrap());
         }
         Self { x }
     }
@@ -199,3 +223,18 @@ const T: [u32; 64] = [
     0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb,
     0xeb86d391,
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn large_updates_are_not_copied_byte_by_byte() {
+        let mut hasher = Md5::new();
+        hasher.update(&[1; 3]);
+        hasher.update(&[2; 64 * 1000 + 7]);
+        // only the first block, which is completed in the buffer, and the bytes after the last
+        // whole block are copied
+        assert_eq!(hasher.buffer.copied_bytes(), 3 + (64 - 3) + 10);
+    }
+}
@@ -0,0 +1,27 @@
+pub mod md2;
+pub mod md4;
+pub mod md5;
+pub mod ripemd160;
+pub mod whirlpool;
+
+use crate::zeroize::Zeroize;
+use crate::Digest;
+
+/// Hashes the bytes of an iterator, which are collected into blocks first so that the hasher
+/// does not have to be called for every byte.
+fn hash_bytes<'a, D: Digest>(data: impl IntoIterator<Item = &'a u8>) -> D::Output {
+    let mut hasher = D::new();
+    let mut block = [0_u8; 64];
+    let mut len = 0;
+    for &byte in data {
+        block[len] = byte;
+        len += 1;
+        if len == block.len() {
+            hasher.update(&block);
+            len = 0;
+        }
+    }
+    hasher.update(&block[..len]);
+    block.zeroize();
+    hasher.finalize()
+}
@@ -0,0 +1,183 @@
+use crate::sha::buffer::BlockBuffer;
+use crate::sha::pad::md_padding;
+use crate::sha::Hash160;
+use crate::zeroize::Zeroize;
+use crate::Digest;
+
+const INITIAL_HASH: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+/// The constants of the five rounds of the left and of the right line.
+const K_LEFT: [u32; 5] = [0x00000000, 0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xa953fd4e];
+const K_RIGHT: [u32; 5] = [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0x7a6d76e9, 0x00000000];
+
+/// The order in which the words of a block are used by the left and by the right line.
+#[rustfmt::skip]
+const R_LEFT: [usize; 80] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+    7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8,
+    3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12,
+    1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2,
+    4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13,
+];
+
+#[rustfmt::skip]
+const R_RIGHT: [usize; 80] = [
+    5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12,
+    6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2,
+    15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13,
+    8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14,
+    12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11,
+];
+
+/// The rotation amounts of the left and of the right line.
+#[rustfmt::skip]
+const S_LEFT: [u32; 80] = [
+    11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8,
+    7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15, 9, 11, 7, 13, 12,
+    11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5,
+    11, 12, 14, 15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12,
+    9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6,
+];
+
+#[rustfmt::skip]
+const S_RIGHT: [u32; 80] = [
+    8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6,
+    9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11,
+    9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5,
+    15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8,
+    8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11,
+];
+
+/// Computes the RIPEMD-160 digest of the bytes of `data`. Slices are better passed to
+/// [`Ripemd160::update`], which does not need to copy them into blocks first.
+pub fn hash<'a>(data: impl IntoIterator<Item = &'a u8>) -> Hash160 {
+    super::hash_bytes::<Ripemd160>(data)
+}
+
+/// The second half of Bitcoin's HASH160, which is RIPEMD-160 of the SHA-256 digest.
+#[derive(Clone)]
+pub struct Ripemd160 {
+    hash: [u32; 5],
+    buffer: BlockBuffer<64>,
+}
+
+impl Ripemd160 {
+    pub fn new() -> Self {
+        Self {
+            hash: INITIAL_HASH,
+            buffer: BlockBuffer::new(),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        let hash = &mut self.hash;
+        self.buffer.update(data, |chunk| process_chunk(hash, chunk));
+    }
+
+    pub fn finalize(mut self) -> Hash160 {
+        // unlike SHA-1, the length is little-endian
+        let message_length = self.buffer.message_length() as u64;
+        let (last_chunk, extra_chunk) =
+            md_padding(self.buffer.remainder(), &(message_length * 8).to_le_bytes());
+
+        process_chunk(&mut self.hash, &last_chunk);
+        if let Some(extra_chunk) = extra_chunk {
+            process_chunk(&mut self.hash, &extra_chunk);
+        }
+
+        let mut out = [0; 20];
+        for (bytes, word) in out.chunks_exact_mut(4).zip(self.hash) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        out.into()
+    }
+}
+
+impl Default for Ripemd160 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Ripemd160 {
+    fn drop(&mut self) {
+        self.hash.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Ripemd160 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ripemd160").finish_non_exhaustive()
+    }
+}
+
+impl Digest for Ripemd160 {
+    type Output = Hash160;
+
+    const OUTPUT_SIZE: usize = 20;
+    const BLOCK_SIZE: usize = 64;
+    const NAME: &'static str = "RIPEMD-160";
+    // 1.3.36.3.2.1
+    const OID: Option<&'static [u8]> = Some(&[0x2b, 0x24, 0x03, 0x02, 0x01]);
+
+    fn new() -> Self {
+        Self::new()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.update(data);
+    }
+
+    fn finalize(self) -> Self::Output {
+        self.finalize()
+    }
+}
+
+/// The boolean function of round `round`. The right line uses the functions in reverse order.
+fn f(round: usize, x: u32, y: u32, z: u32) -> u32 {
+    match round {
+        0 => x ^ y ^ z,
+        1 => (x & y) | (!x & z),
+        2 => (x | !y) ^ z,
+        3 => (x & z) | (y & !z),
+        _ => x ^ (y | !z),
+    }
+}
+
+/// Runs the two parallel lines over a chunk and combines them with the hash.
+fn process_chunk(hash: &mut [u32; 5], chunk: &[u8; 64]) {
+    let mut x = [0_u32; 16];
+    for (word, bytes) in x.iter_mut().zip(chunk.chunks_exact(4)) {
+        *word = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+
+    let [mut al, mut bl, mut cl, mut dl, mut el] = *hash;
+    let [mut ar, mut br, mut cr, mut dr, mut er] = *hash;
+
+    for j in 0..80 {
+        let round = j / 16;
+
+        let t = al
+            .wrapping_add(f(round, bl, cl, dl))
+            .wrapping_add(x[R_LEFT[j]])
+            .wrapping_add(K_LEFT[round])
+            .rotate_left(S_LEFT[j])
+            .wrapping_add(el);
+        (al, bl, cl, dl, el) = (el, t, bl, cl.rotate_left(10), dl);
+
+        let t = ar
+            .wrapping_add(f(4 - round, br, cr, dr))
+            .wrapping_add(x[R_RIGHT[j]])
+            .wrapping_add(K_RIGHT[round])
+            .rotate_left(S_RIGHT[j])
+            .wrapping_add(er);
+        (ar, br, cr, dr, er) = (er, t, br, cr.rotate_left(10), dr);
+    }
+
+    let t = hash[1].wrapping_add(cl).wrapping_add(dr);
+    hash[1] = hash[2].wrapping_add(dl).wrapping_add(er);
+    hash[2] = hash[3].wrapping_add(el).wrapping_add(ar);
+    hash[3] = hash[4].wrapping_add(al).wrapping_add(br);
+    hash[4] = hash[0].wrapping_add(bl).wrapping_add(cr);
+    hash[0] = t;
+}
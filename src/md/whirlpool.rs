@@ -0,0 +1,232 @@
+use crate::sha::buffer::BlockBuffer;
+use crate::sha::pad::md_padding;
+use crate::sha::Hash512;
+use crate::zeroize::Zeroize;
+use crate::Digest;
+
+const ROUNDS: usize = 10;
+
+/// The mini-boxes the S-box is built from, and the inverse of `E`.
+#[rustfmt::skip]
+const E: [u8; 16] = [0x1, 0xb, 0x9, 0xc, 0xd, 0x6, 0xf, 0x3, 0xe, 0x8, 0x7, 0x4, 0xa, 0x2, 0x5, 0x0];
+#[rustfmt::skip]
+const R: [u8; 16] = [0x7, 0xc, 0xb, 0xd, 0xe, 0x4, 0x9, 0xf, 0x6, 0x3, 0x8, 0xa, 0x2, 0x5, 0x1, 0x0];
+const E_INV: [u8; 16] = invert_mini_box(E);
+
+const SBOX: [u8; 256] = calculate_sbox();
+
+const POLYNOMIAL: u16 = 0x11d; // x^8 + x^4 + x^3 + x^2 + 1
+
+/// The first row of the circulant matrix of the diffusion layer.
+const MDS_ROW: [u8; 8] = [0x01, 0x01, 0x04, 0x01, 0x08, 0x05, 0x02, 0x09];
+
+/// The S-box combined with the diffusion layer: `TABLE[x]` is the row that a byte `x` in the
+/// first column contributes, the other columns contribute the same row rotated.
+const TABLE: [u64; 256] = calculate_table();
+
+const ROUND_CONSTANTS: [u64; ROUNDS] = calculate_round_constants();
+
+const fn invert_mini_box(b: [u8; 16]) -> [u8; 16] {
+    let mut inverse = [0; 16];
+    let mut x = 0;
+    while x < 16 {
+        inverse[b[x] as usize] = x as u8;
+        x += 1;
+    }
+    inverse
+}
+
+const fn calculate_sbox() -> [u8; 256] {
+    let mut sbox = [0; 256];
+    let mut x = 0;
+    while x < 256 {
+        let hi = E[x >> 4];
+        let lo = E_INV[x & 0xf];
+        let r = R[(hi ^ lo) as usize];
+        sbox[x] = (E[(hi ^ r) as usize] << 4) | E_INV[(lo ^ r) as usize];
+        x += 1;
+    }
+    sbox
+}
+
+const fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut r = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            r ^= a;
+        }
+        let hi = a & 0x80 != 0;
+        a <<= 1;
+        if hi {
+            a ^= (POLYNOMIAL & 0xff) as u8;
+        }
+        b >>= 1;
+    }
+    r
+}
+
+const fn calculate_table() -> [u64; 256] {
+    let mut table = [0; 256];
+    let mut x = 0;
+    while x < 256 {
+        let mut row = [0; 8];
+        let mut j = 0;
+        while j < 8 {
+            row[j] = gf_mul(SBOX[x], MDS_ROW[j]);
+            j += 1;
+        }
+        table[x] = u64::from_be_bytes(row);
+        x += 1;
+    }
+    table
+}
+
+/// The constant of round `r` is the first row of the state, filled with consecutive S-box
+/// entries.
+const fn calculate_round_constants() -> [u64; ROUNDS] {
+    let mut constants = [0; ROUNDS];
+    let mut r = 0;
+    while r < ROUNDS {
+        let mut row = [0; 8];
+        let mut j = 0;
+        while j < 8 {
+            row[j] = SBOX[8 * r + j];
+            j += 1;
+        }
+        constants[r] = u64::from_be_bytes(row);
+        r += 1;
+    }
+    constants
+}
+
+/// Computes the Whirlpool digest of the bytes of `data`. Slices are better passed to
+/// [`Whirlpool::update`], which does not need to copy them into blocks first.
+pub fn hash<'a>(data: impl IntoIterator<Item = &'a u8>) -> Hash512 {
+    super::hash_bytes::<Whirlpool>(data)
+}
+
+/// The final version of Whirlpool, as standardized in ISO/IEC 10118-3, not Whirlpool-0 or
+/// Whirlpool-T.
+#[derive(Clone)]
+pub struct Whirlpool {
+    hash: [u64; 8],
+    buffer: BlockBuffer<64>,
+}
+
+impl Whirlpool {
+    pub fn new() -> Self {
+        Self {
+            hash: [0; 8],
+            buffer: BlockBuffer::new(),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        let hash = &mut self.hash;
+        self.buffer.update(data, |chunk| process_chunk(hash, chunk));
+    }
+
+    pub fn finalize(mut self) -> Hash512 {
+        // the length field is 256 bits long
+        let mut length = [0; 32];
+        length[16..].copy_from_slice(&(self.buffer.message_length() * 8).to_be_bytes());
+        let (last_chunk, extra_chunk) = md_padding(self.buffer.remainder(), &length);
+
+        process_chunk(&mut self.hash, &last_chunk);
+        if let Some(extra_chunk) = extra_chunk {
+            process_chunk(&mut self.hash, &extra_chunk);
+        }
+
+        self.hash.into()
+    }
+}
+
+impl Default for Whirlpool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Whirlpool {
+    fn drop(&mut self) {
+        self.hash.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Whirlpool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Whirlpool").finish_non_exhaustive()
+    }
+}
+
+impl Digest for Whirlpool {
+    type Output = Hash512;
+
+    const OUTPUT_SIZE: usize = 64;
+    const BLOCK_SIZE: usize = 64;
+    const NAME: &'static str = "Whirlpool";
+    // 1.0.10118.3.0.55
+    const OID: Option<&'static [u8]> = Some(&[0x28, 0xcf, 0x06, 0x03, 0x00, 0x37]);
+
+    fn new() -> Self {
+        Self::new()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.update(data);
+    }
+
+    fn finalize(self) -> Self::Output {
+        self.finalize()
+    }
+}
+
+/// One round of the block cipher W on a state of eight rows: the S-box, the shift of column
+/// `j` down by `j` rows and the diffusion of each row, followed by the addition of `key`.
+fn round(state: &[u64; 8], key: &[u64; 8]) -> [u64; 8] {
+    let mut result = *key;
+    for (i, row) in result.iter_mut().enumerate() {
+        for j in 0..8 {
+            let byte = (state[(i + 8 - j) % 8] >> (56 - 8 * j)) as u8;
+            *row ^= TABLE[byte as usize].rotate_right(8 * j as u32);
+        }
+    }
+    result
+}
+
+/// Encrypts the chunk with the hash as key and adds both to the result, which is the
+/// Miyaguchi–Preneel construction.
+fn process_chunk(hash: &mut [u64; 8], chunk: &[u8; 64]) {
+    let mut message = [0_u64; 8];
+    for (row, bytes) in message.iter_mut().zip(chunk.chunks_exact(8)) {
+        *row = u64::from_be_bytes(bytes.try_into().unwrap());
+    }
+
+    let mut key = *hash;
+    let mut state = [0; 8];
+    for i in 0..8 {
+        state[i] = message[i] ^ key[i];
+    }
+
+    for constant in ROUND_CONSTANTS {
+        let mut round_key = [0; 8];
+        round_key[0] = constant;
+        key = round(&key, &round_key);
+        state = round(&state, &key);
+    }
+
+    for i in 0..8 {
+        hash[i] ^= state[i] ^ message[i];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sbox_matches_the_specification() {
+        assert_eq!(SBOX[..8], [0x18, 0x23, 0xc6, 0xe8, 0x87, 0xb8, 0x01, 0x4f]);
+        assert_eq!(SBOX[0xff], 0x86);
+    }
+}
@@ -0,0 +1,271 @@
+//! The Poly1305 one-time authenticator (RFC 8439, section 2.5). A key must never be used for more
+//! than one message, which is why it is normally derived from a stream cipher and a nonce.
+
+use crate::ct;
+use crate::zeroize::Zeroize;
+
+pub type Tag = [u8; 16];
+
+const MASK: u32 = 0x3ffffff;
+
+/// Computes the tag of a message in 26-bit limbs, so that all products fit in a `u64`.
+pub struct Poly1305 {
+    r: [u32; 5],
+    h: [u32; 5],
+    pad: [u32; 4],
+    buffer: [u8; 16],
+    buffer_len: usize,
+}
+
+impl Poly1305 {
+    pub fn new(key: &[u8; 32]) -> Self {
+        let le = |i: usize| u32::from_le_bytes(key[i..(i + 4)].try_into().unwrap());
+
+        // clamping of r is folded into the masks
+        let r = [
+            le(0) & 0x3ffffff,
+            (le(3) >> 2) & 0x3ffff03,
+            (le(6) >> 4) & 0x3ffc0ff,
+            (le(9) >> 6) & 0x3f03fff,
+            (le(12) >> 8) & 0x00fffff,
+        ];
+        let pad = [le(16), le(20), le(24), le(28)];
+
+        Self {
+            r,
+            h: [0; 5],
+            pad,
+            buffer: [0; 16],
+            buffer_len: 0,
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        let mut data = data;
+
+        if self.buffer_len > 0 {
+            let n = data.len().min(16 - self.buffer_len);
+            self.buffer[self.buffer_len..][..n].copy_from_slice(&data[..n]);
+            self.buffer_len += n;
+            data = &data[n..];
+
+            if self.buffer_len < 16 {
+                return;
+            }
+            let block = self.buffer;
+            self.process_block(&block, 1 << 24);
+            self.buffer_len = 0;
+        }
+
+        let blocks = data.chunks_exact(16);
+        let rest = blocks.remainder();
+        for block in blocks {
+            self.process_block(block.try_into().unwrap(), 1 << 24);
+        }
+
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffer_len = rest.len();
+    }
+
+    /// Pads the message with zeros up to a multiple of 16 bytes, as done by the AEAD construction.
+    pub fn pad_to_block(&mut self) {
+        if self.buffer_len > 0 {
+            self.update(&[0; 16][self.buffer_len..]);
+        }
+    }
+
+    #[rustfmt::skip]
+    pub fn finalize(mut self) -> Tag {
+        if self.buffer_len > 0 {
+            let mut block = [0; 16];
+            block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+            block[self.buffer_len] = 1;
+            self.process_block(&block, 0);
+        }
+
+        let mut h = self.h;
+
+        // full carry
+        let mut c;
+        c = h[1] >> 26; h[1] &= MASK; h[2] += c;
+        c = h[2] >> 26; h[2] &= MASK; h[3] += c;
+        c = h[3] >> 26; h[3] &= MASK; h[4] += c;
+        c = h[4] >> 26; h[4] &= MASK; h[0] += c * 5;
+        c = h[0] >> 26; h[0] &= MASK; h[1] += c;
+
+        // g = h - (2^130 - 5), selected in constant time if h >= 2^130 - 5
+        let mut g = [0_u32; 5];
+        g[0] = h[0].wrapping_add(5); c = g[0] >> 26; g[0] &= MASK;
+        g[1] = h[1].wrapping_add(c); c = g[1] >> 26; g[1] &= MASK;
+        g[2] = h[2].wrapping_add(c); c = g[2] >> 26; g[2] &= MASK;
+        g[3] = h[3].wrapping_add(c); c = g[3] >> 26; g[3] &= MASK;
+        g[4] = h[4].wrapping_add(c).wrapping_sub(1 << 26);
+
+        let use_g = (g[4] >> 31).wrapping_sub(1);
+        for i in 0..5 {
+            h[i] = (h[i] & !use_g) | (g[i] & use_g);
+        }
+
+        let words = [
+            h[0] | (h[1] << 26),
+            (h[1] >> 6) | (h[2] << 20),
+            (h[2] >> 12) | (h[3] << 14),
+            (h[3] >> 18) | (h[4] << 8),
+        ];
+
+        let mut tag = [0_u8; 16];
+        let mut carry = 0_u64;
+        for i in 0..4 {
+            let f = words[i] as u64 + self.pad[i] as u64 + carry;
+            tag[(4 * i)..][..4].copy_from_slice(&(f as u32).to_le_bytes());
+            carry = f >> 32;
+        }
+
+        h.zeroize();
+        g.zeroize();
+        tag
+    }
+
+    /// Compares the computed tag with `tag` in constant time.
+    pub fn verify(self, tag: &[u8]) -> bool {
+        let mut expected = self.finalize();
+        let result = ct::bytes_eq(&expected, tag);
+        expected.zeroize();
+        result
+    }
+
+    #[rustfmt::skip]
+    fn process_block(&mut self, block: &[u8; 16], high_bit: u32) {
+        let le = |i: usize| u32::from_le_bytes(block[i..(i + 4)].try_into().unwrap());
+
+        let [r0, r1, r2, r3, r4] = self.r.map(|v| v as u64);
+        let [s1, s2, s3, s4] = [r1 * 5, r2 * 5, r3 * 5, r4 * 5];
+
+        let h = &mut self.h;
+        h[0] += le(0) & MASK;
+        h[1] += (le(3) >> 2) & MASK;
+        h[2] += (le(6) >> 4) & MASK;
+        h[3] += (le(9) >> 6) & MASK;
+        h[4] += (le(12) >> 8) | high_bit;
+
+        let [h0, h1, h2, h3, h4] = h.map(|v| v as u64);
+
+        let d0 = h0 * r0 + h1 * s4 + h2 * s3 + h3 * s2 + h4 * s1;
+        let mut d1 = h0 * r1 + h1 * r0 + h2 * s4 + h3 * s3 + h4 * s2;
+        let mut d2 = h0 * r2 + h1 * r1 + h2 * r0 + h3 * s4 + h4 * s3;
+        let mut d3 = h0 * r3 + h1 * r2 + h2 * r1 + h3 * r0 + h4 * s4;
+        let mut d4 = h0 * r4 + h1 * r3 + h2 * r2 + h3 * r1 + h4 * r0;
+
+        // partial reduction modulo 2^130 - 5
+        let mut c;
+        c = d0 >> 26; h[0] = d0 as u32 & MASK; d1 += c;
+        c = d1 >> 26; h[1] = d1 as u32 & MASK; d2 += c;
+        c = d2 >> 26; h[2] = d2 as u32 & MASK; d3 += c;
+        c = d3 >> 26; h[3] = d3 as u32 & MASK; d4 += c;
+        c = d4 >> 26; h[4] = d4 as u32 & MASK;
+        h[0] += c as u32 * 5;
+        h[1] += h[0] >> 26;
+        h[0] &= MASK;
+    }
+}
+
+pub fn authenticate(key: &[u8; 32], message: &[u8]) -> Tag {
+    let mut mac = Poly1305::new(key);
+    mac.update(message);
+    mac.finalize()
+}
+
+impl Drop for Poly1305 {
+    fn drop(&mut self) {
+        self.r.zeroize();
+        self.h.zeroize();
+        self.pad.zeroize();
+        self.buffer.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Poly1305 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Poly1305").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::ManuallyDrop;
+
+    use super::*;
+
+    fn rfc_key() -> [u8; 32] {
+        hex::decode("85d6be7857556d337f4452fe42d506a80103808afb0db2fd4abff6af4149f51b")
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn rfc8439_example() {
+        // RFC 8439, section 2.5.2
+        let tag = authenticate(&rfc_key(), b"Cryptographic Forum Research Group");
+        assert_eq!(hex::encode(tag), "a8061dc1305136c6c22b8baf0c0127a9");
+    }
+
+    #[test]
+    fn update_in_uneven_parts() {
+        let message = (0..200).collect::<Vec<u8>>();
+        let expected = authenticate(&rfc_key(), &message);
+
+        for part_len in [1, 7, 15, 16, 17, 33] {
+            let mut mac = Poly1305::new(&rfc_key());
+            for part in message.chunks(part_len) {
+                mac.update(part);
+            }
+            assert_eq!(mac.finalize(), expected, "part_len = {part_len}");
+        }
+    }
+
+    #[test]
+    fn padding_only_affects_partial_blocks() {
+        let mut padded = Poly1305::new(&rfc_key());
+        padded.update(&[1, 2, 3]);
+        padded.pad_to_block();
+        padded.pad_to_block();
+
+        let mut block = [0; 16];
+        block[..3].copy_from_slice(&[1, 2, 3]);
+        assert_eq!(padded.finalize(), authenticate(&rfc_key(), &block));
+    }
+
+    #[test]
+    fn verify_compares_tags() {
+        let message = b"Cryptographic Forum Research Group";
+        let tag = hex::decode("a8061dc1305136c6c22b8baf0c0127a9").unwrap();
+
+        let mut mac = Poly1305::new(&rfc_key());
+        mac.update(message);
+        assert!(mac.verify(&tag));
+
+        let mut mac = Poly1305::new(&rfc_key());
+        mac.update(message);
+        assert!(!mac.verify(&tag[..15]));
+
+        let mut mac = Poly1305::new(&rfc_key());
+        mac.update(&message[1..]);
+        assert!(!mac.verify(&tag));
+    }
+
+    #[test]
+    fn state_is_zeroized_on_drop() {
+        let mut mac = ManuallyDrop::new(Poly1305::new(&rfc_key()));
+        mac.update(b"some data");
+
+        unsafe { ManuallyDrop::drop(&mut mac) };
+        assert_eq!((mac.r, mac.h, mac.pad), ([0; 5], [0; 5], [0; 4]));
+        assert_eq!(mac.buffer, [0; 16]);
+    }
+
+    #[test]
+    fn debug_output_is_redacted() {
+        assert_eq!(format!("{:?}", Poly1305::new(&rfc_key())), "Poly1305 { .. }");
+    }
+}
@@ -1,3 +1,6 @@
+use crate::key::{check_length, decode_base64, decode_hex, KeyError};
+use crate::zeroize::Zeroize;
+
 const PITABLE: [u8; 256] = {
     let mut arr = [0_u8; 256];
 
@@ -40,9 +43,28 @@ pub struct RC2 {
 
 impl RC2 {
     pub fn from_key(key: &[u8], num_bits: u16) -> Self {
-        Self {
-            key: RC2::expand_key(key, num_bits),
+        Self::try_from_key(key, num_bits).expect("invalid RC2 key")
+    }
+
+    pub fn try_from_key(key: &[u8], num_bits: u16) -> Result<Self, KeyError> {
+        check_length(key, 1..=128, "1 to 128 bytes")?;
+        if !(1..=1024).contains(&num_bits) {
+            return Err(KeyError::InvalidParameter(
+                "effective key length must be between 1 and 1024 bits",
+            ));
         }
+
+        Ok(Self {
+            key: RC2::expand_key(key, num_bits),
+        })
+    }
+
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
     }
 
     fn expand_key(key: &[u8], num_bits: u16) -> [u16; 64] {
@@ -67,6 +89,7 @@ impl RC2 {
         for i in 0..64 {
             k[i] = u16::from_le_bytes([l[i * 2], l[i * 2 + 1]]);
         }
+        l.zeroize();
         k
     }
 
@@ -126,6 +149,28 @@ impl RC2 {
     }
 }
 
+impl Drop for RC2 {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+impl std::fmt::Debug for RC2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RC2").finish_non_exhaustive()
+    }
+}
+
+/// Uses the full key length as the effective key length.
+impl TryFrom<&[u8]> for RC2 {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        check_length(key, 1..=128, "1 to 128 bytes")?;
+        Self::try_from_key(key, 8 * key.len() as u16)
+    }
+}
+
 impl crate::BlockCipher<8> for RC2 {
     fn encrypt(&self, plaintext: crate::Block<8>) -> crate::Block<8> {
         let mut data = [0_u16; 4];
@@ -156,7 +201,7 @@ impl crate::BlockCipher<8> for RC2 {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Block, BlockCipher};
+    use crate::{key::KeyError, Block, BlockCipher};
 
     use super::RC2 as rc2;
 
@@ -233,4 +278,63 @@ mod tests {
             0x5b78d3a43dfff1f1_u64.to_be_bytes(),
         );
     }
+
+    #[test]
+    fn rc2_try_from_uses_full_key_length() {
+        let key = 0x88bca90e90875a7f0f79c384627bafb2_u128.to_be_bytes();
+        check(
+            rc2::try_from(&key[..]).unwrap(),
+            0x0000000000000000_u64.to_be_bytes(),
+            0x2269552ab0f85ca6_u64.to_be_bytes(),
+        );
+    }
+
+    #[test]
+    fn rc2_rejects_invalid_keys() {
+        assert!(matches!(
+            rc2::try_from(&[][..]),
+            Err(KeyError::InvalidLength { actual: 0, .. })
+        ));
+        assert!(matches!(
+            rc2::try_from(&[0; 129][..]),
+            Err(KeyError::InvalidLength { actual: 129, .. })
+        ));
+        assert!(matches!(
+            rc2::try_from_key(&[0; 8], 0),
+            Err(KeyError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            rc2::try_from_key(&[0; 8], 1025),
+            Err(KeyError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn rc2_from_hex_and_base64() {
+        check(
+            rc2::from_hex("ffffffffffffffff").unwrap(),
+            0xffffffffffffffff_u64.to_be_bytes(),
+            0x278b27e42e2f0d49_u64.to_be_bytes(),
+        );
+        check(
+            rc2::from_base64("//////////8=").unwrap(),
+            0xffffffffffffffff_u64.to_be_bytes(),
+            0x278b27e42e2f0d49_u64.to_be_bytes(),
+        );
+        assert!(matches!(rc2::from_hex("fff"), Err(KeyError::InvalidHex(_))));
+    }
+
+    #[test]
+    fn rc2_key_is_zeroized_on_drop() {
+        let mut cipher = std::mem::ManuallyDrop::new(rc2::from_key(&[0x88], 64));
+        assert_ne!(cipher.key, [0; 64]);
+
+        unsafe { std::mem::ManuallyDrop::drop(&mut cipher) };
+        assert_eq!(cipher.key, [0; 64]);
+    }
+
+    #[test]
+    fn rc2_debug_output_is_redacted() {
+        assert_eq!(format!("{:?}", rc2::from_key(&[0x88], 64)), "RC2 { .. }");
+    }
 }
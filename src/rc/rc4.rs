@@ -1,3 +1,5 @@
+use crate::key::{check_length, decode_base64, decode_hex, KeyError};
+use crate::zeroize::Zeroize;
 use crate::StreamCipher;
 
 pub struct KeyStream {
@@ -7,22 +9,35 @@ pub struct KeyStream {
 }
 
 impl KeyStream {
+    /// Panics on an empty key, which `try_from` returns as an error instead.
     pub fn new(key_data: &[u8]) -> Self {
-        let mut state = [0_u8; 256];
-        for i in 0..=255_u8 {
-            state[i as usize] = i;
-        }
+        Self::try_from(key_data).expect("invalid RC4 key")
+    }
 
-        let mut j: u8 = 0;
+    /// RC4-drop[n], which discards the first `n` bytes of the keystream since they are known to
+    /// be biased. Common choices are 768, 1536 (RFC 4345) and 3072.
+    pub fn with_drop(key_data: &[u8], n: usize) -> Self {
+        Self::try_with_drop(key_data, n).expect("invalid RC4 key")
+    }
 
-        for i in 0..=255 {
-            j = key_data[i % key_data.len()]
-                .wrapping_add(state[i])
-                .wrapping_add(j);
-            state.swap(i, j as usize);
+    pub fn try_with_drop(key_data: &[u8], n: usize) -> Result<Self, KeyError> {
+        let mut key_stream = Self {
+            state: schedule_key(key_data)?,
+            x: 0,
+            y: 0,
+        };
+        for _ in 0..n {
+            key_stream.next_key();
         }
+        Ok(key_stream)
+    }
 
-        Self { state, x: 0, y: 0 }
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
     }
 
     fn next_key(&mut self) -> u8 {
@@ -46,6 +61,134 @@ impl KeyStream {
     }
 }
 
+impl Drop for KeyStream {
+    fn drop(&mut self) {
+        self.state.zeroize();
+        self.x.zeroize();
+        self.y.zeroize();
+    }
+}
+
+impl std::fmt::Debug for KeyStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyStream").finish_non_exhaustive()
+    }
+}
+
+impl TryFrom<&[u8]> for KeyStream {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        Self::try_with_drop(key, 0)
+    }
+}
+
+/// The RC4 key scheduling algorithm.
+fn schedule_key(key_data: &[u8]) -> Result<[u8; 256], KeyError> {
+    check_length(key_data, 1.., "at least 1 byte")?;
+
+    let mut state = [0_u8; 256];
+    for i in 0..=255_u8 {
+        state[i as usize] = i;
+    }
+
+    let mut j: u8 = 0;
+
+    for i in 0..=255 {
+        j = key_data[i % key_data.len()]
+            .wrapping_add(state[i])
+            .wrapping_add(j);
+        state.swap(i, j as usize);
+    }
+
+    Ok(state)
+}
+
+/// RC4A by Paul and Preneel, which runs two RC4 states that select each other's output bytes.
+/// The way the second key is derived from the first differs between protocols, so both keys are
+/// given explicitly.
+pub struct RC4A {
+    s1: [u8; 256],
+    s2: [u8; 256],
+    i: u8,
+    j1: u8,
+    j2: u8,
+    second: bool, // whether the next byte comes from the second state
+}
+
+impl RC4A {
+    pub fn new(key1: &[u8], key2: &[u8]) -> Self {
+        Self::try_new(key1, key2).expect("invalid RC4A key")
+    }
+
+    pub fn try_new(key1: &[u8], key2: &[u8]) -> Result<Self, KeyError> {
+        Ok(Self {
+            s1: schedule_key(key1)?,
+            s2: schedule_key(key2)?,
+            i: 0,
+            j1: 0,
+            j2: 0,
+            second: false,
+        })
+    }
+
+    /// Alternates between the two states, each stepped like RC4 but indexing the other one.
+    fn next_key(&mut self) -> u8 {
+        if !self.second {
+            self.i = self.i.wrapping_add(1);
+        }
+        let i = self.i as usize;
+
+        let (state, other, j) = if self.second {
+            (&mut self.s2, &self.s1, &mut self.j2)
+        } else {
+            (&mut self.s1, &self.s2, &mut self.j1)
+        };
+        *j = state[i].wrapping_add(*j);
+        state.swap(i, *j as usize);
+
+        let index = state[i].wrapping_add(state[*j as usize]);
+        let key = other[index as usize];
+
+        self.second = !self.second;
+        key
+    }
+
+    pub fn encrypt(self, plaintext: &[u8]) -> Vec<u8> {
+        StreamCipher::new(self)
+            .encrypt(plaintext.iter().cloned())
+            .collect::<Vec<_>>()
+    }
+
+    pub fn decrypt(self, ciphertext: &[u8]) -> Vec<u8> {
+        self.encrypt(ciphertext)
+    }
+}
+
+impl Drop for RC4A {
+    fn drop(&mut self) {
+        self.s1.zeroize();
+        self.s2.zeroize();
+        self.i.zeroize();
+        self.j1.zeroize();
+        self.j2.zeroize();
+    }
+}
+
+impl std::fmt::Debug for RC4A {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RC4A").finish_non_exhaustive()
+    }
+}
+
+impl Iterator for RC4A {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_key())
+    }
+}
+
 impl Iterator for KeyStream {
     type Item = u8;
 
@@ -89,4 +232,94 @@ mod tests {
         output.extend(cipher.encrypt(*b"text"));
         assert_eq!(output, 0xBBF316E8D940AF0AD3_u128.to_be_bytes()[7..]);
     }
+
+    #[test]
+    fn rc4_rejects_invalid_keys() {
+        assert!(matches!(
+            KeyStream::try_from(&[][..]),
+            Err(KeyError::InvalidLength { actual: 0, .. })
+        ));
+        assert!(matches!(KeyStream::from_hex(""), Err(KeyError::InvalidLength { .. })));
+    }
+
+    #[test]
+    fn rc4_accepts_keys_longer_than_the_state() {
+        // only the first 256 bytes of the key are used
+        let mut key = [0x42; 300];
+        let expected = KeyStream::new(&key[..256]).take(16).collect::<Vec<_>>();
+        assert_eq!(KeyStream::new(&key).take(16).collect::<Vec<_>>(), expected);
+        key[256] ^= 1;
+        assert_eq!(KeyStream::try_from(&key[..]).unwrap().take(16).collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid RC4 key")]
+    fn rc4_new_panics_on_empty_key() {
+        KeyStream::new(b"");
+    }
+
+    #[test]
+    fn rc4_drop_discards_initial_key_stream() {
+        let expected = KeyStream::new(b"Key").skip(768).take(10).collect::<Vec<_>>();
+        assert_eq!(hex::encode(&expected), "d51c266be56d45518994");
+
+        let key_stream = KeyStream::with_drop(b"Key", 768).take(10).collect::<Vec<_>>();
+        assert_eq!(key_stream, expected);
+
+        assert!(matches!(
+            KeyStream::try_with_drop(b"", 768),
+            Err(KeyError::InvalidLength { actual: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn rc4a_key_stream() {
+        // there are no published vectors, this one was computed with a separate implementation
+        // written from the pseudocode of the paper
+        let key_stream = RC4A::new(b"Key", b"Secret").take(16).collect::<Vec<_>>();
+        assert_eq!(hex::encode(key_stream), "3afa242b299270c213fe5d09110041a6");
+    }
+
+    #[test]
+    fn rc4a_round_trip_and_key_validation() {
+        let ciphertext = RC4A::new(b"Key", b"Secret").encrypt(b"Plaintext");
+        assert_eq!(RC4A::new(b"Key", b"Secret").decrypt(&ciphertext), b"Plaintext");
+
+        assert!(RC4A::try_new(b"Key", b"").is_err());
+        assert!(RC4A::try_new(b"", b"Key").is_err());
+    }
+
+    #[test]
+    fn rc4_from_hex_and_base64() {
+        let key_stream = KeyStream::from_hex("4b6579").unwrap().take(10).collect::<Vec<_>>();
+        assert_eq!(key_stream, hex::decode("EB9F7781B734CA72A719").unwrap());
+
+        let key_stream = KeyStream::from_base64("S2V5").unwrap().take(10).collect::<Vec<_>>();
+        assert_eq!(key_stream, hex::decode("EB9F7781B734CA72A719").unwrap());
+    }
+
+    #[test]
+    fn rc4_state_is_zeroized_on_drop() {
+        let mut key_stream = std::mem::ManuallyDrop::new(KeyStream::new(b"Key"));
+        key_stream.next();
+
+        unsafe { std::mem::ManuallyDrop::drop(&mut key_stream) };
+        assert_eq!(key_stream.state, [0; 256]);
+        assert_eq!((key_stream.x, key_stream.y), (0, 0));
+    }
+
+    #[test]
+    fn rc4a_state_is_zeroized_on_drop() {
+        let mut key_stream = std::mem::ManuallyDrop::new(RC4A::new(b"Key", b"Secret"));
+        key_stream.next();
+
+        unsafe { std::mem::ManuallyDrop::drop(&mut key_stream) };
+        assert_eq!((key_stream.s1, key_stream.s2), ([0; 256], [0; 256]));
+    }
+
+    #[test]
+    fn rc4_debug_output_is_redacted() {
+        assert_eq!(format!("{:?}", KeyStream::new(b"Key")), "KeyStream { .. }");
+        assert_eq!(format!("{:?}", RC4A::new(b"Key", b"Key")), "RC4A { .. }");
+    }
 }
@@ -1,111 +1,269 @@
-use crate::BlockCipher;
+use crate::key::{check_length, decode_base64, decode_hex, KeyError};
+use crate::zeroize::Zeroize;
+use crate::{Block, BlockCipher};
 
-const P32: u32 = 0xb7e15163;
-const Q32: u32 = 0x9e3779b9;
+/// The fractional parts of e and the golden ratio to 128 bits, from which the magic constants
+/// P_w and Q_w of every word size are taken.
+const E_FRACTION: u128 = 0xb7e151628aed2a6abf7158809cf4f3c7;
+const PHI_FRACTION: u128 = 0x9e3779b97f4a7c15f39cc0605cedc834;
 
-pub struct RC5 {
-    s: Vec<u32>, // expanded key
+/// Odd((f - 1) * 2^w) for the fraction f, where Odd rounds to the nearest odd integer.
+const fn magic_constant(fraction: u128, bits: u32) -> u128 {
+    (fraction >> (128 - bits)) | 1
+}
+
+/// A word size supported by RC5. A block consists of two words.
+pub trait Word: Copy + Zeroize {
+    const BYTES: usize;
+    const P: Self;
+    const Q: Self;
+    /// The number of rounds used by `TryFrom`.
+    const DEFAULT_ROUNDS: u8;
+
+    fn from_u8(value: u8) -> Self;
+    fn from_le_slice(bytes: &[u8]) -> Self;
+    fn write_le_slice(self, bytes: &mut [u8]);
+    fn wrapping_add(self, other: Self) -> Self;
+    fn wrapping_sub(self, other: Self) -> Self;
+    fn xor(self, other: Self) -> Self;
+    /// Rotates by the lowest lg(w) bits of `amount`.
+    fn rotate_left(self, amount: Self) -> Self;
+    fn rotate_right(self, amount: Self) -> Self;
+}
+
+macro_rules! impl_word {
+    ($t:ty, $default_rounds:expr) => {
+        impl Word for $t {
+            const BYTES: usize = <$t>::BITS as usize / 8;
+            const P: Self = magic_constant(E_FRACTION, <$t>::BITS) as $t;
+            const Q: Self = magic_constant(PHI_FRACTION, <$t>::BITS) as $t;
+            const DEFAULT_ROUNDS: u8 = $default_rounds;
+
+            fn from_u8(value: u8) -> Self {
+                value as $t
+            }
+
+            fn from_le_slice(bytes: &[u8]) -> Self {
+                <$t>::from_le_bytes(bytes.try_into().unwrap())
+            }
+
+            fn write_le_slice(self, bytes: &mut [u8]) {
+                bytes.copy_from_slice(&self.to_le_bytes());
+            }
+
+            fn wrapping_add(self, other: Self) -> Self {
+                <$t>::wrapping_add(self, other)
+            }
+
+            fn wrapping_sub(self, other: Self) -> Self {
+                <$t>::wrapping_sub(self, other)
+            }
+
+            fn xor(self, other: Self) -> Self {
+                self ^ other
+            }
+
+            fn rotate_left(self, amount: Self) -> Self {
+                <$t>::rotate_left(self, amount as u32 % <$t>::BITS)
+            }
+
+            fn rotate_right(self, amount: Self) -> Self {
+                <$t>::rotate_right(self, amount as u32 % <$t>::BITS)
+            }
+        }
+    };
+}
+
+impl_word!(u16, 12);
+impl_word!(u32, 12);
+impl_word!(u64, 16);
+
+/// RC5-w/r/b with w = 16, 32 or 64 given by the word type. The block size is 2w bits, so RC5-16
+/// is a `BlockCipher<4>`, RC5-32 a `BlockCipher<8>` and RC5-64 a `BlockCipher<16>`.
+pub struct RC5<W: Word = u32> {
+    s: Vec<W>, // expanded key
     r: u8, // rounds
 }
 
+/// The constructors of RC5-32, by far the most common variant. Other word sizes are created
+/// with `with_word_size` or `TryFrom`, for example `RC5::<u64>::with_word_size(key, 16)`.
 impl RC5 {
+    /// The number of rounds used by `TryFrom`, `from_hex` and `from_base64`.
+    pub const DEFAULT_ROUNDS: u8 = <u32 as Word>::DEFAULT_ROUNDS;
+
     pub fn new(key: &[u8], rounds: u8) -> Self {
-        assert!(key.len() < 256);
+        Self::try_new(key, rounds).expect("invalid RC5 key")
+    }
 
-        Self { s: RC5::expand_key(key, rounds), r: rounds }
+    pub fn try_new(key: &[u8], rounds: u8) -> Result<Self, KeyError> {
+        Self::try_with_word_size(key, rounds)
     }
 
-    fn expand_key(key: &[u8], r: u8) -> Vec<u32> {
-        let c = key.len().div_ceil(4);
-        let mut l: Vec<u32> = Vec::with_capacity(c);
-        for chunk in key.chunks(4) {
-            let mut word = [0_u8; 4];
-            word[..chunk.len()].copy_from_slice(chunk);
-            l.push(u32::from_le_bytes(word));
-        }
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
 
-        let t = 2 * (r as usize + 1);
-        let mut s: Vec<u32> = Vec::with_capacity(t);
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+}
 
-        s.push(P32);
-        for i in 1..t {
-            s.push(s[i-1].wrapping_add(Q32));
-        }
+impl<W: Word> RC5<W> {
+    pub fn with_word_size(key: &[u8], rounds: u8) -> Self {
+        Self::try_with_word_size(key, rounds).expect("invalid RC5 key")
+    }
 
-        let mut i = 0;
-        let mut j = 0;
-        let mut a: u32 = 0;
-        let mut b: u32 = 0;
-
-        for _ in 0..(3 * t.max(c)) {
-            let a_b = a.wrapping_add(b);
-            a = s[i].wrapping_add(a_b).rotate_left(3);
-            s[i] = a;
-            
-            let a_b = a.wrapping_add(b);
-            b = l[j].wrapping_add(a_b).rotate_left(a_b);
-            l[j] = b;
-
-            i = i.wrapping_add(1) % t;
-            j = j.wrapping_add(1) % c;
-        }
+    pub fn try_with_word_size(key: &[u8], rounds: u8) -> Result<Self, KeyError> {
+        check_length(key, 0..=255, "at most 255 bytes")?;
+
+        Ok(Self { s: Self::expand_key(key, rounds), r: rounds })
+    }
 
-        s
+    fn expand_key(key: &[u8], r: u8) -> Vec<W> {
+        expand_key(key, 2 * (r as usize + 1))
     }
 
-    fn encrypt_32(&self, plaintext: [u32; 2]) -> [u32; 2] {
+    fn encrypt_words(&self, plaintext: [W; 2]) -> [W; 2] {
         let [mut a, mut b] = plaintext;
         
         a = a.wrapping_add(self.s[0]);
         b = b.wrapping_add(self.s[1]);
         for i in 1..=self.r as usize {
-            a = (a ^ b).rotate_left(b).wrapping_add(self.s[2 * i]);
-            b = (b ^ a).rotate_left(a).wrapping_add(self.s[2 * i + 1]);
+            a = a.xor(b).rotate_left(b).wrapping_add(self.s[2 * i]);
+            b = b.xor(a).rotate_left(a).wrapping_add(self.s[2 * i + 1]);
         }
 
         [a, b]
     }
     
-    fn decrypt_32(&self, ciphertext: [u32; 2]) -> [u32; 2] {
+    fn decrypt_words(&self, ciphertext: [W; 2]) -> [W; 2] {
         let [mut a, mut b] = ciphertext;
 
         for i in (1..=self.r as usize).rev() {
-            b = b.wrapping_sub(self.s[2 * i + 1]).rotate_right(a) ^ a;
-            a = a.wrapping_sub(self.s[2 * i]).rotate_right(b) ^ b;
+            b = b.wrapping_sub(self.s[2 * i + 1]).rotate_right(a).xor(a);
+            a = a.wrapping_sub(self.s[2 * i]).rotate_right(b).xor(b);
         }
         b = b.wrapping_sub(self.s[1]);
         a = a.wrapping_sub(self.s[0]);
 
         [a, b]
     }
-}
-
-impl BlockCipher<8> for RC5 {
-    fn encrypt(&self, plaintext: crate::Block<8>) -> crate::Block<8> {
-        let a = u32::from_le_bytes(<[u8; 4]>::try_from(&plaintext[..4]).unwrap());
-        let b = u32::from_le_bytes(<[u8; 4]>::try_from(&plaintext[4..]).unwrap());
 
-        let [a, b] = self.encrypt_32([a, b]);
+    fn encrypt_block<const N: usize>(&self, plaintext: Block<N>) -> Block<N> {
+        let (a, b) = plaintext.split_at(W::BYTES);
+        let [a, b] = self.encrypt_words([W::from_le_slice(a), W::from_le_slice(b)]);
  
-        let mut res = [0_u8; 8];
-        res[..4].copy_from_slice(&a.to_le_bytes());
-        res[4..].copy_from_slice(&b.to_le_bytes());
+        let mut res = [0_u8; N];
+        a.write_le_slice(&mut res[..W::BYTES]);
+        b.write_le_slice(&mut res[W::BYTES..]);
         res
     }
 
-    fn decrypt(&self, ciphertext: crate::Block<8>) -> crate::Block<8> {
-        let a = u32::from_le_bytes(<[u8; 4]>::try_from(&ciphertext[..4]).unwrap());
-        let b = u32::from_le_bytes(<[u8; 4]>::try_from(&ciphertext[4..]).unwrap());
-
-        let [a, b] = self.decrypt_32([a, b]);
+    fn decrypt_block<const N: usize>(&self, ciphertext: Block<N>) -> Block<N> {
+        let (a, b) = ciphertext.split_at(W::BYTES);
+        let [a, b] = self.decrypt_words([W::from_le_slice(a), W::from_le_slice(b)]);
  
-        let mut res = [0_u8; 8];
-        res[..4].copy_from_slice(&a.to_le_bytes());
-        res[4..].copy_from_slice(&b.to_le_bytes());
+        let mut res = [0_u8; N];
+        a.write_le_slice(&mut res[..W::BYTES]);
+        b.write_le_slice(&mut res[W::BYTES..]);
         res
     }
 }
 
+/// Mixes the secret key into a table of `t` words initialized from the magic constants. This key
+/// schedule is shared by RC5 and RC6, which only differ in the size of the table.
+pub(super) fn expand_key<W: Word>(key: &[u8], t: usize) -> Vec<W> {
+    // an empty key is expanded as a single zero word
+    let c = key.len().div_ceil(W::BYTES).max(1);
+    let mut l: Vec<W> = vec![W::from_u8(0); c];
+    for (i, chunk) in key.chunks(W::BYTES).enumerate() {
+        let mut word = [0_u8; 8];
+        word[..chunk.len()].copy_from_slice(chunk);
+        l[i] = W::from_le_slice(&word[..W::BYTES]);
+    }
+
+    let mut s: Vec<W> = Vec::with_capacity(t);
+
+    s.push(W::P);
+    for i in 1..t {
+        s.push(s[i-1].wrapping_add(W::Q));
+    }
+
+    let mut i = 0;
+    let mut j = 0;
+    let mut a = W::from_u8(0);
+    let mut b = W::from_u8(0);
+
+    for _ in 0..(3 * t.max(c)) {
+        let a_b = a.wrapping_add(b);
+        a = s[i].wrapping_add(a_b).rotate_left(W::from_u8(3));
+        s[i] = a;
+        
+        let a_b = a.wrapping_add(b);
+        b = l[j].wrapping_add(a_b).rotate_left(a_b);
+        l[j] = b;
+
+        i = i.wrapping_add(1) % t;
+        j = j.wrapping_add(1) % c;
+    }
+
+    l.zeroize();
+    s
+}
+
+impl<W: Word> Drop for RC5<W> {
+    fn drop(&mut self) {
+        self.s.zeroize();
+    }
+}
+
+impl<W: Word> std::fmt::Debug for RC5<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RC5")
+            .field("word_size", &(8 * W::BYTES))
+            .field("rounds", &self.r)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<W: Word> TryFrom<&[u8]> for RC5<W> {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        Self::try_with_word_size(key, W::DEFAULT_ROUNDS)
+    }
+}
+
+impl BlockCipher<4> for RC5<u16> {
+    fn encrypt(&self, plaintext: Block<4>) -> Block<4> {
+        self.encrypt_block(plaintext)
+    }
+
+    fn decrypt(&self, ciphertext: Block<4>) -> Block<4> {
+        self.decrypt_block(ciphertext)
+    }
+}
+
+impl BlockCipher<8> for RC5<u32> {
+    fn encrypt(&self, plaintext: Block<8>) -> Block<8> {
+        self.encrypt_block(plaintext)
+    }
+
+    fn decrypt(&self, ciphertext: Block<8>) -> Block<8> {
+        self.decrypt_block(ciphertext)
+    }
+}
+
+impl BlockCipher<16> for RC5<u64> {
+    fn encrypt(&self, plaintext: Block<16>) -> Block<16> {
+        self.encrypt_block(plaintext)
+    }
+
+    fn decrypt(&self, ciphertext: Block<16>) -> Block<16> {
+        self.decrypt_block(ciphertext)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -114,32 +272,88 @@ mod tests {
     fn rc5_examples() {
         let key = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
         assert_eq!(
-            RC5::new(&key, 12).encrypt_32([0x00000000, 0x00000000]),
+            RC5::new(&key, 12).encrypt_words([0x00000000, 0x00000000]),
             [0xEEDBA521, 0x6D8F4B15]
         );
         
         let key = [0x91, 0x5F, 0x46, 0x19, 0xBE, 0x41, 0xB2, 0x51, 0x63, 0x55, 0xA5, 0x01, 0x10, 0xA9, 0xCE, 0x91];
         assert_eq!(
-            RC5::new(&key, 12).encrypt_32([0xEEDBA521, 0x6D8F4B15]),
+            RC5::new(&key, 12).encrypt_words([0xEEDBA521, 0x6D8F4B15]),
             [0xAC13C0F7, 0x52892B5B]
         );
 
         let key = [0x78, 0x33, 0x48, 0xE7, 0x5A, 0xEB, 0x0F, 0x2F, 0xD7, 0xB1, 0x69, 0xBB, 0x8D, 0xC1, 0x67, 0x87];
         assert_eq!(
-            RC5::new(&key, 12).encrypt_32([0xAC13C0F7, 0x52892B5B]),
+            RC5::new(&key, 12).encrypt_words([0xAC13C0F7, 0x52892B5B]),
             [0xB7B3422F, 0x92FC6903]
         );
 
         let key = [0xDC, 0x49, 0xDB, 0x13, 0x75, 0xA5, 0x58, 0x4F, 0x64, 0x85, 0xB4, 0x13, 0xB5, 0xF1, 0x2B, 0xAF];
         assert_eq!(
-            RC5::new(&key, 12).encrypt_32([0xB7B3422F, 0x92FC6903]),
+            RC5::new(&key, 12).encrypt_words([0xB7B3422F, 0x92FC6903]),
             [0xB278C165, 0xCC97D184]
         );
 
         let key = [0x52, 0x69, 0xF1, 0x49, 0xD4, 0x1B, 0xA0, 0x15, 0x24, 0x97, 0x57, 0x4D, 0x7F, 0x15, 0x31, 0x25];
         assert_eq!(
-            RC5::new(&key, 12).encrypt_32([0xB278C165, 0xCC97D184]),
+            RC5::new(&key, 12).encrypt_words([0xB278C165, 0xCC97D184]),
             [0x15E444EB, 0x249831DA]
         );
     }
+
+    #[test]
+    fn rc5_magic_constants() {
+        assert_eq!((u16::P, u16::Q), (0xb7e1, 0x9e37));
+        assert_eq!((u32::P, u32::Q), (0xb7e15163, 0x9e3779b9));
+        assert_eq!((u64::P, u64::Q), (0xb7e151628aed2a6b, 0x9e3779b97f4a7c15));
+    }
+
+    #[test]
+    fn rc5_other_word_sizes_round_trip() {
+        let cipher = RC5::<u16>::try_from(&b"key"[..]).unwrap();
+        let ciphertext = cipher.encrypt([1, 2, 3, 4]);
+        assert_ne!(ciphertext, [1, 2, 3, 4]);
+        assert_eq!(cipher.decrypt(ciphertext), [1, 2, 3, 4]);
+
+        let cipher = RC5::<u64>::with_word_size(&[], 16);
+        let plaintext = *b"sixteen byte msg";
+        assert_eq!(cipher.decrypt(cipher.encrypt(plaintext)), plaintext);
+    }
+
+    #[test]
+    fn rc5_rejects_too_long_keys() {
+        assert!(matches!(
+            RC5::try_new(&[0; 256], 12),
+            Err(KeyError::InvalidLength { actual: 256, .. })
+        ));
+    }
+
+    #[test]
+    fn rc5_accepts_empty_key() {
+        let cipher = RC5::try_from(&[][..]).unwrap();
+        let ciphertext = cipher.encrypt([1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(cipher.decrypt(ciphertext), [1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn rc5_from_hex_and_base64() {
+        let key = "915F4619BE41B2516355A50110A9CE91";
+        assert_eq!(
+            RC5::from_hex(key).unwrap().encrypt_words([0xEEDBA521, 0x6D8F4B15]),
+            [0xAC13C0F7, 0x52892B5B]
+        );
+        assert_eq!(
+            RC5::from_base64("kV9GGb5BslFjVaUBEKnOkQ==").unwrap().encrypt_words([0xEEDBA521, 0x6D8F4B15]),
+            [0xAC13C0F7, 0x52892B5B]
+        );
+    }
+
+    #[test]
+    fn rc5_debug_output_is_redacted() {
+        assert_eq!(format!("{:?}", RC5::new(&[1, 2, 3], 12)), "RC5 { word_size: 32, rounds: 12, .. }");
+        assert_eq!(
+            format!("{:?}", RC5::<u64>::with_word_size(&[1, 2, 3], 16)),
+            "RC5 { word_size: 64, rounds: 16, .. }"
+        );
+    }
 }
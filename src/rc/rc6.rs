@@ -0,0 +1,160 @@
+use crate::key::{check_length, decode_base64, decode_hex, KeyError};
+use crate::zeroize::Zeroize;
+use crate::BlockCipher;
+
+use super::rc5;
+
+pub struct RC6 {
+    s: Vec<u32>, // expanded key
+    r: u8, // rounds
+}
+
+impl RC6 {
+    /// The number of rounds used by `TryFrom`, `from_hex` and `from_base64`.
+    pub const DEFAULT_ROUNDS: u8 = 20;
+
+    pub fn new(key: &[u8], rounds: u8) -> Self {
+        Self::try_new(key, rounds).expect("invalid RC6 key")
+    }
+
+    pub fn try_new(key: &[u8], rounds: u8) -> Result<Self, KeyError> {
+        check_length(key, 0..=255, "at most 255 bytes")?;
+
+        Ok(Self { s: RC6::expand_key(key, rounds), r: rounds })
+    }
+
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+
+    /// Same as the RC5 key schedule, but with two more words for the final whitening.
+    fn expand_key(key: &[u8], r: u8) -> Vec<u32> {
+        rc5::expand_key(key, 2 * (r as usize + 2))
+    }
+
+    fn encrypt_32(&self, plaintext: [u32; 4]) -> [u32; 4] {
+        let [mut a, mut b, mut c, mut d] = plaintext;
+        let r = self.r as usize;
+
+        b = b.wrapping_add(self.s[0]);
+        d = d.wrapping_add(self.s[1]);
+        for i in 1..=r {
+            let t = b.wrapping_mul(b.wrapping_mul(2).wrapping_add(1)).rotate_left(5);
+            let u = d.wrapping_mul(d.wrapping_mul(2).wrapping_add(1)).rotate_left(5);
+            a = (a ^ t).rotate_left(u).wrapping_add(self.s[2 * i]);
+            c = (c ^ u).rotate_left(t).wrapping_add(self.s[2 * i + 1]);
+            (a, b, c, d) = (b, c, d, a);
+        }
+        a = a.wrapping_add(self.s[2 * r + 2]);
+        c = c.wrapping_add(self.s[2 * r + 3]);
+
+        [a, b, c, d]
+    }
+
+    fn decrypt_32(&self, ciphertext: [u32; 4]) -> [u32; 4] {
+        let [mut a, mut b, mut c, mut d] = ciphertext;
+        let r = self.r as usize;
+
+        c = c.wrapping_sub(self.s[2 * r + 3]);
+        a = a.wrapping_sub(self.s[2 * r + 2]);
+        for i in (1..=r).rev() {
+            (a, b, c, d) = (d, a, b, c);
+            let u = d.wrapping_mul(d.wrapping_mul(2).wrapping_add(1)).rotate_left(5);
+            let t = b.wrapping_mul(b.wrapping_mul(2).wrapping_add(1)).rotate_left(5);
+            c = c.wrapping_sub(self.s[2 * i + 1]).rotate_right(t) ^ u;
+            a = a.wrapping_sub(self.s[2 * i]).rotate_right(u) ^ t;
+        }
+        d = d.wrapping_sub(self.s[1]);
+        b = b.wrapping_sub(self.s[0]);
+
+        [a, b, c, d]
+    }
+}
+
+impl Drop for RC6 {
+    fn drop(&mut self) {
+        self.s.zeroize();
+    }
+}
+
+impl std::fmt::Debug for RC6 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RC6")
+            .field("rounds", &self.r)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TryFrom<&[u8]> for RC6 {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        Self::try_new(key, Self::DEFAULT_ROUNDS)
+    }
+}
+
+impl BlockCipher<16> for RC6 {
+    fn encrypt(&self, plaintext: crate::Block<16>) -> crate::Block<16> {
+        let words = std::array::from_fn(|i| {
+            u32::from_le_bytes(<[u8; 4]>::try_from(&plaintext[(4 * i)..][..4]).unwrap())
+        });
+
+        let words = self.encrypt_32(words);
+
+        let mut res = [0_u8; 16];
+        for (i, w) in words.iter().enumerate() {
+            res[(4 * i)..][..4].copy_from_slice(&w.to_le_bytes());
+        }
+        res
+    }
+
+    fn decrypt(&self, ciphertext: crate::Block<16>) -> crate::Block<16> {
+        let words = std::array::from_fn(|i| {
+            u32::from_le_bytes(<[u8; 4]>::try_from(&ciphertext[(4 * i)..][..4]).unwrap())
+        });
+
+        let words = self.decrypt_32(words);
+
+        let mut res = [0_u8; 16];
+        for (i, w) in words.iter().enumerate() {
+            res[(4 * i)..][..4].copy_from_slice(&w.to_le_bytes());
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rc6_expanded_key_size() {
+        assert_eq!(RC6::new(&[0; 16], 20).s.len(), 44);
+        assert_eq!(RC6::new(&[0; 16], 12).s.len(), 28);
+    }
+
+    #[test]
+    fn rc6_round_trip_with_other_rounds() {
+        let cipher = RC6::new(b"a key of any length", 8);
+        let plaintext = *b"sixteen byte msg";
+        assert_ne!(cipher.encrypt(plaintext), RC6::new(b"a key of any length", 20).encrypt(plaintext));
+        assert_eq!(cipher.decrypt(cipher.encrypt(plaintext)), plaintext);
+    }
+
+    #[test]
+    fn rc6_rejects_too_long_keys() {
+        assert!(matches!(
+            RC6::try_new(&[0; 256], 20),
+            Err(KeyError::InvalidLength { actual: 256, .. })
+        ));
+    }
+
+    #[test]
+    fn rc6_debug_output_is_redacted() {
+        assert_eq!(format!("{:?}", RC6::new(&[1, 2, 3], 20)), "RC6 { rounds: 20, .. }");
+    }
+}
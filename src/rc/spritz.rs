@@ -0,0 +1,238 @@
+use crate::zeroize::Zeroize;
+use crate::StreamCipher;
+
+const N: usize = 256;
+
+/// Spritz by Rivest and Schuldt, a sponge-like successor of RC4. The same state is used as a
+/// stream cipher (absorb a key, then squeeze), as a hash and as a MAC.
+#[derive(Clone)]
+pub struct Spritz {
+    s: [u8; N],
+    i: u8,
+    j: u8,
+    k: u8,
+    z: u8,
+    a: u8,
+    w: u8,
+}
+
+impl Spritz {
+    pub fn new() -> Self {
+        let mut s = [0_u8; N];
+        for (v, x) in s.iter_mut().enumerate() {
+            *x = v as u8;
+        }
+
+        Self { s, i: 0, j: 0, k: 0, z: 0, a: 0, w: 1 }
+    }
+
+    /// The key setup of the stream cipher. An empty key is allowed, but then the keystream is
+    /// not secret.
+    pub fn with_key(key: &[u8]) -> Self {
+        let mut spritz = Self::new();
+        spritz.absorb(key);
+        spritz
+    }
+
+    /// The key setup of the stream cipher with an initialization vector.
+    pub fn with_key_and_iv(key: &[u8], iv: &[u8]) -> Self {
+        let mut spritz = Self::with_key(key);
+        spritz.absorb_stop();
+        spritz.absorb(iv);
+        spritz
+    }
+
+    pub fn absorb(&mut self, data: &[u8]) {
+        for &b in data {
+            self.absorb_nibble(b & 0x0f);
+            self.absorb_nibble(b >> 4);
+        }
+    }
+
+    /// Separates inputs, so that absorbing "ab" differs from absorbing "a" and then "b".
+    pub fn absorb_stop(&mut self) {
+        if self.a as usize == N / 2 {
+            self.shuffle();
+        }
+        self.a = self.a.wrapping_add(1);
+    }
+
+    pub fn squeeze(&mut self, output: &mut [u8]) {
+        if self.a > 0 {
+            self.shuffle();
+        }
+        for b in output {
+            *b = self.drip();
+        }
+    }
+
+    pub fn drip(&mut self) -> u8 {
+        if self.a > 0 {
+            self.shuffle();
+        }
+        self.update();
+        self.output()
+    }
+
+    /// Hashes `message` to `output.len()` bytes (at most 255).
+    pub fn hash(message: &[u8], output: &mut [u8]) {
+        let mut spritz = Self::new();
+        spritz.absorb(message);
+        spritz.absorb_stop();
+        spritz.absorb(&[output_length(output)]);
+        spritz.squeeze(output);
+    }
+
+    /// Computes a tag of `output.len()` bytes (at most 255) for `message`.
+    pub fn mac(key: &[u8], message: &[u8], output: &mut [u8]) {
+        let mut spritz = Self::with_key(key);
+        spritz.absorb_stop();
+        spritz.absorb(message);
+        spritz.absorb_stop();
+        spritz.absorb(&[output_length(output)]);
+        spritz.squeeze(output);
+    }
+
+    pub fn encrypt(self, plaintext: &[u8]) -> Vec<u8> {
+        StreamCipher::new(self)
+            .encrypt(plaintext.iter().cloned())
+            .collect::<Vec<_>>()
+    }
+
+    pub fn decrypt(self, ciphertext: &[u8]) -> Vec<u8> {
+        self.encrypt(ciphertext)
+    }
+
+    fn absorb_nibble(&mut self, x: u8) {
+        if self.a as usize == N / 2 {
+            self.shuffle();
+        }
+        self.s.swap(self.a as usize, N / 2 + x as usize);
+        self.a = self.a.wrapping_add(1);
+    }
+
+    fn shuffle(&mut self) {
+        self.whip(2 * N);
+        self.crush();
+        self.whip(2 * N);
+        self.crush();
+        self.whip(2 * N);
+        self.a = 0;
+    }
+
+    fn whip(&mut self, r: usize) {
+        for _ in 0..r {
+            self.update();
+        }
+        // the next value relatively prime to N = 256
+        self.w = self.w.wrapping_add(2);
+    }
+
+    fn crush(&mut self) {
+        for v in 0..(N / 2) {
+            if self.s[v] > self.s[N - 1 - v] {
+                self.s.swap(v, N - 1 - v);
+            }
+        }
+    }
+
+    fn update(&mut self) {
+        let s = &self.s;
+        self.i = self.i.wrapping_add(self.w);
+        self.j = self.k.wrapping_add(s[self.j.wrapping_add(s[self.i as usize]) as usize]);
+        self.k = self.i.wrapping_add(self.k).wrapping_add(s[self.j as usize]);
+        self.s.swap(self.i as usize, self.j as usize);
+    }
+
+    fn output(&mut self) -> u8 {
+        let s = &self.s;
+        let index = self.j.wrapping_add(s[self.i.wrapping_add(s[self.z.wrapping_add(self.k) as usize]) as usize]);
+        self.z = s[index as usize];
+        self.z
+    }
+}
+
+fn output_length(output: &[u8]) -> u8 {
+    u8::try_from(output.len()).expect("Spritz output must be at most 255 bytes")
+}
+
+impl Default for Spritz {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Spritz {
+    fn drop(&mut self) {
+        self.s.zeroize();
+        self.i.zeroize();
+        self.j.zeroize();
+        self.k.zeroize();
+        self.z.zeroize();
+        self.a.zeroize();
+        self.w.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Spritz {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Spritz").finish_non_exhaustive()
+    }
+}
+
+impl Iterator for Spritz {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.drip())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::ManuallyDrop;
+
+    use super::*;
+
+    #[test]
+    fn spritz_key_streams() {
+        #[track_caller]
+        fn check(key: &[u8], expected_key_stream_hex: &str) {
+            let key_stream = Spritz::with_key(key).take(8).collect::<Vec<_>>();
+            assert_eq!(hex::encode(key_stream), expected_key_stream_hex);
+        }
+
+        check(b"ABC", "779a8e01f9e9cbc0");
+        check(b"spam", "f0609a1df143cebf");
+        check(b"arcfour", "1afa8b5ee337dbc7");
+    }
+
+    #[test]
+    fn spritz_hashes() {
+        #[track_caller]
+        fn check(message: &[u8], expected_hash_prefix_hex: &str) {
+            let mut hash = [0; 32];
+            Spritz::hash(message, &mut hash);
+            assert_eq!(hex::encode(&hash[..8]), expected_hash_prefix_hex);
+        }
+
+        check(b"ABC", "028fa2b48b934a18");
+        check(b"spam", "acbba0813f300d3a");
+        check(b"arcfour", "ff8cf268094c87b9");
+    }
+
+    #[test]
+    fn spritz_state_is_zeroized_on_drop() {
+        let mut spritz = ManuallyDrop::new(Spritz::with_key(b"key"));
+        spritz.next();
+
+        unsafe { ManuallyDrop::drop(&mut spritz) };
+        assert_eq!(spritz.s, [0; N]);
+        assert_eq!((spritz.i, spritz.j, spritz.k, spritz.z), (0, 0, 0, 0));
+    }
+
+    #[test]
+    fn spritz_debug_output_is_redacted() {
+        assert_eq!(format!("{:?}", Spritz::with_key(b"key")), "Spritz { .. }");
+    }
+}
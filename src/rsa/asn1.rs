@@ -1,13 +1,10 @@
-use crate::{big::BigUint, pem::asn1::Asn1};
+use crate::{big::BigUint, pem::asn1::Asn1, zeroize::Zeroize};
 
 pub struct PrivateKeyInfo {
-    version: u32,
-    private_key_algorithm: AlgorithmIdentifier,
     pub private_key: PrivateKey,
 }
 
 pub struct SubjectPublicKeyInfo {
-    algorithm: AlgorithmIdentifier,
     pub subject_public_key: PublicKey,
 }
 
@@ -15,7 +12,7 @@ struct AlgorithmIdentifier {
     algorithm: Vec<u8>,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(PartialEq, Eq)]
 pub struct PrivateKey {
     version: u8,
     pub modulus: BigUint,          // n
@@ -28,6 +25,27 @@ pub struct PrivateKey {
     pub coefficient: BigUint,      // (inverse of q) mod p
 }
 
+impl std::fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PrivateKey")
+            .field("version", &self.version)
+            .field("modulus", &self.modulus)
+            .field("public_exponent", &self.public_exponent)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for PrivateKey {
+    fn drop(&mut self) {
+        self.private_exponent.zeroize();
+        self.prime1.zeroize();
+        self.prime2.zeroize();
+        self.exponent1.zeroize();
+        self.exponent2.zeroize();
+        self.coefficient.zeroize();
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct PublicKey {
     pub modulus: BigUint,         // n
@@ -63,11 +81,7 @@ impl PrivateKeyInfo {
         let pr: PrivateKey = PrivateKey::try_from(asn)
             .map_err(|e| format!("failed to extract private key from ASN.1: {}", e))?;
 
-        Ok(Self {
-            version: 0,
-            private_key_algorithm: algo,
-            private_key: pr,
-        })
+        Ok(Self { private_key: pr })
     }
 }
 
@@ -100,10 +114,7 @@ impl SubjectPublicKeyInfo {
             .try_into()
             .map_err(|e| format!("failed to convert ASN.1 into public key: {}", e))?;
 
-        Ok(Self {
-            algorithm: algo,
-            subject_public_key: pb,
-        })
+        Ok(Self { subject_public_key: pb })
     }
 }
 
@@ -345,11 +356,6 @@ mod tests {
         let asn1 = Asn1::try_from(pem.data.as_slice()).unwrap();
         let info = PrivateKeyInfo::try_from(asn1).unwrap();
 
-        assert_eq!(info.version, 0);
-        assert_eq!(
-            info.private_key_algorithm.algorithm,
-            vec![42, 134, 72, 134, 247, 13, 1, 1, 1]
-        );
         assert_eq!(info.private_key.modulus, expected_n);
         assert_eq!(info.private_key.private_exponent, expected_d);
     }
@@ -364,10 +370,6 @@ mod tests {
         let asn1 = Asn1::try_from(pem.data.as_slice()).unwrap();
         let info = SubjectPublicKeyInfo::try_from(asn1).unwrap();
 
-        assert_eq!(
-            info.algorithm.algorithm,
-            vec![42, 134, 72, 134, 247, 13, 1, 1, 1]
-        );
         assert_eq!(info.subject_public_key.modulus, expected_n);
         assert_eq!(info.subject_public_key.public_exponent, expected_e);
     }
@@ -11,6 +11,7 @@ pub use pad::RsaPadding;
 use crate::{
     big::BigUint,
     pem::{asn1::Asn1, PEM},
+    zeroize::Zeroize,
 };
 
 pub trait PaddingScheme {
@@ -26,12 +27,27 @@ pub trait PaddingScheme {
 
 // TODO: the numbers below need to be much bigger than u128, so we need an array of bytes instead
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(PartialEq, Eq, Clone)]
 pub struct PrivateKey {
     n: BigUint,
     d: BigUint,
 }
 
+impl std::fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PrivateKey")
+            .field("n", &self.n)
+            .field("d", &"<redacted>")
+            .finish()
+    }
+}
+
+impl Drop for PrivateKey {
+    fn drop(&mut self) {
+        self.d.zeroize();
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct PublicKey {
     n: BigUint,
@@ -61,15 +77,31 @@ pub fn create_keys(p: BigUint, q: BigUint, e: BigUint) -> (PrivateKey, PublicKey
 }
 
 pub struct RsaEncryption {
-    exponent: BigUint,
+    exponent: BigUint, // secret when created from a private key
     modulo: BigUint,
 }
 
+impl std::fmt::Debug for RsaEncryption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RsaEncryption")
+            .field("exponent", &"<redacted>")
+            .field("modulo", &self.modulo)
+            .finish()
+    }
+}
+
+impl Drop for RsaEncryption {
+    fn drop(&mut self) {
+        self.exponent.zeroize();
+    }
+}
+
 impl From<PrivateKey> for RsaEncryption {
     fn from(key: PrivateKey) -> Self {
+        // the key is zeroized when it is dropped at the end of this function
         RsaEncryption {
-            exponent: key.d,
-            modulo: key.n,
+            exponent: key.d.clone(),
+            modulo: key.n.clone(),
         }
     }
 }
@@ -94,8 +126,8 @@ impl TryFrom<PEM> for PrivateKey {
         let info = PrivateKeyInfo::try_from(asn)?;
 
         Ok(Self {
-            n: info.private_key.modulus,
-            d: info.private_key.private_exponent,
+            n: info.private_key.modulus.clone(),
+            d: info.private_key.private_exponent.clone(),
         })
     }
 }
@@ -422,4 +454,16 @@ mod tests {
 
         assert_eq!(decrypted, plaintext);
     }
+
+    #[test]
+    fn debug_output_of_private_key_is_redacted() {
+        let (private_key, _) = create_keys(61.into(), 53.into(), 17.into());
+        let output = format!("{:?}", private_key);
+
+        assert!(output.contains("<redacted>"));
+        assert!(!output.contains(&format!("{:?}", BigUint::from(413))));
+
+        let output = format!("{:?}", RsaEncryption::from(private_key));
+        assert!(!output.contains(&format!("{:?}", BigUint::from(413))));
+    }
 }
@@ -1,18 +1,18 @@
-use crate::HashFunction;
+use std::marker::PhantomData;
+
+use crate::Digest;
 
 use super::mgf::mgf1;
 
-pub struct OaepPadding<const N: usize, H> {
-    hasher: H,
+pub struct OaepPadding<H> {
+    hash_function: PhantomData<H>,
 }
 
-impl<const N: usize, H> OaepPadding<N, H>
-where
-    H: HashFunction,
-    H::Output: Into<[u8; N]>,
-{
-    pub fn new(hash_function: H) -> Self {
-        Self { hasher: hash_function }
+impl<H: Digest> OaepPadding<H> {
+    pub fn new() -> Self {
+        Self {
+            hash_function: PhantomData,
+        }
     }
 
     pub fn encode(
@@ -21,27 +21,28 @@ where
         message: &[u8],
         n_len: usize,
     ) -> Vec<u8> {
-        let label_hash = self.hasher.hash(label).into();
-        let sub = message.len() + 2 * N + 2;
+        let hash_len = H::OUTPUT_SIZE;
+        let label_hash = H::digest(label);
+        let sub = message.len() + 2 * hash_len + 2;
         if n_len < sub {
             panic!("output length is too small, expected at least {sub} but got {n_len}");
         }
         let ps_len = n_len - sub;
 
         // data block
-        let mut db = Vec::with_capacity(n_len - N - 1);
-        db.extend_from_slice(&label_hash);
+        let mut db = Vec::with_capacity(n_len - hash_len - 1);
+        db.extend_from_slice(label_hash.as_ref());
         db.extend(std::iter::repeat_n(0, ps_len));
         db.push(1);
         db.extend_from_slice(message);
 
         // TODO: generate random seed!!
-        let seed: [u8; N] = [42; N];
+        let seed = vec![42; hash_len];
 
-        let db_mask = mgf1(&seed, n_len - N - 1, self.hasher.clone());
+        let db_mask = mgf1::<H>(&seed, n_len - hash_len - 1);
         let masked_db = xor_slices(&db, &db_mask);
 
-        let seed_mask = mgf1(&masked_db, N, self.hasher.clone());
+        let seed_mask = mgf1::<H>(&masked_db, hash_len);
         let masked_seed = xor_slices(&seed, &seed_mask);
 
         let mut output = Vec::with_capacity(n_len);
@@ -61,20 +62,21 @@ where
             return Err("first byte is not 0");
         }
 
-        let label_hash = self.hasher.hash(label).into();
-        let masked_seed = &encoded_message[1..(N + 1)];
-        let masked_db = &encoded_message[(N + 1)..];
+        let hash_len = H::OUTPUT_SIZE;
+        let label_hash = H::digest(label);
+        let masked_seed = &encoded_message[1..(hash_len + 1)];
+        let masked_db = &encoded_message[(hash_len + 1)..];
 
-        let seed_mask = mgf1(masked_db, N, self.hasher.clone());
+        let seed_mask = mgf1::<H>(masked_db, hash_len);
         let seed = xor_slices(masked_seed, &seed_mask);
 
-        let db_mask = mgf1(&seed, n_len - N - 1, self.hasher.clone());
+        let db_mask = mgf1::<H>(&seed, n_len - hash_len - 1);
         let db = xor_slices(masked_db, &db_mask);
 
-        if db[..N] != label_hash {
+        if db[..hash_len] != *label_hash.as_ref() {
             return Err("wrong label");
         }
-        let rest = &db[N..];
+        let rest = &db[hash_len..];
         let separator_idx = rest
             .iter()
             .position(|&b| b != 0)
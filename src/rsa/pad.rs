@@ -6,7 +6,7 @@ pub struct RsaPadding;
 
 impl PaddingScheme for RsaPadding {
     fn encode(&self, label: &[u8], message: &[u8], n_len: usize) -> Vec<u8> {
-        OaepPadding::new(Sha256).encode(label, message, n_len)
+        OaepPadding::<Sha256>::new().encode(label, message, n_len)
     }
 
     fn decode(
@@ -15,7 +15,7 @@ impl PaddingScheme for RsaPadding {
         encoded_message: &[u8],
         n_len: usize,
     ) -> Result<Vec<u8>, &'static str> {
-        OaepPadding::new(Sha256).decode(label, encoded_message, n_len)
+        OaepPadding::<Sha256>::new().decode(label, encoded_message, n_len)
     }
 }
 
@@ -0,0 +1,66 @@
+//! The Salsa20 core, computed for several consecutive counter values at once in the same lane
+//! layout as the ChaCha block function.
+
+pub(super) const SIGMA: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+pub(super) const TAU: [u32; 4] = [0x61707865, 0x3120646e, 0x79622d36, 0x6b206574];
+
+type Lanes<const B: usize> = [[u32; B]; 16];
+
+#[inline(always)]
+#[allow(clippy::needless_range_loop)]
+fn quarter_round<const B: usize>(x: &mut Lanes<B>, a: usize, b: usize, c: usize, d: usize) {
+    for l in 0..B {
+        x[b][l] ^= x[a][l].wrapping_add(x[d][l]).rotate_left(7);
+        x[c][l] ^= x[b][l].wrapping_add(x[a][l]).rotate_left(9);
+        x[d][l] ^= x[c][l].wrapping_add(x[b][l]).rotate_left(13);
+        x[a][l] ^= x[d][l].wrapping_add(x[c][l]).rotate_left(18);
+    }
+}
+
+/// Applies `rounds` rounds, alternating between column rounds and row rounds.
+fn permute<const B: usize>(x: &mut Lanes<B>, rounds: u8) {
+    for _ in 0..(rounds / 2) {
+        quarter_round(x, 0, 4, 8, 12);
+        quarter_round(x, 5, 9, 13, 1);
+        quarter_round(x, 10, 14, 2, 6);
+        quarter_round(x, 15, 3, 7, 11);
+
+        quarter_round(x, 0, 1, 2, 3);
+        quarter_round(x, 5, 6, 7, 4);
+        quarter_round(x, 10, 11, 8, 9);
+        quarter_round(x, 15, 12, 13, 14);
+    }
+}
+
+/// Writes `B` keystream blocks to `output`, where block `l` uses the 64-bit counter in words 8
+/// and 9 increased by `l`.
+#[allow(clippy::needless_range_loop)]
+pub(super) fn blocks<const B: usize>(state: &[u32; 16], rounds: u8, output: &mut [u8]) {
+    let mut input: Lanes<B> = [[0; B]; 16];
+    for (lanes, &word) in input.iter_mut().zip(state) {
+        *lanes = [word; B];
+    }
+    let counter = state[8] as u64 | (state[9] as u64) << 32;
+    for l in 0..B {
+        let c = counter.wrapping_add(l as u64);
+        input[8][l] = c as u32;
+        input[9][l] = (c >> 32) as u32;
+    }
+
+    let mut x = input;
+    permute(&mut x, rounds);
+
+    for l in 0..B {
+        for i in 0..16 {
+            let word = x[i][l].wrapping_add(input[i][l]);
+            output[(64 * l + 4 * i)..][..4].copy_from_slice(&word.to_le_bytes());
+        }
+    }
+}
+
+/// The Salsa20 rounds without the final addition of the input, as used by HSalsa20.
+pub(super) fn permute_single(state: &[u32; 16], rounds: u8) -> [u32; 16] {
+    let mut x: Lanes<1> = state.map(|w| [w]);
+    permute(&mut x, rounds);
+    x.map(|[w]| w)
+}
@@ -0,0 +1,294 @@
+use crate::key::{fixed_length, KeyError};
+use crate::zeroize::Zeroize;
+use crate::StreamCipher;
+
+mod block;
+mod secretbox;
+
+pub use secretbox::SecretBox;
+
+use block::{SIGMA, TAU};
+
+/// Number of rounds of Salsa20/20. The reduced-round variants Salsa20/12 and Salsa20/8 are
+/// created with `Salsa20::with_rounds`.
+pub const DEFAULT_ROUNDS: u8 = 20;
+
+/// Number of blocks generated per call to the block function.
+const PARALLEL_BLOCKS: usize = 4;
+const BUFFER_LEN: usize = 64 * PARALLEL_BLOCKS;
+
+/// The Salsa20 keystream with a 64-bit nonce and a 64-bit block counter.
+pub struct Salsa20 {
+    state: [u32; 16],
+    rounds: u8,
+    buffer: [u8; BUFFER_LEN],
+    buffer_pos: usize,
+}
+
+impl Salsa20 {
+    pub fn new(key: &[u8; 32], nonce: &[u8; 8]) -> Self {
+        Self::with_rounds(key, nonce, DEFAULT_ROUNDS)
+    }
+
+    pub fn with_rounds(key: &[u8; 32], nonce: &[u8; 8], rounds: u8) -> Self {
+        Self::try_new(key, nonce, rounds).expect("invalid Salsa20 parameters")
+    }
+
+    /// Accepts 128-bit and 256-bit keys, and any positive even number of rounds.
+    pub fn try_new(key: &[u8], nonce: &[u8], rounds: u8) -> Result<Self, KeyError> {
+        if key.len() != 16 && key.len() != 32 {
+            return Err(KeyError::InvalidLength {
+                expected: "16 or 32 bytes",
+                actual: key.len(),
+            });
+        }
+        let nonce: [u8; 8] = <[u8; 8]>::try_from(nonce)
+            .map_err(|_| KeyError::InvalidParameter("nonce must be 8 bytes"))?;
+        if rounds == 0 || !rounds.is_multiple_of(2) {
+            return Err(KeyError::InvalidParameter("rounds must be positive and even"));
+        }
+
+        let (constants, key_high) = if key.len() == 32 {
+            (SIGMA, &key[16..])
+        } else {
+            (TAU, key)
+        };
+
+        let le = |b: &[u8], i: usize| u32::from_le_bytes(b[(4 * i)..][..4].try_into().unwrap());
+
+        let mut state = [0_u32; 16];
+        for i in 0..4 {
+            state[5 * i] = constants[i];
+            state[1 + i] = le(key, i);
+            state[11 + i] = le(key_high, i);
+        }
+        state[6] = le(&nonce, 0);
+        state[7] = le(&nonce, 1);
+
+        Ok(Self {
+            state,
+            rounds,
+            buffer: [0; BUFFER_LEN],
+            buffer_pos: BUFFER_LEN,
+        })
+    }
+
+    /// Moves to the start of the block with the given counter value.
+    pub fn seek(&mut self, block_counter: u64) {
+        self.state[8] = block_counter as u32;
+        self.state[9] = (block_counter >> 32) as u32;
+        self.buffer_pos = BUFFER_LEN;
+    }
+
+    /// XORs the keystream into `data`, processing whole blocks at a time.
+    pub fn apply_keystream(&mut self, data: &mut [u8]) {
+        let mut data = data;
+        while !data.is_empty() {
+            if self.buffer_pos == BUFFER_LEN {
+                self.refill();
+            }
+
+            let n = data.len().min(BUFFER_LEN - self.buffer_pos);
+            let (head, tail) = data.split_at_mut(n);
+            for (d, k) in head.iter_mut().zip(&self.buffer[self.buffer_pos..]) {
+                *d ^= k;
+            }
+            self.buffer_pos += n;
+            data = tail;
+        }
+    }
+
+    pub fn encrypt(self, plaintext: &[u8]) -> Vec<u8> {
+        StreamCipher::new(self)
+            .encrypt(plaintext.iter().cloned())
+            .collect::<Vec<_>>()
+    }
+
+    pub fn decrypt(self, ciphertext: &[u8]) -> Vec<u8> {
+        self.encrypt(ciphertext)
+    }
+
+    fn refill(&mut self) {
+        block::blocks::<PARALLEL_BLOCKS>(&self.state, self.rounds, &mut self.buffer);
+
+        let counter = self.state[8] as u64 | (self.state[9] as u64) << 32;
+        self.seek(counter.wrapping_add(PARALLEL_BLOCKS as u64));
+        self.buffer_pos = 0;
+    }
+}
+
+impl Drop for Salsa20 {
+    fn drop(&mut self) {
+        self.state.zeroize();
+        self.buffer.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Salsa20 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Salsa20")
+            .field("rounds", &self.rounds)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Iterator for Salsa20 {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer_pos == BUFFER_LEN {
+            self.refill();
+        }
+        let b = self.buffer[self.buffer_pos];
+        self.buffer_pos += 1;
+        Some(b)
+    }
+}
+
+/// Derives a subkey from a key and the first 128 bits of an extended nonce, as used by XSalsa20
+/// and by NaCl to turn a Diffie-Hellman shared secret into a key.
+pub fn hsalsa20(key: &[u8; 32], input: &[u8; 16]) -> [u8; 32] {
+    let le = |b: &[u8], i: usize| u32::from_le_bytes(b[(4 * i)..][..4].try_into().unwrap());
+
+    let mut state = [0_u32; 16];
+    for i in 0..4 {
+        state[5 * i] = SIGMA[i];
+        state[1 + i] = le(key, i);
+        state[11 + i] = le(key, 4 + i);
+        state[6 + i] = le(input, i);
+    }
+
+    let mut x = block::permute_single(&state, DEFAULT_ROUNDS);
+
+    let mut subkey = [0_u8; 32];
+    for (i, &w) in [x[0], x[5], x[10], x[15], x[6], x[7], x[8], x[9]].iter().enumerate() {
+        subkey[(4 * i)..][..4].copy_from_slice(&w.to_le_bytes());
+    }
+
+    state.zeroize();
+    x.zeroize();
+    subkey
+}
+
+/// Salsa20/20 with a 192-bit nonce, which is large enough to be chosen at random. The first 128
+/// bits of the nonce are used to derive a subkey with HSalsa20.
+pub struct XSalsa20 {
+    inner: Salsa20,
+}
+
+impl XSalsa20 {
+    pub fn new(key: &[u8; 32], nonce: &[u8; 24]) -> Self {
+        let mut subkey = hsalsa20(key, nonce[..16].try_into().unwrap());
+        let inner = Salsa20::new(&subkey, nonce[16..].try_into().unwrap());
+        subkey.zeroize();
+        Self { inner }
+    }
+
+    pub fn try_new(key: &[u8], nonce: &[u8]) -> Result<Self, KeyError> {
+        let mut key: [u8; 32] = fixed_length(key, "32 bytes")?;
+        let nonce: [u8; 24] = <[u8; 24]>::try_from(nonce)
+            .map_err(|_| KeyError::InvalidParameter("nonce must be 24 bytes"))?;
+
+        let cipher = Self::new(&key, &nonce);
+        key.zeroize();
+        Ok(cipher)
+    }
+
+    pub fn seek(&mut self, block_counter: u64) {
+        self.inner.seek(block_counter);
+    }
+
+    pub fn apply_keystream(&mut self, data: &mut [u8]) {
+        self.inner.apply_keystream(data);
+    }
+
+    pub fn encrypt(self, plaintext: &[u8]) -> Vec<u8> {
+        StreamCipher::new(self)
+            .encrypt(plaintext.iter().cloned())
+            .collect::<Vec<_>>()
+    }
+
+    pub fn decrypt(self, ciphertext: &[u8]) -> Vec<u8> {
+        self.encrypt(ciphertext)
+    }
+}
+
+impl std::fmt::Debug for XSalsa20 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("XSalsa20").finish_non_exhaustive()
+    }
+}
+
+impl Iterator for XSalsa20 {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::ManuallyDrop;
+
+    use super::*;
+
+    #[test]
+    fn counter_carries_into_high_word() {
+        let mut cipher = Salsa20::new(&[1; 32], &[2; 8]);
+        cipher.seek(u32::MAX as u64);
+        let blocks = cipher.take(128).collect::<Vec<_>>();
+
+        let mut cipher = Salsa20::new(&[1; 32], &[2; 8]);
+        cipher.seek(1 << 32);
+        assert_eq!(cipher.take(64).collect::<Vec<_>>(), blocks[64..]);
+    }
+
+    #[test]
+    fn counter_wraps_around() {
+        let mut cipher = Salsa20::new(&[1; 32], &[2; 8]);
+        cipher.seek(u64::MAX);
+        let blocks = cipher.take(128).collect::<Vec<_>>();
+
+        let cipher = Salsa20::new(&[1; 32], &[2; 8]);
+        assert_eq!(cipher.take(64).collect::<Vec<_>>(), blocks[64..]);
+    }
+
+    #[test]
+    fn rejects_invalid_parameters() {
+        assert!(matches!(
+            Salsa20::try_new(&[0; 24], &[0; 8], 20),
+            Err(KeyError::InvalidLength { actual: 24, .. })
+        ));
+        assert_eq!(
+            Salsa20::try_new(&[0; 32], &[0; 12], 20).unwrap_err(),
+            KeyError::InvalidParameter("nonce must be 8 bytes")
+        );
+        assert!(Salsa20::try_new(&[0; 16], &[0; 8], 0).is_err());
+        assert!(Salsa20::try_new(&[0; 16], &[0; 8], 7).is_err());
+        assert!(Salsa20::try_new(&[0; 16], &[0; 8], 8).is_ok());
+        assert!(XSalsa20::try_new(&[0; 32], &[0; 8]).is_err());
+    }
+
+    #[test]
+    fn state_is_zeroized_on_drop() {
+        let mut cipher = ManuallyDrop::new(Salsa20::new(&[1; 32], &[2; 8]));
+        cipher.next();
+
+        unsafe { ManuallyDrop::drop(&mut cipher) };
+        assert_eq!(cipher.state, [0; 16]);
+        assert_eq!(cipher.buffer, [0; BUFFER_LEN]);
+    }
+
+    #[test]
+    fn debug_output_is_redacted() {
+        assert_eq!(
+            format!("{:?}", Salsa20::with_rounds(&[0; 32], &[0; 8], 12)),
+            "Salsa20 { rounds: 12, .. }"
+        );
+        assert_eq!(
+            format!("{:?}", XSalsa20::new(&[0; 32], &[0; 24])),
+            "XSalsa20 { .. }"
+        );
+    }
+}
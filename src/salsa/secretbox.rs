@@ -0,0 +1,121 @@
+use crate::aead::AuthenticationError;
+use crate::key::{decode_base64, decode_hex, fixed_length, KeyError};
+use crate::poly1305::{Poly1305, Tag};
+use crate::zeroize::Zeroize;
+
+use super::{Salsa20, XSalsa20};
+
+/// Authenticated encryption with XSalsa20 and Poly1305, compatible with `crypto_secretbox` in
+/// NaCl and `crypto_secretbox_easy` in libsodium. The sealed message is the 16 byte tag followed
+/// by the ciphertext.
+pub struct SecretBox {
+    key: [u8; 32],
+}
+
+impl SecretBox {
+    pub fn new(key: &[u8; 32]) -> Self {
+        Self { key: *key }
+    }
+
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+
+    pub fn seal(&self, nonce: &[u8; 24], plaintext: &[u8]) -> Vec<u8> {
+        let (mut stream, mac) = self.start(nonce);
+
+        let mut output = vec![0; 16];
+        output.extend_from_slice(plaintext);
+        stream.apply_keystream(&mut output[16..]);
+
+        let tag = authenticate(mac, &output[16..]).finalize();
+        output[..16].copy_from_slice(&tag);
+        output
+    }
+
+    pub fn open(&self, nonce: &[u8; 24], sealed: &[u8]) -> Result<Vec<u8>, AuthenticationError> {
+        if sealed.len() < 16 {
+            return Err(AuthenticationError);
+        }
+        let (tag, ciphertext) = sealed.split_at(16);
+        let tag: &Tag = tag.try_into().unwrap();
+
+        let (mut stream, mac) = self.start(nonce);
+        if !authenticate(mac, ciphertext).verify(tag) {
+            return Err(AuthenticationError);
+        }
+
+        let mut output = ciphertext.to_vec();
+        stream.apply_keystream(&mut output);
+        Ok(output)
+    }
+
+    /// The first 32 bytes of the keystream give the one-time Poly1305 key, and the message is
+    /// encrypted with the keystream that follows.
+    fn start(&self, nonce: &[u8; 24]) -> (Salsa20, Poly1305) {
+        let mut stream = XSalsa20::new(&self.key, nonce).inner;
+
+        let mut mac_key = [0_u8; 32];
+        stream.apply_keystream(&mut mac_key);
+        let mac = Poly1305::new(&mac_key);
+        mac_key.zeroize();
+
+        (stream, mac)
+    }
+}
+
+fn authenticate(mut mac: Poly1305, ciphertext: &[u8]) -> Poly1305 {
+    mac.update(ciphertext);
+    mac
+}
+
+impl TryFrom<&[u8]> for SecretBox {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            key: fixed_length(key, "32 bytes")?,
+        })
+    }
+}
+
+impl Drop for SecretBox {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+impl std::fmt::Debug for SecretBox {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretBox").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::ManuallyDrop;
+
+    use super::*;
+
+    #[test]
+    fn too_short_message_is_rejected() {
+        let secret_box = SecretBox::new(&[0; 32]);
+        assert_eq!(secret_box.open(&[0; 24], &[0; 15]), Err(AuthenticationError));
+    }
+
+    #[test]
+    fn key_is_zeroized_on_drop() {
+        let mut secret_box = ManuallyDrop::new(SecretBox::new(&[1; 32]));
+        unsafe { ManuallyDrop::drop(&mut secret_box) };
+        assert_eq!(secret_box.key, [0; 32]);
+    }
+
+    #[test]
+    fn debug_output_is_redacted() {
+        assert_eq!(format!("{:?}", SecretBox::new(&[0; 32])), "SecretBox { .. }");
+    }
+}
@@ -0,0 +1,285 @@
+use crate::key::{check_length, decode_base64, decode_hex, KeyError};
+use crate::zeroize::Zeroize;
+use crate::{Block, BlockCipher};
+
+const ROUNDS: usize = 32;
+const PHI: u32 = 0x9e3779b9;
+
+#[rustfmt::skip]
+const SBOXES: [[u8; 16]; 8] = [
+    [3, 8, 15, 1, 10, 6, 5, 11, 14, 13, 4, 2, 7, 0, 9, 12],
+    [15, 12, 2, 7, 9, 0, 5, 10, 1, 11, 14, 8, 6, 13, 3, 4],
+    [8, 6, 7, 9, 3, 12, 10, 15, 13, 1, 14, 4, 0, 11, 5, 2],
+    [0, 15, 11, 8, 12, 9, 6, 3, 13, 1, 2, 4, 10, 7, 5, 14],
+    [1, 15, 8, 3, 12, 0, 11, 6, 2, 5, 4, 10, 9, 14, 7, 13],
+    [15, 5, 2, 11, 4, 10, 9, 12, 0, 3, 14, 8, 13, 6, 7, 1],
+    [7, 2, 12, 5, 8, 4, 6, 11, 14, 9, 1, 15, 13, 3, 10, 0],
+    [1, 13, 15, 0, 14, 8, 2, 11, 7, 4, 12, 10, 9, 3, 5, 6],
+];
+
+const FORWARD: [Anf; 8] = algebraic_normal_forms(false);
+const BACKWARD: [Anf; 8] = algebraic_normal_forms(true);
+
+/// An S-box as a polynomial over GF(2) for each output bit. Bit `m` of an output polynomial is
+/// set if it contains the product of the input bits in `m`, so that the S-box can be evaluated
+/// on 32 nibbles at once using only AND and XOR.
+type Anf = [u16; 4];
+
+const fn algebraic_normal_forms(inverse: bool) -> [Anf; 8] {
+    let mut forms = [[0; 4]; 8];
+    let mut s = 0;
+    while s < 8 {
+        let mut table = [0_u8; 16];
+        let mut x = 0;
+        while x < 16 {
+            if inverse {
+                table[SBOXES[s][x] as usize] = x as u8;
+            } else {
+                table[x] = SBOXES[s][x];
+            }
+            x += 1;
+        }
+
+        let mut bit = 0;
+        while bit < 4 {
+            // the Moebius transform of the truth table of the output bit
+            let mut coefficients = [0_u8; 16];
+            let mut x = 0;
+            while x < 16 {
+                coefficients[x] = (table[x] >> bit) & 1;
+                x += 1;
+            }
+            let mut step = 1;
+            while step < 16 {
+                let mut x = 0;
+                while x < 16 {
+                    if x & step != 0 {
+                        coefficients[x] ^= coefficients[x ^ step];
+                    }
+                    x += 1;
+                }
+                step <<= 1;
+            }
+
+            let mut m = 0;
+            while m < 16 {
+                forms[s][bit] |= (coefficients[m] as u16) << m;
+                m += 1;
+            }
+            bit += 1;
+        }
+        s += 1;
+    }
+    forms
+}
+
+/// Applies an S-box in bitsliced form: bit `j` of word `i` is bit `i` of the `j`th nibble.
+fn substitute(x: [u32; 4], anf: &Anf) -> [u32; 4] {
+    let mut monomials = [u32::MAX; 16];
+    for m in 1..16_usize {
+        let lowest = m.trailing_zeros() as usize;
+        monomials[m] = monomials[m & (m - 1)] & x[lowest];
+    }
+
+    anf.map(|polynomial| {
+        (0..16)
+            .filter(|m| polynomial & (1 << m) != 0)
+            .fold(0, |y, m| y ^ monomials[m])
+    })
+}
+
+fn linear_transform([mut x0, mut x1, mut x2, mut x3]: [u32; 4]) -> [u32; 4] {
+    x0 = x0.rotate_left(13);
+    x2 = x2.rotate_left(3);
+    x1 ^= x0 ^ x2;
+    x3 ^= x2 ^ (x0 << 3);
+    x1 = x1.rotate_left(1);
+    x3 = x3.rotate_left(7);
+    x0 ^= x1 ^ x3;
+    x2 ^= x3 ^ (x1 << 7);
+    x0 = x0.rotate_left(5);
+    x2 = x2.rotate_left(22);
+    [x0, x1, x2, x3]
+}
+
+fn inverse_linear_transform([mut x0, mut x1, mut x2, mut x3]: [u32; 4]) -> [u32; 4] {
+    x2 = x2.rotate_right(22);
+    x0 = x0.rotate_right(5);
+    x2 ^= x3 ^ (x1 << 7);
+    x0 ^= x1 ^ x3;
+    x3 = x3.rotate_right(7);
+    x1 = x1.rotate_right(1);
+    x3 ^= x2 ^ (x0 << 3);
+    x1 ^= x0 ^ x2;
+    x2 = x2.rotate_right(3);
+    x0 = x0.rotate_right(13);
+    [x0, x1, x2, x3]
+}
+
+fn xor(x: [u32; 4], key: &[u32; 4]) -> [u32; 4] {
+    std::array::from_fn(|i| x[i] ^ key[i])
+}
+
+/// Serpent by Anderson, Biham and Knudsen, with keys of up to 256 bits. The implementation is
+/// bitsliced, which avoids table lookups that depend on secret data. Words are read in
+/// little-endian order, which is compatible with the NESSIE test vectors.
+pub struct Serpent {
+    keys: [[u32; 4]; ROUNDS + 1],
+}
+
+impl Serpent {
+    pub fn new(key: &[u8]) -> Self {
+        Self::try_from(key).expect("invalid Serpent key")
+    }
+
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+
+    fn expand_key(key: &[u8]) -> Self {
+        // shorter keys are padded with a single one bit followed by zeros
+        let mut padded = [0_u8; 32];
+        padded[..key.len()].copy_from_slice(key);
+        if key.len() < 32 {
+            padded[key.len()] = 1;
+        }
+
+        let mut w = [0_u32; 8 + 4 * (ROUNDS + 1)];
+        for (i, word) in padded.chunks_exact(4).enumerate() {
+            w[i] = u32::from_le_bytes(word.try_into().unwrap());
+        }
+        for i in 8..w.len() {
+            w[i] = (w[i - 8] ^ w[i - 5] ^ w[i - 3] ^ w[i - 1] ^ PHI ^ (i - 8) as u32).rotate_left(11);
+        }
+
+        let mut keys = [[0_u32; 4]; ROUNDS + 1];
+        for (i, (k, prekey)) in keys.iter_mut().zip(w[8..].chunks_exact(4)).enumerate() {
+            let sbox = (ROUNDS + 3 - i) % 8;
+            *k = substitute(prekey.try_into().unwrap(), &FORWARD[sbox]);
+        }
+
+        padded.zeroize();
+        w.zeroize();
+        Self { keys }
+    }
+}
+
+/// Accepts keys of 0 to 256 bits. The standard key lengths are 128, 192 and 256 bits.
+impl TryFrom<&[u8]> for Serpent {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        check_length(key, 0..=32, "at most 32 bytes")?;
+        Ok(Self::expand_key(key))
+    }
+}
+
+impl Drop for Serpent {
+    fn drop(&mut self) {
+        self.keys.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Serpent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Serpent").finish_non_exhaustive()
+    }
+}
+
+impl BlockCipher<16> for Serpent {
+    fn encrypt(&self, plaintext: Block<16>) -> Block<16> {
+        let mut x: [u32; 4] =
+            std::array::from_fn(|i| u32::from_le_bytes(plaintext[(4 * i)..][..4].try_into().unwrap()));
+
+        for round in 0..ROUNDS {
+            x = substitute(xor(x, &self.keys[round]), &FORWARD[round % 8]);
+            x = if round < ROUNDS - 1 {
+                linear_transform(x)
+            } else {
+                xor(x, &self.keys[ROUNDS])
+            };
+        }
+
+        let mut res = [0_u8; 16];
+        for (chunk, w) in res.chunks_exact_mut(4).zip(x) {
+            chunk.copy_from_slice(&w.to_le_bytes());
+        }
+        res
+    }
+
+    fn decrypt(&self, ciphertext: Block<16>) -> Block<16> {
+        let mut x: [u32; 4] =
+            std::array::from_fn(|i| u32::from_le_bytes(ciphertext[(4 * i)..][..4].try_into().unwrap()));
+
+        for round in (0..ROUNDS).rev() {
+            x = if round < ROUNDS - 1 {
+                inverse_linear_transform(x)
+            } else {
+                xor(x, &self.keys[ROUNDS])
+            };
+            x = xor(substitute(x, &BACKWARD[round % 8]), &self.keys[round]);
+        }
+
+        let mut res = [0_u8; 16];
+        for (chunk, w) in res.chunks_exact_mut(4).zip(x) {
+            chunk.copy_from_slice(&w.to_le_bytes());
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bitsliced_sboxes_match_tables() {
+        for (s, sbox) in SBOXES.iter().enumerate() {
+            // nibble j is the value j, so every entry of the table is looked up once
+            let mut x = [0_u32; 4];
+            for j in 0..16 {
+                for (i, w) in x.iter_mut().enumerate() {
+                    *w |= ((j >> i) & 1) << j;
+                }
+            }
+
+            let y = substitute(x, &FORWARD[s]);
+            for (j, &expected) in sbox.iter().enumerate() {
+                let nibble = (0..4).fold(0, |n, i| n | ((y[i] >> j) & 1) << i);
+                assert_eq!(nibble, expected as u32, "S{s}[{j}]");
+            }
+            assert_eq!(substitute(y, &BACKWARD[s]), x);
+        }
+    }
+
+    #[test]
+    fn linear_transform_is_invertible() {
+        let x = [0x01234567, 0x89abcdef, 0xfedcba98, 0x76543210];
+        assert_eq!(inverse_linear_transform(linear_transform(x)), x);
+    }
+
+    #[test]
+    fn key_length_is_validated() {
+        assert!(matches!(
+            Serpent::try_from(&[0; 33][..]),
+            Err(KeyError::InvalidLength { actual: 33, .. })
+        ));
+        assert!(Serpent::try_from(&[0; 5][..]).is_ok());
+    }
+
+    #[test]
+    fn subkeys_are_zeroized_on_drop() {
+        let mut cipher = std::mem::ManuallyDrop::new(Serpent::new(&[1; 32]));
+        unsafe { std::mem::ManuallyDrop::drop(&mut cipher) };
+        assert_eq!(cipher.keys, [[0; 4]; ROUNDS + 1]);
+    }
+
+    #[test]
+    fn debug_output_is_redacted() {
+        assert_eq!(format!("{:?}", Serpent::new(&[0; 16])), "Serpent { .. }");
+    }
+}
@@ -0,0 +1,129 @@
+use crate::zeroize::Zeroize;
+use crate::Block;
+
+/// Collects the input of a streaming hash into blocks of `N` bytes, so that the compression
+/// function only ever sees whole blocks, and counts the length of the message.
+#[derive(Clone)]
+pub(crate) struct BlockBuffer<const N: usize> {
+    buffer: Block<N>,
+    buffer_len: usize,
+    message_length: u128,
+    /// The number of bytes that were copied into the buffer, which tests use to check that
+    /// whole blocks are not.
+    #[cfg(test)]
+    copied_bytes: usize,
+}
+
+impl<const N: usize> BlockBuffer<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            buffer: [0; N],
+            buffer_len: 0,
+            message_length: 0,
+            #[cfg(test)]
+            copied_bytes: 0,
+        }
+    }
+
+    /// Calls `process` for every block that is completed by `data`. Whole blocks of `data` are
+    /// passed on directly rather than copied into the buffer first.
+    pub(crate) fn update(&mut self, mut data: &[u8], mut process: impl FnMut(&Block<N>)) {
+        self.message_length += data.len() as u128;
+
+        if self.buffer_len > 0 {
+            let n = data.len().min(N - self.buffer_len);
+            self.buffer[self.buffer_len..][..n].copy_from_slice(&data[..n]);
+            self.buffer_len += n;
+            data = &data[n..];
+            #[cfg(test)]
+            {
+                self.copied_bytes += n;
+            }
+            if self.buffer_len < N {
+                return;
+            }
+            process(&self.buffer);
+            self.buffer_len = 0;
+        }
+
+        let chunks = data.chunks_exact(N);
+        let rest = chunks.remainder();
+        for chunk in chunks {
+            process(chunk.try_into().unwrap());
+        }
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffer_len = rest.len();
+        #[cfg(test)]
+        {
+            self.copied_bytes += rest.len();
+        }
+    }
+
+    /// The bytes after the last whole block, which go into the padding.
+    pub(crate) fn remainder(&self) -> &[u8] {
+        &self.buffer[..self.buffer_len]
+    }
+
+    /// The number of bytes that have been hashed so far.
+    pub(crate) fn message_length(&self) -> u128 {
+        self.message_length
+    }
+
+    #[cfg(test)]
+    pub(crate) fn copied_bytes(&self) -> usize {
+        self.copied_bytes
+    }
+}
+
+impl<const N: usize> Drop for BlockBuffer<N> {
+    fn drop(&mut self) {
+        self.buffer.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocks_are_completed_across_updates() {
+        let mut buffer = BlockBuffer::<4>::new();
+        let mut blocks = Vec::new();
+
+        buffer.update(&[1, 2, 3], |b| blocks.push(*b));
+        assert!(blocks.is_empty());
+        buffer.update(&[4, 5, 6, 7, 8, 9], |b| blocks.push(*b));
+        assert_eq!(blocks, [[1, 2, 3, 4], [5, 6, 7, 8]]);
+        buffer.update(&[], |b| blocks.push(*b));
+
+        assert_eq!(buffer.remainder(), [9]);
+        assert_eq!(buffer.message_length(), 9);
+    }
+
+    #[test]
+    fn whole_blocks_are_passed_without_copying() {
+        let data = [7; 4 * 100 + 2];
+        let mut buffer = BlockBuffer::<4>::new();
+        buffer.update(&[1], |_| {});
+
+        let mut blocks = 0;
+        buffer.update(&data, |block| {
+            // after the first block, which is completed in the buffer, the blocks are borrowed
+            // from `data`
+            if blocks > 0 {
+                assert!(data.as_ptr_range().contains(&block.as_ptr()));
+            }
+            blocks += 1;
+        });
+        assert_eq!(blocks, 100);
+        assert_eq!(buffer.copied_bytes(), 1 + 3 + 3);
+    }
+
+    #[test]
+    fn buffer_is_zeroized_on_drop() {
+        let mut buffer = std::mem::ManuallyDrop::new(BlockBuffer::<4>::new());
+        buffer.update(&[1, 2], |_| {});
+        unsafe { std::mem::ManuallyDrop::drop(&mut buffer) };
+        assert_eq!(buffer.buffer, [0; 4]);
+    }
+}
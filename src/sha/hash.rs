@@ -3,6 +3,7 @@ use hex::FromHex;
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct HashValue<const N: usize>([u8; N]);
 
+pub type Hash128 = HashValue<16>;
 pub type Hash160 = HashValue<20>;
 pub type Hash224 = HashValue<28>;
 pub type Hash256 = HashValue<32>;
@@ -73,6 +74,14 @@ impl From<Hash512> for [u64; 8] {
     }
 }
 
+// From bytes
+
+impl<const N: usize> From<[u8; N]> for HashValue<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        HashValue(bytes)
+    }
+}
+
 // To bytes
 
 impl<const N: usize> From<HashValue<N>> for [u8; N] {
@@ -81,6 +90,12 @@ impl<const N: usize> From<HashValue<N>> for [u8; N] {
     }
 }
 
+impl<const N: usize> AsRef<[u8]> for HashValue<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
 // From hex
 
 impl<const N: usize> FromHex for HashValue<N> {
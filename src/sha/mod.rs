@@ -1,5 +1,6 @@
+pub(crate) mod buffer;
 mod hash;
-mod pad;
+pub(crate) mod pad;
 mod sha1;
 mod sha2;
 
@@ -8,10 +9,22 @@ pub use sha1::Sha1;
 
 pub use sha2::sha224;
 pub use sha2::sha256;
-pub use sha2::Sha256;
 pub use sha2::sha384;
 pub use sha2::sha512;
+pub use sha2::sha512_224;
+pub use sha2::sha512_256;
+pub use sha2::sha512_t;
+pub use sha2::sha512_t_initial_hash;
+pub use sha2::Sha224;
+pub use sha2::Sha256;
+pub use sha2::Sha384;
+pub use sha2::Sha512;
+pub use sha2::Sha512T;
+pub use sha2::Sha512_224;
+pub use sha2::Sha512_256;
 
+pub use hash::HashValue;
+pub use hash::Hash128;
 pub use hash::Hash160;
 pub use hash::Hash224;
 pub use hash::Hash256;
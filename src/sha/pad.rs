@@ -1,24 +1,34 @@
 use crate::Block;
 
-pub fn sha1_padding(src: &[u8], message_length: u64) -> (Block<64>, Option<Block<64>>) {
-    let mut last = [0; 64];
-    let mut extra = [0; 64];
+/// Pads the bytes after the last whole block of a Merkle–Damgård hash: the bit 1, then zeros
+/// up to the encoded message `length` at the end of a block. A second block is needed if the
+/// length does not fit after the 1 bit.
+pub(crate) fn md_padding<const N: usize>(
+    src: &[u8],
+    length: &[u8],
+) -> (Block<N>, Option<Block<N>>) {
+    let mut last = [0; N];
+    let mut extra = [0; N];
 
     last[..src.len()].copy_from_slice(src);
     last[src.len()] = 0x80;
 
-    if src.len() < 64 - 8 {
-        last[(64 - 8)..].copy_from_slice(&(message_length * 8).to_be_bytes());
+    if src.len() < N - length.len() {
+        last[(N - length.len())..].copy_from_slice(length);
         (last, None)
     } else {
-        extra[(64 - 8)..].copy_from_slice(&(message_length * 8).to_be_bytes());
+        extra[(N - length.len())..].copy_from_slice(length);
         (last, Some(extra))
     }
 }
 
+pub fn sha1_padding(src: &[u8], message_length: u64) -> (Block<64>, Option<Block<64>>) {
+    md_padding(src, &(message_length * 8).to_be_bytes())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::sha1_padding;
+    use super::{md_padding, sha1_padding};
 
     #[test]
     fn pad_empty_chunk_sets_1_bit() {
@@ -58,4 +68,31 @@ mod tests {
             (expected_chunk, Some(expected_extra_chunk))
         );
     }
+
+    #[test]
+    fn pad_little_endian_length_of_whole_block() {
+        let mut expected_chunk = [0; 64];
+        expected_chunk[0] = 0x80;
+        expected_chunk[(64 - 8)..].copy_from_slice(&(64_u64 * 8).to_le_bytes());
+
+        assert_eq!(
+            md_padding::<64>(b"", &(64_u64 * 8).to_le_bytes()),
+            (expected_chunk, None)
+        );
+    }
+
+    #[test]
+    fn pad_with_long_length_field_creates_extra_chunk() {
+        let mut expected_chunk = [0; 64];
+        expected_chunk[..32].copy_from_slice(&[1; 32]);
+        expected_chunk[32] = 0x80;
+
+        let mut expected_extra_chunk = [0; 64];
+        expected_extra_chunk[32..].copy_from_slice(&[2; 32]);
+
+        assert_eq!(
+            md_padding::<64>(&[1; 32], &[2; 32]),
+            (expected_chunk, Some(expected_extra_chunk))
+        );
+    }
 }
@@ -1,15 +1,82 @@
-use crate::HashFunction;
+use crate::zeroize::Zeroize;
+use crate::Digest;
 
-use super::{hash::Hash160, pad::sha1_padding};
+use super::{buffer::BlockBuffer, hash::Hash160, pad::sha1_padding};
+
+const INITIAL_HASH: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
 
 #[derive(Clone)]
-pub struct Sha1;
+pub struct Sha1 {
+    hash: [u32; 5],
+    buffer: BlockBuffer<64>,
+}
+
+impl Sha1 {
+    pub fn new() -> Self {
+        Self {
+            hash: INITIAL_HASH,
+            buffer: BlockBuffer::new(),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        let hash = &mut self.hash;
+        self.buffer.update(data, |chunk| process_chunk(hash, *chunk));
+    }
+
+    pub fn finalize(mut self) -> Hash160 {
+        let message_length = self.buffer.message_length() as u64;
+        let (last_chunk, extra_chunk) = sha1_padding(self.buffer.remainder(), message_length);
+
+        process_chunk(&mut self.hash, last_chunk);
+        if let Some(extra_chunk) = extra_chunk {
+            process_chunk(&mut self.hash, extra_chunk);
+        }
+
+        self.hash.into()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl Default for Sha1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-impl HashFunction for Sha1 {
+impl Drop for Sha1 {
+    fn drop(&mut self) {
+        self.hash.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Sha1 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sha1").finish_non_exhaustive()
+    }
+}
+
+impl Digest for Sha1 {
     type Output = Hash160;
 
-    fn hash(&self, message: &[u8]) -> Self::Output {
-        sha1(message)
+    const OUTPUT_SIZE: usize = 20;
+    const BLOCK_SIZE: usize = 64;
+    const NAME: &'static str = "SHA-1";
+    const OID: Option<&'static [u8]> = Some(&[0x2b, 0x0e, 0x03, 0x02, 0x1a]);
+
+    fn new() -> Self {
+        Self::new()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.update(data);
+    }
+
+    fn finalize(self) -> Self::Output {
+        self.finalize()
     }
 }
 
@@ -56,20 +123,7 @@ fn process_chunk(hash: &mut [u32; 5], chunk: [u8; 64]) {
 }
 
 pub fn sha1(message: &[u8]) -> Hash160 {
-    let chunks = message.chunks_exact(64);
-    let remaining_bytes = chunks.remainder();
-    let (last_chunk, extra_chunk) = sha1_padding(remaining_bytes, message.len() as u64);
-
-    let mut hash = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
-
-    for chunk in chunks {
-        let chunk = <[u8; 64]>::try_from(chunk).unwrap();
-        process_chunk(&mut hash, chunk);
-    }
-    process_chunk(&mut hash, last_chunk);
-    if let Some(extra_chunk) = extra_chunk {
-        process_chunk(&mut hash, extra_chunk);
-    }
-
-    hash.into()
+    let mut hasher = Sha1::new();
+    hasher.update(message);
+    hasher.finalize()
 }
@@ -1,8 +1,10 @@
-use crate::{Block, HashFunction};
+use crate::zeroize::Zeroize;
+use crate::{Block, Digest};
 
 use super::{
+    buffer::BlockBuffer,
     hash::{Hash224, Hash256, Hash384, Hash512},
-    pad::sha1_padding,
+    pad::{md_padding, sha1_padding},
 };
 
 #[rustfmt::skip]
@@ -29,6 +31,18 @@ static INITIAL_HASH_512: [u64; 8] = [
     0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
 ];
 
+#[rustfmt::skip]
+static INITIAL_HASH_512_224: [u64; 8] = [
+    0x8c3d37c819544da2, 0x73e1996689dcd4d6, 0x1dfab7ae32ff9c82, 0x679dd514582f9fcf,
+    0x0f6d2b697bd44da8, 0x77e36f7304c48942, 0x3f9d85a86a1d36c8, 0x1112e6ad91d692a1,
+];
+
+#[rustfmt::skip]
+static INITIAL_HASH_512_256: [u64; 8] = [
+    0x22312194fc2bf72c, 0x9f555fa3c84c64c2, 0x2393b86b6f53b151, 0x963877195940eabd,
+    0x96283ee2a88effe3, 0xbe5e1e2553863992, 0x2b0199fc2c85b8aa, 0x0eb72ddc81c52ca2,
+];
+
 #[rustfmt::skip]
 static ROUND_CONSTANTS: [u32; 64] = [
     0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5,
@@ -73,91 +87,341 @@ static ROUND_CONSTANTS_BIG: [u64; 80] = [
    0x4cc5d4becb3e42b6, 0x597f299cfc657e2a, 0x5fcb6fab3ad6faec, 0x6c44198c4a475817,
 ];
 
+/// The state of SHA-224 and SHA-256, which only differ in the initial hash and in how much of
+/// the final hash is output.
 #[derive(Clone)]
-pub struct Sha256;
-
-impl HashFunction for Sha256 {
-    type Output = Hash256;
+struct State {
+    hash: [u32; 8],
+    buffer: BlockBuffer<64>,
+}
 
-    fn hash(&self, message: &[u8]) -> Self::Output {
-        sha256(message)
+impl State {
+    fn new(initial_hash: [u32; 8]) -> Self {
+        Self {
+            hash: initial_hash,
+            buffer: BlockBuffer::new(),
+        }
     }
-}
 
-pub fn sha224(message: &[u8]) -> Hash224 {
-    let chunks = message.chunks_exact(64);
-    let remaining_bytes = chunks.remainder();
-    let (last_chunk, extra_chunk) = sha2_padding(remaining_bytes, message.len() as u64);
+    fn update(&mut self, data: &[u8]) {
+        let hash = &mut self.hash;
+        self.buffer.update(data, |chunk| process_chunk(hash, *chunk));
+    }
 
-    let mut hash = INITIAL_HASH_224;
+    fn finalize(&mut self) -> [u32; 8] {
+        let message_length = self.buffer.message_length() as u64;
+        let (last_chunk, extra_chunk) = sha2_padding(self.buffer.remainder(), message_length);
 
-    for chunk in chunks {
-        let chunk = <[u8; 64]>::try_from(chunk).unwrap();
-        process_chunk(&mut hash, chunk);
+        process_chunk(&mut self.hash, last_chunk);
+        if let Some(extra_chunk) = extra_chunk {
+            process_chunk(&mut self.hash, extra_chunk);
+        }
+        self.hash
     }
-    process_chunk(&mut hash, last_chunk);
-    if let Some(extra_chunk) = extra_chunk {
-        process_chunk(&mut hash, extra_chunk);
+}
+
+impl Drop for State {
+    fn drop(&mut self) {
+        self.hash.zeroize();
     }
+}
 
-    <[u32; 7]>::try_from(&hash[..7]).unwrap().into()
+/// The state of SHA-384 and SHA-512, with 64-bit words and 128 byte chunks.
+#[derive(Clone)]
+struct BigState {
+    hash: [u64; 8],
+    buffer: BlockBuffer<128>,
 }
 
-pub fn sha256(message: &[u8]) -> Hash256 {
-    let chunks = message.chunks_exact(64);
-    let remaining_bytes = chunks.remainder();
-    let (last_chunk, extra_chunk) = sha2_padding(remaining_bytes, message.len() as u64);
+impl BigState {
+    fn new(initial_hash: [u64; 8]) -> Self {
+        Self {
+            hash: initial_hash,
+            buffer: BlockBuffer::new(),
+        }
+    }
 
-    let mut hash = INITIAL_HASH_256;
+    fn update(&mut self, data: &[u8]) {
+        let hash = &mut self.hash;
+        self.buffer.update(data, |chunk| process_big_chunk(hash, *chunk));
+    }
 
-    for chunk in chunks {
-        let chunk = <[u8; 64]>::try_from(chunk).unwrap();
-        process_chunk(&mut hash, chunk);
+    fn finalize(&mut self) -> [u64; 8] {
+        let message_length = self.buffer.message_length();
+        let (last_chunk, extra_chunk) = sha2_padding_big(self.buffer.remainder(), message_length);
+
+        process_big_chunk(&mut self.hash, last_chunk);
+        if let Some(extra_chunk) = extra_chunk {
+            process_big_chunk(&mut self.hash, extra_chunk);
+        }
+        self.hash
     }
-    process_chunk(&mut hash, last_chunk);
-    if let Some(extra_chunk) = extra_chunk {
-        process_chunk(&mut hash, extra_chunk);
+}
+
+impl Drop for BigState {
+    fn drop(&mut self) {
+        self.hash.zeroize();
     }
+}
 
-    hash.into()
+macro_rules! sha2_hasher {
+    (
+        $(#[$doc:meta])*
+        $name:ident,
+        $state:ident,
+        $initial_hash:expr,
+        $output:ty,
+        $output_size:literal,
+        $block_size:literal,
+        $algorithm:literal,
+        $oid_suffix:literal
+    ) => {
+        $(#[$doc])*
+        #[derive(Clone)]
+        pub struct $name {
+            state: $state,
+        }
+
+        impl $name {
+            pub fn new() -> Self {
+                Self {
+                    state: $state::new($initial_hash),
+                }
+            }
+
+            pub fn update(&mut self, data: &[u8]) {
+                self.state.update(data);
+            }
+
+            pub fn finalize(mut self) -> $output {
+                let hash = self.state.finalize();
+                // the output is a prefix of the words, which may end in the middle of a word
+                let mut output = [0_u8; $output_size];
+                for (chunk, word) in output.chunks_mut(size_of_val(&hash[0])).zip(hash) {
+                    chunk.copy_from_slice(&word.to_be_bytes()[..chunk.len()]);
+                }
+                output.into()
+            }
+
+            pub fn reset(&mut self) {
+                *self = Self::new();
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!($name)).finish_non_exhaustive()
+            }
+        }
+
+        impl Digest for $name {
+            type Output = $output;
+
+            const OUTPUT_SIZE: usize = $output_size;
+            const BLOCK_SIZE: usize = $block_size;
+            const NAME: &'static str = $algorithm;
+            // 2.16.840.1.101.3.4.2 followed by the number of the hash function
+            const OID: Option<&'static [u8]> =
+                Some(&[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, $oid_suffix]);
+
+            fn new() -> Self {
+                Self::new()
+            }
+
+            fn update(&mut self, data: &[u8]) {
+                self.update(data);
+            }
+
+            fn finalize(self) -> Self::Output {
+                self.finalize()
+            }
+        }
+    };
 }
 
-pub fn sha384(message: &[u8]) -> Hash384 {
-    let chunks = message.chunks_exact(128);
-    let remaining_bytes = chunks.remainder();
-    let (last_chunk, extra_chunk) = sha2_padding_big(remaining_bytes, message.len() as u128);
+sha2_hasher!(
+    Sha224,
+    State,
+    INITIAL_HASH_224,
+    Hash224,
+    28,
+    64,
+    "SHA-224",
+    4
+);
+
+sha2_hasher!(
+    Sha256,
+    State,
+    INITIAL_HASH_256,
+    Hash256,
+    32,
+    64,
+    "SHA-256",
+    1
+);
+
+sha2_hasher!(
+    Sha384,
+    BigState,
+    INITIAL_HASH_384,
+    Hash384,
+    48,
+    128,
+    "SHA-384",
+    2
+);
+
+sha2_hasher!(
+    Sha512,
+    BigState,
+    INITIAL_HASH_512,
+    Hash512,
+    64,
+    128,
+    "SHA-512",
+    3
+);
+
+sha2_hasher!(
+    /// SHA-512/224, which is SHA-512 with a different initial hash, truncated to 224 bits. It is
+    /// faster than SHA-224 on 64-bit machines.
+    Sha512_224,
+    BigState,
+    INITIAL_HASH_512_224,
+    Hash224,
+    28,
+    128,
+    "SHA-512/224",
+    5
+);
+
+sha2_hasher!(
+    /// SHA-512/256, which is SHA-512 with a different initial hash, truncated to 256 bits. It is
+    /// faster than SHA-256 on 64-bit machines.
+    Sha512_256,
+    BigState,
+    INITIAL_HASH_512_256,
+    Hash256,
+    32,
+    128,
+    "SHA-512/256",
+    6
+);
+
+/// SHA-512/t for any output length `t` in bits below 512 other than 384, which is SHA-512 with
+/// an initial hash derived from `t`, truncated to `t` bits.
+#[derive(Clone)]
+pub struct Sha512T {
+    state: BigState,
+    t: u32,
+}
+
+impl Sha512T {
+    /// Panics if `t` is 0, 384 or not less than 512.
+    pub fn new(t: u32) -> Self {
+        Self::try_new(t).unwrap_or_else(|| panic!("invalid SHA-512/t output length: {t}"))
+    }
 
-    let mut hash = INITIAL_HASH_384;
+    /// Returns `None` if `t` is 0, 384 or not less than 512.
+    pub fn try_new(t: u32) -> Option<Self> {
+        Some(Self {
+            state: BigState::new(sha512_t_initial_hash(t)?),
+            t,
+        })
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.state.update(data);
+    }
 
-    for chunk in chunks {
-        let chunk = <[u8; 128]>::try_from(chunk).unwrap();
-        process_big_chunk(&mut hash, chunk);
+    /// Returns the first `t` bits of the hash. If `t` is not a multiple of 8, the unused low
+    /// bits of the last byte are zero.
+    pub fn finalize(mut self) -> Vec<u8> {
+        let hash = self.state.finalize();
+        let mut output = hash
+            .iter()
+            .flat_map(|word| word.to_be_bytes())
+            .take(self.t.div_ceil(8) as usize)
+            .collect::<Vec<_>>();
+        if !self.t.is_multiple_of(8) {
+            *output.last_mut().unwrap() &= 0xff << (8 - self.t % 8);
+        }
+        output
     }
-    process_big_chunk(&mut hash, last_chunk);
-    if let Some(extra_chunk) = extra_chunk {
-        process_big_chunk(&mut hash, extra_chunk);
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.t);
+    }
+}
+
+impl std::fmt::Debug for Sha512T {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sha512T")
+            .field("t", &self.t)
+            .finish_non_exhaustive()
     }
+}
 
-    <[u64; 6]>::try_from(&hash[..6]).unwrap().into()
+pub fn sha224(message: &[u8]) -> Hash224 {
+    let mut hasher = Sha224::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+pub fn sha256(message: &[u8]) -> Hash256 {
+    let mut hasher = Sha256::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+pub fn sha384(message: &[u8]) -> Hash384 {
+    let mut hasher = Sha384::new();
+    hasher.update(message);
+    hasher.finalize()
 }
 
 pub fn sha512(message: &[u8]) -> Hash512 {
-    let chunks = message.chunks_exact(128);
-    let remaining_bytes = chunks.remainder();
-    let (last_chunk, extra_chunk) = sha2_padding_big(remaining_bytes, message.len() as u128);
+    let mut hasher = Sha512::new();
+    hasher.update(message);
+    hasher.finalize()
+}
 
-    let mut hash = INITIAL_HASH_512;
+pub fn sha512_224(message: &[u8]) -> Hash224 {
+    let mut hasher = Sha512_224::new();
+    hasher.update(message);
+    hasher.finalize()
+}
 
-    for chunk in chunks {
-        let chunk = <[u8; 128]>::try_from(chunk).unwrap();
-        process_big_chunk(&mut hash, chunk);
-    }
-    process_big_chunk(&mut hash, last_chunk);
-    if let Some(extra_chunk) = extra_chunk {
-        process_big_chunk(&mut hash, extra_chunk);
+pub fn sha512_256(message: &[u8]) -> Hash256 {
+    let mut hasher = Sha512_256::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+pub fn sha512_t(t: u32, message: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha512T::new(t);
+    hasher.update(message);
+    hasher.finalize()
+}
+
+/// The initial hash of SHA-512/t (FIPS 180-4, section 5.3.6), which is the SHA-512 hash of the
+/// string "SHA-512/t", computed with a modified initial hash. `t` is the output length in bits,
+/// and lengths of 0, 384 and 512 or more give `None`.
+pub fn sha512_t_initial_hash(t: u32) -> Option<[u64; 8]> {
+    if t == 0 || t >= 512 || t == 384 {
+        return None;
     }
 
-    hash.into()
+    let mut state = BigState::new(INITIAL_HASH_512.map(|h| h ^ 0xa5a5a5a5a5a5a5a5));
+    state.update(format!("SHA-512/{t}").as_bytes());
+    Some(state.finalize())
 }
 
 pub fn sha2_padding(src: &[u8], message_length: u64) -> (Block<64>, Option<Block<64>>) {
@@ -166,19 +430,7 @@ pub fn sha2_padding(src: &[u8], message_length: u64) -> (Block<64>, Option<Block
 }
 
 pub fn sha2_padding_big(src: &[u8], message_length: u128) -> (Block<128>, Option<Block<128>>) {
-    let mut last = [0; 128];
-    let mut extra = [0; 128];
-
-    last[..src.len()].copy_from_slice(src);
-    last[src.len()] = 0x80;
-
-    if src.len() < 128 - 16 {
-        last[(128 - 16)..].copy_from_slice(&(message_length * 8).to_be_bytes());
-        (last, None)
-    } else {
-        extra[(128 - 16)..].copy_from_slice(&(message_length * 8).to_be_bytes());
-        (last, Some(extra))
-    }
+    md_padding(src, &(message_length * 8).to_be_bytes())
 }
 
 fn process_chunk(hash: &mut [u32; 8], chunk: [u8; 64]) {
@@ -286,3 +538,14 @@ fn process_big_chunk(hash: &mut [u64; 8], chunk: [u8; 128]) {
     hash[6] = hash[6].wrapping_add(g);
     hash[7] = hash[7].wrapping_add(h);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "invalid SHA-512/t output length: 384")]
+    fn sha384_length_is_rejected() {
+        Sha512T::new(384);
+    }
+}
@@ -0,0 +1,189 @@
+use crate::sha::buffer::BlockBuffer;
+use crate::zeroize::Zeroize;
+
+#[rustfmt::skip]
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808a, 0x8000000080008000,
+    0x000000000000808b, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008a, 0x0000000000000088, 0x0000000080008009, 0x000000008000000a,
+    0x000000008000808b, 0x800000000000008b, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800a, 0x800000008000000a,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+];
+
+/// The rotation of each lane in the rho step, in the order in which the pi step visits them.
+const ROTATIONS: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+/// The lanes in the order of the cycle that the pi step moves them along, starting after lane 1.
+const PI_LANES: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// The Keccak-f[1600] permutation (FIPS 202, section 3). Lane (x, y) of the state is `state[x + 5 * y]`.
+pub fn keccak_f1600(state: &mut [u64; 25]) {
+    for rc in ROUND_CONSTANTS {
+        // theta
+        let c: [u64; 5] = std::array::from_fn(|x| {
+            state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20]
+        });
+        for x in 0..5 {
+            let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                state[x + 5 * y] ^= d;
+            }
+        }
+
+        // rho and pi
+        let mut moving = state[1];
+        for (&lane, &rotation) in PI_LANES.iter().zip(&ROTATIONS) {
+            let next = state[lane];
+            state[lane] = moving.rotate_left(rotation);
+            moving = next;
+        }
+
+        // chi
+        for row in state.chunks_exact_mut(5) {
+            let lanes: [u64; 5] = row.try_into().unwrap();
+            for (x, lane) in row.iter_mut().enumerate() {
+                *lane = lanes[x] ^ (!lanes[(x + 1) % 5] & lanes[(x + 2) % 5]);
+            }
+        }
+
+        // iota
+        state[0] ^= rc;
+    }
+}
+
+/// A Keccak sponge with a rate of `RATE` bytes. The capacity is the rest of the 200 byte state.
+#[derive(Clone)]
+pub(crate) struct Sponge<const RATE: usize> {
+    state: [u64; 25],
+    buffer: BlockBuffer<RATE>,
+}
+
+impl<const RATE: usize> Sponge<RATE> {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: [0; 25],
+            buffer: BlockBuffer::new(),
+        }
+    }
+
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        let state = &mut self.state;
+        self.buffer.update(data, |block| absorb(state, block));
+    }
+
+    /// Absorbs zero bytes up to the end of the current block, if it has been started.
+    pub(crate) fn pad_to_block(&mut self) {
+        let len = self.buffer.remainder().len();
+        if len > 0 {
+            self.update(&[0; RATE][len..]);
+        }
+    }
+
+    /// Pads the message with the domain separation bits in `suffix`, which end with the first
+    /// bit of the padding, and switches to squeezing.
+    pub(crate) fn finalize(mut self, suffix: u8) -> XofReader<RATE> {
+        let mut block = [0_u8; RATE];
+        let remainder = self.buffer.remainder();
+        block[..remainder.len()].copy_from_slice(remainder);
+        block[remainder.len()] ^= suffix;
+        block[RATE - 1] ^= 0x80;
+        absorb(&mut self.state, &block);
+        block.zeroize();
+
+        XofReader {
+            state: self.state,
+            position: 0,
+        }
+    }
+}
+
+impl<const RATE: usize> Drop for Sponge<RATE> {
+    fn drop(&mut self) {
+        self.state.zeroize();
+    }
+}
+
+fn absorb<const RATE: usize>(state: &mut [u64; 25], block: &[u8; RATE]) {
+    for (lane, word) in state.iter_mut().zip(block.chunks_exact(8)) {
+        *lane ^= u64::from_le_bytes(word.try_into().unwrap());
+    }
+    keccak_f1600(state);
+}
+
+pub struct XofReader<const RATE: usize> {
+    state: [u64; 25],
+    /// The number of bytes of the rate that have been read since the last permutation.
+    position: usize,
+}
+
+impl<const RATE: usize> XofReader<RATE> {
+    pub fn read(&mut self, output: &mut [u8]) {
+        for b in output {
+            if self.position == RATE {
+                keccak_f1600(&mut self.state);
+                self.position = 0;
+            }
+            *b = (self.state[self.position / 8] >> (8 * (self.position % 8))) as u8;
+            self.position += 1;
+        }
+    }
+}
+
+impl<const RATE: usize> Drop for XofReader<RATE> {
+    fn drop(&mut self) {
+        self.state.zeroize();
+    }
+}
+
+impl<const RATE: usize> std::fmt::Debug for XofReader<RATE> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("XofReader").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permutation_of_zero_state() {
+        // the first lanes of the Keccak team's KeccakF-1600-IntermediateValues.txt
+        let mut state = [0; 25];
+        keccak_f1600(&mut state);
+        assert_eq!(
+            state[..3],
+            [0xf1258f7940e1dde7, 0x84d5ccf933c0478a, 0xd598261ea65aa9ee]
+        );
+        keccak_f1600(&mut state);
+        assert_eq!(
+            state[..3],
+            [0x2d5c954df96ecb3c, 0x6a332cd07057b56d, 0x093d8d1270d76b6c]
+        );
+    }
+
+    #[test]
+    fn padding_of_a_full_block_is_a_separate_block() {
+        let mut sponge = Sponge::<8>::new();
+        sponge.update(&[1; 8]);
+        let mut expected = [0; 25];
+        expected[0] = u64::from_le_bytes([1; 8]);
+        keccak_f1600(&mut expected);
+        expected[0] ^= 0x8000000000000006;
+        keccak_f1600(&mut expected);
+        assert_eq!(sponge.finalize(0x06).state, expected);
+    }
+
+    #[test]
+    fn state_is_zeroized_on_drop() {
+        let mut sponge = Sponge::<136>::new();
+        sponge.update(b"secret");
+        let mut reader = std::mem::ManuallyDrop::new(sponge.finalize(0x1f));
+        unsafe { std::mem::ManuallyDrop::drop(&mut reader) };
+        assert_eq!(reader.state, [0; 25]);
+    }
+}
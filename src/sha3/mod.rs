@@ -0,0 +1,252 @@
+mod keccak;
+mod sp800_185;
+
+pub use keccak::keccak_f1600;
+pub use keccak::XofReader;
+
+pub use sp800_185::{cshake128, cshake256, CShake128, CShake256};
+pub use sp800_185::{kmac128, kmac256, Kmac128, Kmac256};
+pub use sp800_185::{parallel_hash128, parallel_hash256, ParallelHash128, ParallelHash256};
+pub use sp800_185::{tuple_hash128, tuple_hash256, TupleHash128, TupleHash256};
+
+use crate::sha::{Hash224, Hash256, Hash384, Hash512};
+use crate::Digest;
+use keccak::Sponge;
+
+/// The suffix of SHA-3 messages, the bits 01 followed by the first bit of the padding.
+const SHA3_SUFFIX: u8 = 0x06;
+/// The suffix of SHAKE messages, the bits 1111 followed by the first bit of the padding.
+const SHAKE_SUFFIX: u8 = 0x1f;
+/// The original Keccak padding, which appends no domain separation bits.
+const KECCAK_SUFFIX: u8 = 0x01;
+
+/// The output of SHAKE128, which can be read in pieces of any length.
+pub type Shake128Reader = XofReader<168>;
+/// The output of SHAKE256, which can be read in pieces of any length.
+pub type Shake256Reader = XofReader<136>;
+
+macro_rules! keccak_hasher {
+    (
+        $(#[$doc:meta])*
+        $name:ident,
+        $suffix:expr,
+        $output:ty,
+        $output_size:literal,
+        $algorithm:literal,
+        $oid:expr
+    ) => {
+        $(#[$doc])*
+        #[derive(Clone)]
+        pub struct $name {
+            // the capacity is twice the output size
+            sponge: Sponge<{ 200 - 2 * $output_size }>,
+        }
+
+        impl $name {
+            pub fn new() -> Self {
+                Self {
+                    sponge: Sponge::new(),
+                }
+            }
+
+            pub fn update(&mut self, data: &[u8]) {
+                self.sponge.update(data);
+            }
+
+            pub fn finalize(self) -> $output {
+                let mut output = [0_u8; $output_size];
+                self.sponge.finalize($suffix).read(&mut output);
+                output.into()
+            }
+
+            pub fn reset(&mut self) {
+                *self = Self::new();
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!($name)).finish_non_exhaustive()
+            }
+        }
+
+        impl Digest for $name {
+            type Output = $output;
+
+            const OUTPUT_SIZE: usize = $output_size;
+            const BLOCK_SIZE: usize = 200 - 2 * $output_size;
+            const NAME: &'static str = $algorithm;
+            const OID: Option<&'static [u8]> = $oid;
+
+            fn new() -> Self {
+                Self::new()
+            }
+
+            fn update(&mut self, data: &[u8]) {
+                self.update(data);
+            }
+
+            fn finalize(self) -> Self::Output {
+                self.finalize()
+            }
+        }
+    };
+}
+
+/// 2.16.840.1.101.3.4.2 followed by the number of the hash function, as for SHA-2.
+macro_rules! nist_hash_oid {
+    ($suffix:literal) => {
+        Some(&[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, $suffix])
+    };
+}
+
+keccak_hasher!(
+    Sha3_224, SHA3_SUFFIX, Hash224, 28, "SHA3-224", nist_hash_oid!(0x07)
+);
+keccak_hasher!(
+    Sha3_256, SHA3_SUFFIX, Hash256, 32, "SHA3-256", nist_hash_oid!(0x08)
+);
+keccak_hasher!(
+    Sha3_384, SHA3_SUFFIX, Hash384, 48, "SHA3-384", nist_hash_oid!(0x09)
+);
+keccak_hasher!(
+    Sha3_512, SHA3_SUFFIX, Hash512, 64, "SHA3-512", nist_hash_oid!(0x0a)
+);
+keccak_hasher!(
+    /// Keccak-224 with the padding of the original submission.
+    Keccak224, KECCAK_SUFFIX, Hash224, 28, "Keccak-224", None
+);
+keccak_hasher!(
+    /// Keccak-256 with the padding of the original submission. This is the hash function
+    /// that Ethereum calls `keccak256`, which differs from SHA3-256.
+    Keccak256, KECCAK_SUFFIX, Hash256, 32, "Keccak-256", None
+);
+keccak_hasher!(
+    /// Keccak-384 with the padding of the original submission.
+    Keccak384, KECCAK_SUFFIX, Hash384, 48, "Keccak-384", None
+);
+keccak_hasher!(
+    /// Keccak-512 with the padding of the original submission.
+    Keccak512, KECCAK_SUFFIX, Hash512, 64, "Keccak-512", None
+);
+
+macro_rules! shake {
+    ($(#[$doc:meta])* $name:ident, $rate:literal) => {
+        $(#[$doc])*
+        #[derive(Clone)]
+        pub struct $name {
+            sponge: Sponge<$rate>,
+        }
+
+        impl $name {
+            pub fn new() -> Self {
+                Self {
+                    sponge: Sponge::new(),
+                }
+            }
+
+            pub fn update(&mut self, data: &[u8]) {
+                self.sponge.update(data);
+            }
+
+            pub fn finalize(self) -> XofReader<$rate> {
+                self.sponge.finalize(SHAKE_SUFFIX)
+            }
+
+            pub fn reset(&mut self) {
+                *self = Self::new();
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!($name)).finish_non_exhaustive()
+            }
+        }
+    };
+}
+
+shake!(
+    /// SHAKE128, an extendable-output function with 128-bit security. The output is read from
+    /// the reader returned by `finalize`.
+    Shake128,
+    168
+);
+shake!(
+    /// SHAKE256, an extendable-output function with 256-bit security. The output is read from
+    /// the reader returned by `finalize`.
+    Shake256,
+    136
+);
+
+pub fn sha3_224(message: &[u8]) -> Hash224 {
+    let mut hasher = Sha3_224::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+pub fn sha3_256(message: &[u8]) -> Hash256 {
+    let mut hasher = Sha3_256::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+pub fn sha3_384(message: &[u8]) -> Hash384 {
+    let mut hasher = Sha3_384::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+pub fn sha3_512(message: &[u8]) -> Hash512 {
+    let mut hasher = Sha3_512::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+pub fn keccak224(message: &[u8]) -> Hash224 {
+    let mut hasher = Keccak224::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+pub fn keccak256(message: &[u8]) -> Hash256 {
+    let mut hasher = Keccak256::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+pub fn keccak384(message: &[u8]) -> Hash384 {
+    let mut hasher = Keccak384::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+pub fn keccak512(message: &[u8]) -> Hash512 {
+    let mut hasher = Keccak512::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+pub fn shake128(message: &[u8], output: &mut [u8]) {
+    let mut xof = Shake128::new();
+    xof.update(message);
+    xof.finalize().read(output);
+}
+
+pub fn shake256(message: &[u8], output: &mut [u8]) {
+    let mut xof = Shake256::new();
+    xof.update(message);
+    xof.finalize().read(output);
+}
@@ -0,0 +1,331 @@
+//! The functions derived from SHA-3 in SP 800-185. All of them are built on cSHAKE, which
+//! separates domains by prefixing the input with a function name and a customization string.
+
+use crate::ct;
+use crate::zeroize::Zeroize;
+
+use super::keccak::{Sponge, XofReader};
+use super::SHAKE_SUFFIX;
+
+/// The suffix of cSHAKE messages, the bits 00 followed by the first bit of the padding.
+const CSHAKE_SUFFIX: u8 = 0x04;
+
+/// Absorbs `x` as its big-endian bytes without leading zeros, preceded by their number.
+fn left_encode<const RATE: usize>(sponge: &mut Sponge<RATE>, x: u64) {
+    let bytes = x.to_be_bytes();
+    let skip = (x.leading_zeros() as usize / 8).min(7);
+    sponge.update(&[8 - skip as u8]);
+    sponge.update(&bytes[skip..]);
+}
+
+/// Absorbs `x` as its big-endian bytes without leading zeros, followed by their number.
+fn right_encode<const RATE: usize>(sponge: &mut Sponge<RATE>, x: u64) {
+    let bytes = x.to_be_bytes();
+    let skip = (x.leading_zeros() as usize / 8).min(7);
+    sponge.update(&bytes[skip..]);
+    sponge.update(&[8 - skip as u8]);
+}
+
+/// Absorbs `s` preceded by its length in bits, so that the end of `s` is unambiguous.
+fn encode_string<const RATE: usize>(sponge: &mut Sponge<RATE>, s: &[u8]) {
+    left_encode(sponge, 8 * s.len() as u64);
+    sponge.update(s);
+}
+
+/// Starts a cSHAKE sponge, which is plain SHAKE if both strings are empty.
+fn cshake<const RATE: usize>(function_name: &[u8], customization: &[u8]) -> (Sponge<RATE>, u8) {
+    let mut sponge = Sponge::new();
+    if function_name.is_empty() && customization.is_empty() {
+        return (sponge, SHAKE_SUFFIX);
+    }
+
+    // bytepad(encode_string(N) || encode_string(S), rate)
+    left_encode(&mut sponge, RATE as u64);
+    encode_string(&mut sponge, function_name);
+    encode_string(&mut sponge, customization);
+    sponge.pad_to_block();
+    (sponge, CSHAKE_SUFFIX)
+}
+
+macro_rules! sp800_185 {
+    (
+        $rate:literal,
+        $cshake:ident,
+        $kmac:ident,
+        $tuple_hash:ident,
+        $parallel_hash:ident,
+        $cshake_fn:ident,
+        $kmac_fn:ident,
+        $tuple_hash_fn:ident,
+        $parallel_hash_fn:ident,
+        $security:literal
+    ) => {
+        #[doc = concat!("cSHAKE", $security, ", SHAKE", $security, " customized with a function name")]
+        /// and a customization string. The function name is reserved for functions defined by NIST
+        /// and is usually empty. The output is read from the reader returned by `finalize`.
+        #[derive(Clone)]
+        pub struct $cshake {
+            sponge: Sponge<$rate>,
+            suffix: u8,
+        }
+
+        impl $cshake {
+            pub fn new(function_name: &[u8], customization: &[u8]) -> Self {
+                let (sponge, suffix) = cshake(function_name, customization);
+                Self { sponge, suffix }
+            }
+
+            pub fn update(&mut self, data: &[u8]) {
+                self.sponge.update(data);
+            }
+
+            pub fn finalize(self) -> XofReader<$rate> {
+                self.sponge.finalize(self.suffix)
+            }
+        }
+
+        impl std::fmt::Debug for $cshake {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!($cshake)).finish_non_exhaustive()
+            }
+        }
+
+        #[doc = concat!("KMAC", $security, ", a MAC with a key of any length. The output length")]
+        /// is part of the input, so tags of different lengths are unrelated, unless the output is
+        /// read from `finalize_xof`.
+        #[derive(Clone)]
+        pub struct $kmac {
+            cshake: $cshake,
+        }
+
+        impl $kmac {
+            /// The shortest tag that `verify` accepts, 80 bits like for HMAC.
+            pub const MIN_TAG_LEN: usize = 10;
+
+            pub fn new(key: &[u8], customization: &[u8]) -> Self {
+                let mut cshake = $cshake::new(b"KMAC", customization);
+                // bytepad(encode_string(K), rate)
+                left_encode(&mut cshake.sponge, $rate);
+                encode_string(&mut cshake.sponge, key);
+                cshake.sponge.pad_to_block();
+                Self { cshake }
+            }
+
+            pub fn update(&mut self, data: &[u8]) {
+                self.cshake.update(data);
+            }
+
+            /// Fills `output` with the tag, whose length is `output.len()`.
+            pub fn finalize(mut self, output: &mut [u8]) {
+                right_encode(&mut self.cshake.sponge, 8 * output.len() as u64);
+                self.cshake.finalize().read(output);
+            }
+
+            #[doc = concat!("Switches to KMACXOF", $security, ", whose output can be read in pieces")]
+            /// of any length.
+            pub fn finalize_xof(mut self) -> XofReader<$rate> {
+                right_encode(&mut self.cshake.sponge, 0);
+                self.cshake.finalize()
+            }
+
+            /// Compares the tag of length `tag.len()` with `tag` in constant time. Tags shorter
+            /// than `MIN_TAG_LEN` are never valid.
+            pub fn verify(self, tag: &[u8]) -> bool {
+                if tag.len() < Self::MIN_TAG_LEN {
+                    return false;
+                }
+                let mut expected = vec![0; tag.len()];
+                self.finalize(&mut expected);
+                let result = ct::bytes_eq(&expected, tag);
+                expected.zeroize();
+                result
+            }
+        }
+
+        impl std::fmt::Debug for $kmac {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!($kmac)).finish_non_exhaustive()
+            }
+        }
+
+        #[doc = concat!("TupleHash", $security, ", which hashes a sequence of strings such that")]
+        /// different sequences with the same concatenation have different hashes.
+        #[derive(Clone)]
+        pub struct $tuple_hash {
+            cshake: $cshake,
+        }
+
+        impl $tuple_hash {
+            pub fn new(customization: &[u8]) -> Self {
+                Self {
+                    cshake: $cshake::new(b"TupleHash", customization),
+                }
+            }
+
+            /// Appends the next string of the tuple, which has to be passed in one piece.
+            pub fn push(&mut self, item: &[u8]) {
+                encode_string(&mut self.cshake.sponge, item);
+            }
+
+            /// Fills `output` with the hash, whose length is `output.len()`.
+            pub fn finalize(mut self, output: &mut [u8]) {
+                right_encode(&mut self.cshake.sponge, 8 * output.len() as u64);
+                self.cshake.finalize().read(output);
+            }
+        }
+
+        impl std::fmt::Debug for $tuple_hash {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!($tuple_hash)).finish_non_exhaustive()
+            }
+        }
+
+        #[doc = concat!("ParallelHash", $security, ", which splits the message into blocks of a")]
+        /// fixed size and hashes the concatenated hashes of the blocks. The blocks are
+        /// independent of each other, which makes the hashing of large inputs parallelizable.
+        #[derive(Clone)]
+        pub struct $parallel_hash {
+            cshake: $cshake,
+            block: $cshake,
+            block_size: usize,
+            /// The number of bytes of the current block that have been hashed.
+            block_len: usize,
+            blocks: u64,
+        }
+
+        impl $parallel_hash {
+            /// Starts a hash over blocks of `block_size` bytes, which must be positive.
+            pub fn new(block_size: usize, customization: &[u8]) -> Self {
+                assert!(block_size > 0, "ParallelHash block size must be positive");
+                let mut cshake = $cshake::new(b"ParallelHash", customization);
+                left_encode(&mut cshake.sponge, block_size as u64);
+                Self {
+                    cshake,
+                    block: $cshake::new(b"", b""),
+                    block_size,
+                    block_len: 0,
+                    blocks: 0,
+                }
+            }
+
+            pub fn update(&mut self, mut data: &[u8]) {
+                while !data.is_empty() {
+                    let n = data.len().min(self.block_size - self.block_len);
+                    self.block.update(&data[..n]);
+                    self.block_len += n;
+                    data = &data[n..];
+                    if self.block_len == self.block_size {
+                        self.finish_block();
+                    }
+                }
+            }
+
+            /// Fills `output` with the hash, whose length is `output.len()`.
+            pub fn finalize(mut self, output: &mut [u8]) {
+                if self.block_len > 0 {
+                    self.finish_block();
+                }
+                right_encode(&mut self.cshake.sponge, self.blocks);
+                right_encode(&mut self.cshake.sponge, 8 * output.len() as u64);
+                self.cshake.finalize().read(output);
+            }
+
+            fn finish_block(&mut self) {
+                let block = std::mem::replace(&mut self.block, $cshake::new(b"", b""));
+                // the hash of a block is as long as the capacity
+                let mut hash = [0_u8; 200 - $rate];
+                block.finalize().read(&mut hash);
+                self.cshake.update(&hash);
+                self.block_len = 0;
+                self.blocks += 1;
+            }
+        }
+
+        impl std::fmt::Debug for $parallel_hash {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!($parallel_hash)).finish_non_exhaustive()
+            }
+        }
+
+        pub fn $cshake_fn(
+            message: &[u8],
+            function_name: &[u8],
+            customization: &[u8],
+            output: &mut [u8],
+        ) {
+            let mut xof = $cshake::new(function_name, customization);
+            xof.update(message);
+            xof.finalize().read(output);
+        }
+
+        pub fn $kmac_fn(key: &[u8], message: &[u8], customization: &[u8], output: &mut [u8]) {
+            let mut mac = $kmac::new(key, customization);
+            mac.update(message);
+            mac.finalize(output);
+        }
+
+        pub fn $tuple_hash_fn(tuple: &[&[u8]], customization: &[u8], output: &mut [u8]) {
+            let mut hasher = $tuple_hash::new(customization);
+            for item in tuple {
+                hasher.push(item);
+            }
+            hasher.finalize(output);
+        }
+
+        pub fn $parallel_hash_fn(
+            message: &[u8],
+            block_size: usize,
+            customization: &[u8],
+            output: &mut [u8],
+        ) {
+            let mut hasher = $parallel_hash::new(block_size, customization);
+            hasher.update(message);
+            hasher.finalize(output);
+        }
+    };
+}
+
+sp800_185!(
+    168,
+    CShake128,
+    Kmac128,
+    TupleHash128,
+    ParallelHash128,
+    cshake128,
+    kmac128,
+    tuple_hash128,
+    parallel_hash128,
+    "128"
+);
+sp800_185!(
+    136,
+    CShake256,
+    Kmac256,
+    TupleHash256,
+    ParallelHash256,
+    cshake256,
+    kmac256,
+    tuple_hash256,
+    parallel_hash256,
+    "256"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_strings_give_shake() {
+        let mut a = [0; 32];
+        let mut b = [0; 32];
+        cshake128(b"abc", b"", b"", &mut a);
+        super::super::shake128(b"abc", &mut b);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    #[should_panic(expected = "block size must be positive")]
+    fn zero_block_size_panics() {
+        ParallelHash128::new(0, b"");
+    }
+}
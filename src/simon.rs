@@ -0,0 +1,185 @@
+use crate::key::{decode_base64, decode_hex, KeyError};
+use crate::speck::{expected_key_lengths, WordSize};
+use crate::zeroize::Zeroize;
+use crate::{Block, BlockCipher};
+
+const MAX_ROUNDS: usize = 72;
+
+/// The five constant sequences z0 to z4 of period 62, with the first element in the lowest bit.
+const Z: [u64; 5] = [
+    reverse_sequence(0b11111010001001010110000111001101111101000100101011000011100110),
+    reverse_sequence(0b10001110111110010011000010110101000111011111001001100001011010),
+    reverse_sequence(0b10101111011100000011010010011000101000010001111110010110110011),
+    reverse_sequence(0b11011011101011000110010111100000010010001010011100110100001111),
+    reverse_sequence(0b11010001111001101011011000100000010111000011001010010011101111),
+];
+
+/// The sequences are written with the first element to the left.
+const fn reverse_sequence(z: u64) -> u64 {
+    z.reverse_bits() >> 2
+}
+
+/// The number of rounds and the constant sequence for a block size and a number of key words, as
+/// specified by Beaulieu et al.
+const fn parameters(block_bytes: usize, key_words: usize) -> Option<(usize, usize)> {
+    match (block_bytes, key_words) {
+        (4, 4) => Some((32, 0)),
+        (6, 3) => Some((36, 0)),
+        (6, 4) => Some((36, 1)),
+        (8, 3) => Some((42, 2)),
+        (8, 4) => Some((44, 3)),
+        (12, 2) => Some((52, 2)),
+        (12, 3) => Some((54, 3)),
+        (16, 2) => Some((68, 2)),
+        (16, 3) => Some((69, 3)),
+        (16, 4) => Some((72, 4)),
+        _ => None,
+    }
+}
+
+/// The Simon family of lightweight Feistel block ciphers by the NSA, for blocks of `N` bytes (4,
+/// 6, 8, 12 or 16) and two to four key words depending on the block size. For example,
+/// Simon64/128 is `Simon<8>` with a 16 byte key.
+pub struct Simon<const N: usize> {
+    keys: [u64; MAX_ROUNDS],
+    rounds: usize,
+}
+
+impl<const N: usize> Simon<N> {
+    pub fn new(key: &[u8]) -> Self {
+        Self::try_from(key).expect("invalid Simon key")
+    }
+
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+
+    fn word_size() -> WordSize {
+        WordSize::for_block_size(N)
+    }
+
+    fn expand_key(key: &[u8], rounds: usize, z: u64) -> Self {
+        let ws = Self::word_size();
+        let m = key.len() / ws.bytes();
+
+        let mut keys = [0_u64; MAX_ROUNDS];
+        for (k, w) in keys.iter_mut().zip(key.chunks_exact(ws.bytes())) {
+            *k = ws.read(w);
+        }
+        for i in m..rounds {
+            let mut tmp = ws.rotate_right(keys[i - 1], 3);
+            if m == 4 {
+                tmp ^= keys[i - 3];
+            }
+            tmp ^= ws.rotate_right(tmp, 1);
+            keys[i] = ws.not(keys[i - m]) ^ tmp ^ ((z >> ((i - m) % 62)) & 1) ^ 3;
+        }
+
+        Self { keys, rounds }
+    }
+
+    fn f(x: u64) -> u64 {
+        let ws = Self::word_size();
+        (ws.rotate_left(x, 1) & ws.rotate_left(x, 8)) ^ ws.rotate_left(x, 2)
+    }
+}
+
+/// Accepts the key lengths of the block size, which are listed in the error message.
+impl<const N: usize> TryFrom<&[u8]> for Simon<N> {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        if !matches!(N, 4 | 6 | 8 | 12 | 16) {
+            return Err(KeyError::InvalidParameter("unsupported Simon block size"));
+        }
+
+        let word_bytes = N / 2;
+        let parameters = match key.len() % word_bytes {
+            0 => parameters(N, key.len() / word_bytes),
+            _ => None,
+        };
+        match parameters {
+            Some((rounds, z)) => Ok(Self::expand_key(key, rounds, Z[z])),
+            None => Err(KeyError::InvalidLength {
+                expected: expected_key_lengths(N),
+                actual: key.len(),
+            }),
+        }
+    }
+}
+
+impl<const N: usize> Drop for Simon<N> {
+    fn drop(&mut self) {
+        self.keys.zeroize();
+    }
+}
+
+impl<const N: usize> std::fmt::Debug for Simon<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Simon")
+            .field("word_size", &Self::word_size().bits)
+            .field("rounds", &self.rounds)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<const N: usize> BlockCipher<N> for Simon<N> {
+    fn encrypt(&self, plaintext: Block<N>) -> Block<N> {
+        let ws = Self::word_size();
+        let (mut x, mut y) = ws.read_block(&plaintext);
+        for &k in &self.keys[..self.rounds] {
+            (x, y) = (y ^ Self::f(x) ^ k, x);
+        }
+        ws.write_block((x, y))
+    }
+
+    fn decrypt(&self, ciphertext: Block<N>) -> Block<N> {
+        let ws = Self::word_size();
+        let (mut x, mut y) = ws.read_block(&ciphertext);
+        for &k in self.keys[..self.rounds].iter().rev() {
+            (x, y) = (y, x ^ Self::f(y) ^ k);
+        }
+        ws.write_block((x, y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constant_sequences_start_with_first_element() {
+        // z0 starts with 1111101000...
+        assert_eq!(Z[0] & 0x3ff, 0b0001011111);
+        assert_eq!(Z[0] >> 62, 0);
+    }
+
+    #[test]
+    fn key_length_is_validated() {
+        assert!(matches!(
+            Simon::<16>::try_from(&[0; 20][..]),
+            Err(KeyError::InvalidLength { expected: "16, 24 or 32 bytes", actual: 20 })
+        ));
+        assert!(Simon::<4>::try_from(&[0; 8][..]).is_ok());
+        assert!(Simon::<2>::try_from(&[0; 8][..]).is_err());
+    }
+
+    #[test]
+    fn subkeys_are_zeroized_on_drop() {
+        let mut cipher = std::mem::ManuallyDrop::new(Simon::<16>::new(&[1; 32]));
+        unsafe { std::mem::ManuallyDrop::drop(&mut cipher) };
+        assert_eq!(cipher.keys, [0; MAX_ROUNDS]);
+    }
+
+    #[test]
+    fn debug_output_is_redacted() {
+        assert_eq!(
+            format!("{:?}", Simon::<12>::new(&[0; 18])),
+            "Simon { word_size: 48, rounds: 54, .. }"
+        );
+    }
+}
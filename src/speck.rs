@@ -0,0 +1,265 @@
+use crate::key::{decode_base64, decode_hex, KeyError};
+use crate::zeroize::Zeroize;
+use crate::{Block, BlockCipher};
+
+const MAX_ROUNDS: usize = 34;
+
+/// The number of rounds for a block size and a number of key words, as specified by Beaulieu et
+/// al. Every block size supports a few key sizes.
+const fn rounds(block_bytes: usize, key_words: usize) -> Option<usize> {
+    match (block_bytes, key_words) {
+        (4, 4) => Some(22),
+        (6, 3) => Some(22),
+        (6, 4) => Some(23),
+        (8, 3) => Some(26),
+        (8, 4) => Some(27),
+        (12, 2) => Some(28),
+        (12, 3) => Some(29),
+        (16, 2) => Some(32),
+        (16, 3) => Some(33),
+        (16, 4) => Some(34),
+        _ => None,
+    }
+}
+
+/// The valid key lengths for a block size, in the form used by `KeyError`.
+pub(crate) const fn expected_key_lengths(block_bytes: usize) -> &'static str {
+    match block_bytes {
+        4 => "8 bytes",
+        6 => "9 or 12 bytes",
+        8 => "12 or 16 bytes",
+        12 => "12 or 18 bytes",
+        _ => "16, 24 or 32 bytes",
+    }
+}
+
+/// Arithmetic on words of 16, 24, 32, 48 or 64 bits, stored in the low bits of a `u64`.
+#[derive(Clone, Copy)]
+pub(crate) struct WordSize {
+    pub(crate) bits: u32,
+}
+
+impl WordSize {
+    pub(crate) fn for_block_size(block_bytes: usize) -> Self {
+        Self {
+            bits: 4 * block_bytes as u32,
+        }
+    }
+
+    pub(crate) fn bytes(self) -> usize {
+        self.bits as usize / 8
+    }
+
+    fn mask(self) -> u64 {
+        u64::MAX >> (64 - self.bits)
+    }
+
+    pub(crate) fn rotate_left(self, x: u64, n: u32) -> u64 {
+        ((x << n) | (x >> (self.bits - n))) & self.mask()
+    }
+
+    pub(crate) fn rotate_right(self, x: u64, n: u32) -> u64 {
+        ((x >> n) | (x << (self.bits - n))) & self.mask()
+    }
+
+    fn add(self, x: u64, y: u64) -> u64 {
+        x.wrapping_add(y) & self.mask()
+    }
+
+    fn sub(self, x: u64, y: u64) -> u64 {
+        x.wrapping_sub(y) & self.mask()
+    }
+
+    pub(crate) fn not(self, x: u64) -> u64 {
+        !x & self.mask()
+    }
+
+    /// Words are stored in little-endian byte order, as in the implementation guide.
+    pub(crate) fn read(self, bytes: &[u8]) -> u64 {
+        bytes.iter().rev().fold(0, |w, &b| (w << 8) | b as u64)
+    }
+
+    pub(crate) fn write(self, w: u64, bytes: &mut [u8]) {
+        for (i, b) in bytes.iter_mut().enumerate() {
+            *b = (w >> (8 * i)) as u8;
+        }
+    }
+
+    /// Splits a block into the words (x, y). The block starts with y, as in the implementation
+    /// guide.
+    pub(crate) fn read_block(self, block: &[u8]) -> (u64, u64) {
+        let (y, x) = block.split_at(self.bytes());
+        (self.read(x), self.read(y))
+    }
+
+    pub(crate) fn write_block<const N: usize>(self, (x, y): (u64, u64)) -> Block<N> {
+        let mut block = [0_u8; N];
+        let (y_bytes, x_bytes) = block.split_at_mut(self.bytes());
+        self.write(x, x_bytes);
+        self.write(y, y_bytes);
+        block
+    }
+}
+
+/// The Speck family of ARX block ciphers by the NSA, for blocks of `N` bytes (4, 6, 8, 12 or 16)
+/// and two to four key words depending on the block size. For example, Speck64/128 is
+/// `Speck<8>` with a 16 byte key.
+pub struct Speck<const N: usize> {
+    keys: [u64; MAX_ROUNDS],
+    rounds: usize,
+}
+
+impl<const N: usize> Speck<N> {
+    pub fn new(key: &[u8]) -> Self {
+        Self::try_from(key).expect("invalid Speck key")
+    }
+
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+
+    fn word_size() -> WordSize {
+        WordSize::for_block_size(N)
+    }
+
+    /// The rotation amounts alpha and beta.
+    fn rotations() -> (u32, u32) {
+        if N == 4 { (7, 2) } else { (8, 3) }
+    }
+
+    fn expand_key(key: &[u8], rounds: usize) -> Self {
+        let ws = Self::word_size();
+        let (alpha, beta) = Self::rotations();
+
+        let mut words = key.chunks_exact(ws.bytes()).map(|w| ws.read(w));
+        let mut k = words.next().unwrap();
+        // reserved up front, so that no copies of the key words are left in freed memory
+        let mut l = Vec::with_capacity(rounds + 3);
+        l.extend(words);
+
+        let mut keys = [0_u64; MAX_ROUNDS];
+        for (i, key) in keys[..rounds].iter_mut().enumerate() {
+            *key = k;
+            // the key schedule is the round function with the round number as the key
+            let new_l = ws.add(k, ws.rotate_right(l[i], alpha)) ^ i as u64;
+            k = ws.rotate_left(k, beta) ^ new_l;
+            l.push(new_l);
+        }
+
+        k.zeroize();
+        l.zeroize();
+        Self { keys, rounds }
+    }
+}
+
+/// Accepts the key lengths of the block size, which are listed in the error message.
+impl<const N: usize> TryFrom<&[u8]> for Speck<N> {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        if !matches!(N, 4 | 6 | 8 | 12 | 16) {
+            return Err(KeyError::InvalidParameter("unsupported Speck block size"));
+        }
+
+        let word_bytes = N / 2;
+        let rounds = match key.len() % word_bytes {
+            0 => rounds(N, key.len() / word_bytes),
+            _ => None,
+        };
+        match rounds {
+            Some(rounds) => Ok(Self::expand_key(key, rounds)),
+            None => Err(KeyError::InvalidLength {
+                expected: expected_key_lengths(N),
+                actual: key.len(),
+            }),
+        }
+    }
+}
+
+impl<const N: usize> Drop for Speck<N> {
+    fn drop(&mut self) {
+        self.keys.zeroize();
+    }
+}
+
+impl<const N: usize> std::fmt::Debug for Speck<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Speck")
+            .field("word_size", &Self::word_size().bits)
+            .field("rounds", &self.rounds)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<const N: usize> BlockCipher<N> for Speck<N> {
+    fn encrypt(&self, plaintext: Block<N>) -> Block<N> {
+        let ws = Self::word_size();
+        let (alpha, beta) = Self::rotations();
+
+        let (mut x, mut y) = ws.read_block(&plaintext);
+        for &k in &self.keys[..self.rounds] {
+            x = ws.add(ws.rotate_right(x, alpha), y) ^ k;
+            y = ws.rotate_left(y, beta) ^ x;
+        }
+        ws.write_block((x, y))
+    }
+
+    fn decrypt(&self, ciphertext: Block<N>) -> Block<N> {
+        let ws = Self::word_size();
+        let (alpha, beta) = Self::rotations();
+
+        let (mut x, mut y) = ws.read_block(&ciphertext);
+        for &k in self.keys[..self.rounds].iter().rev() {
+            y = ws.rotate_right(y ^ x, beta);
+            x = ws.rotate_left(ws.sub(x ^ k, y), alpha);
+        }
+        ws.write_block((x, y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_length_is_validated() {
+        assert!(matches!(
+            Speck::<8>::try_from(&[0; 8][..]),
+            Err(KeyError::InvalidLength { expected: "12 or 16 bytes", actual: 8 })
+        ));
+        assert!(Speck::<6>::try_from(&[0; 10][..]).is_err());
+        assert!(Speck::<6>::try_from(&[0; 9][..]).is_ok());
+        assert_eq!(
+            Speck::<5>::try_from(&[0; 10][..]).unwrap_err(),
+            KeyError::InvalidParameter("unsupported Speck block size")
+        );
+    }
+
+    #[test]
+    fn words_are_masked() {
+        let ws = WordSize::for_block_size(6);
+        assert_eq!(ws.rotate_left(0x800001, 1), 0x000003);
+        assert_eq!(ws.rotate_right(0x000003, 1), 0x800001);
+        assert_eq!(ws.add(0xffffff, 2), 1);
+        assert_eq!(ws.sub(0, 1), 0xffffff);
+    }
+
+    #[test]
+    fn subkeys_are_zeroized_on_drop() {
+        let mut cipher = std::mem::ManuallyDrop::new(Speck::<16>::new(&[1; 32]));
+        unsafe { std::mem::ManuallyDrop::drop(&mut cipher) };
+        assert_eq!(cipher.keys, [0; MAX_ROUNDS]);
+    }
+
+    #[test]
+    fn debug_output_is_redacted() {
+        assert_eq!(
+            format!("{:?}", Speck::<8>::new(&[0; 16])),
+            "Speck { word_size: 32, rounds: 27, .. }"
+        );
+    }
+}
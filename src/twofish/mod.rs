@@ -0,0 +1,361 @@
+use crate::key::{check_length, decode_base64, decode_hex, KeyError};
+use crate::zeroize::Zeroize;
+use crate::{Block, BlockCipher};
+
+#[rustfmt::skip]
+const Q0_TABLES: [[u8; 16]; 4] = [
+    [0x8, 0x1, 0x7, 0xd, 0x6, 0xf, 0x3, 0x2, 0x0, 0xb, 0x5, 0x9, 0xe, 0xc, 0xa, 0x4],
+    [0xe, 0xc, 0xb, 0x8, 0x1, 0x2, 0x3, 0x5, 0xf, 0x4, 0xa, 0x6, 0x7, 0x0, 0x9, 0xd],
+    [0xb, 0xa, 0x5, 0xe, 0x6, 0xd, 0x9, 0x0, 0xc, 0x8, 0xf, 0x3, 0x2, 0x4, 0x7, 0x1],
+    [0xd, 0x7, 0xf, 0x4, 0x1, 0x2, 0x6, 0xe, 0x9, 0xb, 0x3, 0x0, 0x8, 0x5, 0xc, 0xa],
+];
+
+#[rustfmt::skip]
+const Q1_TABLES: [[u8; 16]; 4] = [
+    [0x2, 0x8, 0xb, 0xd, 0xf, 0x7, 0x6, 0xe, 0x3, 0x1, 0x9, 0x4, 0x0, 0xa, 0xc, 0x5],
+    [0x1, 0xe, 0x2, 0xb, 0x4, 0xc, 0x3, 0x7, 0x6, 0xd, 0xa, 0x5, 0xf, 0x9, 0x0, 0x8],
+    [0x4, 0xc, 0x7, 0x5, 0x1, 0x6, 0x9, 0xa, 0x0, 0xe, 0xd, 0x8, 0x2, 0xb, 0x3, 0xf],
+    [0xb, 0x9, 0x5, 0x1, 0xc, 0x3, 0xd, 0xe, 0x6, 0x4, 0x7, 0xf, 0x2, 0x0, 0x8, 0xa],
+];
+
+const Q0: [u8; 256] = calculate_q(Q0_TABLES);
+const Q1: [u8; 256] = calculate_q(Q1_TABLES);
+
+const MDS_POLYNOMIAL: u16 = 0x169; // x^8 + x^6 + x^5 + x^3 + 1
+const RS_POLYNOMIAL: u16 = 0x14d; // x^8 + x^6 + x^3 + x^2 + 1
+
+#[rustfmt::skip]
+const MDS: [[u8; 4]; 4] = [
+    [0x01, 0xef, 0x5b, 0x5b],
+    [0x5b, 0xef, 0xef, 0x01],
+    [0xef, 0x5b, 0x01, 0xef],
+    [0xef, 0x01, 0xef, 0x5b],
+];
+
+#[rustfmt::skip]
+const RS: [[u8; 8]; 4] = [
+    [0x01, 0xa4, 0x55, 0x87, 0x5a, 0x58, 0xdb, 0x9e],
+    [0xa4, 0x56, 0x82, 0xf3, 0x1e, 0xc6, 0x68, 0xe5],
+    [0x02, 0xa1, 0xfc, 0xc1, 0x47, 0xae, 0x3d, 0x19],
+    [0xa4, 0x55, 0x87, 0x5a, 0x58, 0xdb, 0x9e, 0x03],
+];
+
+const fn calculate_q(t: [[u8; 16]; 4]) -> [u8; 256] {
+    const fn ror4(x: u8) -> u8 {
+        ((x >> 1) | (x << 3)) & 0xf
+    }
+
+    let mut q = [0; 256];
+    let mut x = 0;
+    while x < 256 {
+        let a0 = (x >> 4) as u8;
+        let b0 = (x & 0xf) as u8;
+        let a1 = a0 ^ b0;
+        let b1 = a0 ^ ror4(b0) ^ ((a0 << 3) & 0xf);
+        let a2 = t[0][a1 as usize];
+        let b2 = t[1][b1 as usize];
+        let a3 = a2 ^ b2;
+        let b3 = a2 ^ ror4(b2) ^ ((a2 << 3) & 0xf);
+        let a4 = t[2][a3 as usize];
+        let b4 = t[3][b3 as usize];
+        q[x] = (b4 << 4) | a4;
+        x += 1;
+    }
+    q
+}
+
+const fn gf_mul(mut a: u8, mut b: u8, polynomial: u16) -> u8 {
+    let mut r = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            r ^= a;
+        }
+        let hi = a & 0x80 != 0;
+        a <<= 1;
+        if hi {
+            a ^= (polynomial & 0xff) as u8;
+        }
+        b >>= 1;
+    }
+    r
+}
+
+/// The byte substitutions of the h function, keyed by the list `l` of 2, 3 or 4 words.
+fn substitute(y: [u8; 4], l: &[[u8; 4]]) -> [u8; 4] {
+    let [mut y0, mut y1, mut y2, mut y3] = y;
+    if l.len() == 4 {
+        y0 = Q1[y0 as usize] ^ l[3][0];
+        y1 = Q0[y1 as usize] ^ l[3][1];
+        y2 = Q0[y2 as usize] ^ l[3][2];
+        y3 = Q1[y3 as usize] ^ l[3][3];
+    }
+    if l.len() >= 3 {
+        y0 = Q1[y0 as usize] ^ l[2][0];
+        y1 = Q1[y1 as usize] ^ l[2][1];
+        y2 = Q0[y2 as usize] ^ l[2][2];
+        y3 = Q0[y3 as usize] ^ l[2][3];
+    }
+    [
+        Q1[(Q0[(Q0[y0 as usize] ^ l[1][0]) as usize] ^ l[0][0]) as usize],
+        Q0[(Q0[(Q1[y1 as usize] ^ l[1][1]) as usize] ^ l[0][1]) as usize],
+        Q1[(Q1[(Q0[y2 as usize] ^ l[1][2]) as usize] ^ l[0][2]) as usize],
+        Q0[(Q1[(Q1[y3 as usize] ^ l[1][3]) as usize] ^ l[0][3]) as usize],
+    ]
+}
+
+fn mds_multiply(y: [u8; 4]) -> u32 {
+    let mut z = [0_u8; 4];
+    for (i, row) in MDS.iter().enumerate() {
+        for (j, &m) in row.iter().enumerate() {
+            z[i] ^= gf_mul(m, y[j], MDS_POLYNOMIAL);
+        }
+    }
+    u32::from_le_bytes(z)
+}
+
+fn h(x: u32, l: &[[u8; 4]]) -> u32 {
+    mds_multiply(substitute(x.to_le_bytes(), l))
+}
+
+fn rs_multiply(m: &[u8]) -> [u8; 4] {
+    let mut s = [0_u8; 4];
+    for (i, row) in RS.iter().enumerate() {
+        for (j, &r) in row.iter().enumerate() {
+            s[i] ^= gf_mul(r, m[j], RS_POLYNOMIAL);
+        }
+    }
+    s
+}
+
+pub struct Twofish {
+    k: [u32; 40], // whitening and round subkeys
+    s: [[u32; 256]; 4], // the key-dependent S-boxes combined with the MDS matrix
+}
+
+impl Twofish {
+    pub fn new(key: &[u8]) -> Self {
+        Self::try_from(key).expect("invalid Twofish key")
+    }
+
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+
+    fn expand_key(key: &[u8]) -> Self {
+        // shorter keys are padded with zeros to the next supported length
+        let n = match key.len() {
+            0..=16 => 16,
+            17..=24 => 24,
+            _ => 32,
+        };
+        let mut padded = [0_u8; 32];
+        padded[..key.len()].copy_from_slice(key);
+        let m = &padded[..n];
+
+        let words: Vec<[u8; 4]> = m
+            .chunks_exact(4)
+            .map(|w| <[u8; 4]>::try_from(w).unwrap())
+            .collect();
+        let mut me: Vec<[u8; 4]> = words.iter().step_by(2).copied().collect();
+        let mut mo: Vec<[u8; 4]> = words.iter().skip(1).step_by(2).copied().collect();
+        let mut s: Vec<[u8; 4]> = m.chunks_exact(8).rev().map(rs_multiply).collect();
+
+        let mut k = [0_u32; 40];
+        for i in 0..20 {
+            let rho = 0x01010101_u32;
+            let a = h((2 * i as u32).wrapping_mul(rho), &me);
+            let b = h((2 * i as u32 + 1).wrapping_mul(rho), &mo).rotate_left(8);
+            k[2 * i] = a.wrapping_add(b);
+            k[2 * i + 1] = a.wrapping_add(b.wrapping_mul(2)).rotate_left(9);
+        }
+
+        let mut sboxes = [[0_u32; 256]; 4];
+        for x in 0..256 {
+            let y = substitute([x as u8; 4], &s);
+            for (j, sbox) in sboxes.iter_mut().enumerate() {
+                let mut column = [0; 4];
+                column[j] = y[j];
+                sbox[x] = mds_multiply(column);
+            }
+        }
+
+        padded.zeroize();
+        for w in me.iter_mut().chain(mo.iter_mut()).chain(s.iter_mut()) {
+            w.zeroize();
+        }
+
+        Self { k, s: sboxes }
+    }
+
+    fn g(&self, x: u32) -> u32 {
+        let [b0, b1, b2, b3] = x.to_le_bytes();
+        self.s[0][b0 as usize] ^ self.s[1][b1 as usize] ^ self.s[2][b2 as usize] ^ self.s[3][b3 as usize]
+    }
+
+    fn f(&self, r0: u32, r1: u32, round: usize) -> (u32, u32) {
+        let t0 = self.g(r0);
+        let t1 = self.g(r1.rotate_left(8));
+        let f0 = t0.wrapping_add(t1).wrapping_add(self.k[2 * round + 8]);
+        let f1 = t0
+            .wrapping_add(t1.wrapping_mul(2))
+            .wrapping_add(self.k[2 * round + 9]);
+        (f0, f1)
+    }
+
+    fn encrypt_words(&self, p: [u32; 4]) -> [u32; 4] {
+        let mut r = [0_u32; 4];
+        for i in 0..4 {
+            r[i] = p[i] ^ self.k[i];
+        }
+
+        for round in 0..16 {
+            let (f0, f1) = self.f(r[0], r[1], round);
+            let r2 = (r[2] ^ f0).rotate_right(1);
+            let r3 = r[3].rotate_left(1) ^ f1;
+            r = [r2, r3, r[0], r[1]];
+        }
+
+        // the swap of the last round is undone here
+        [
+            r[2] ^ self.k[4],
+            r[3] ^ self.k[5],
+            r[0] ^ self.k[6],
+            r[1] ^ self.k[7],
+        ]
+    }
+
+    fn decrypt_words(&self, c: [u32; 4]) -> [u32; 4] {
+        let mut r = [
+            c[2] ^ self.k[6],
+            c[3] ^ self.k[7],
+            c[0] ^ self.k[4],
+            c[1] ^ self.k[5],
+        ];
+
+        for round in (0..16).rev() {
+            // r = [r2', r3', r0, r1]
+            let (f0, f1) = self.f(r[2], r[3], round);
+            let r2 = r[0].rotate_left(1) ^ f0;
+            let r3 = (r[1] ^ f1).rotate_right(1);
+            r = [r[2], r[3], r2, r3];
+        }
+
+        let mut p = [0_u32; 4];
+        for i in 0..4 {
+            p[i] = r[i] ^ self.k[i];
+        }
+        p
+    }
+}
+
+/// Accepts keys up to 256 bits. Keys that are not 128, 192 or 256 bits long are padded with zeros.
+impl TryFrom<&[u8]> for Twofish {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        check_length(key, 1..=32, "1 to 32 bytes")?;
+        Ok(Self::expand_key(key))
+    }
+}
+
+impl Drop for Twofish {
+    fn drop(&mut self) {
+        self.k.zeroize();
+        self.s.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Twofish {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Twofish").finish_non_exhaustive()
+    }
+}
+
+impl BlockCipher<16> for Twofish {
+    fn encrypt(&self, plaintext: Block<16>) -> Block<16> {
+        let mut words = [0_u32; 4];
+        for (i, w) in words.iter_mut().enumerate() {
+            *w = u32::from_le_bytes(<[u8; 4]>::try_from(&plaintext[(4 * i)..][..4]).unwrap());
+        }
+
+        let words = self.encrypt_words(words);
+
+        let mut res = [0_u8; 16];
+        for (i, w) in words.iter().enumerate() {
+            res[(4 * i)..][..4].copy_from_slice(&w.to_le_bytes());
+        }
+        res
+    }
+
+    fn decrypt(&self, ciphertext: Block<16>) -> Block<16> {
+        let mut words = [0_u32; 4];
+        for (i, w) in words.iter_mut().enumerate() {
+            *w = u32::from_le_bytes(<[u8; 4]>::try_from(&ciphertext[(4 * i)..][..4]).unwrap());
+        }
+
+        let words = self.decrypt_words(words);
+
+        let mut res = [0_u8; 16];
+        for (i, w) in words.iter().enumerate() {
+            res[(4 * i)..][..4].copy_from_slice(&w.to_le_bytes());
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn q_permutations() {
+        assert_eq!(&Q0[..4], &[0xa9, 0x67, 0xb3, 0xe8]);
+        assert_eq!(&Q1[..4], &[0x75, 0xf3, 0xc6, 0xf4]);
+    }
+
+    #[test]
+    fn subkeys_for_zero_key() {
+        // from the intermediate values published with the Twofish specification
+        #[rustfmt::skip]
+        let expected: [u32; 40] = [
+            0x52C54DDE, 0x11F0626D, 0x7CAC9D4A, 0x4D1B4AAA, 0xB7B83A10, 0x1E7D0BEB, 0xEE9C341F,
+            0xCFE14BE4, 0xF98FFEF9, 0x9C5B3C17, 0x15A48310, 0x342A4D81, 0x424D89FE, 0xC14724A7,
+            0x311B834C, 0xFDE87320, 0x3302778F, 0x26CD67B4, 0x7A6C6362, 0xC2BAF60E, 0x3411B994,
+            0xD972C87F, 0x84ADB1EA, 0xA7DEE434, 0x54D2960F, 0xA2F7CAA8, 0xA6B8FF8C, 0x8014C425,
+            0x6A748D1C, 0xEDBAF720, 0x928EF78C, 0x0338EE13, 0x9949D6BE, 0xC8314176, 0x07C07D68,
+            0xECAE7EA7, 0x1FE71844, 0x85C05C89, 0xF298311E, 0x696EA672,
+        ];
+        assert_eq!(Twofish::new(&[0; 16]).k, expected);
+    }
+
+    #[test]
+    fn short_keys_are_padded_with_zeros() {
+        let plaintext = [7; 16];
+        assert_eq!(
+            Twofish::new(&[1, 2, 3]).encrypt(plaintext),
+            Twofish::new(&[1, 2, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]).encrypt(plaintext)
+        );
+        assert_eq!(
+            Twofish::new(&[1; 20]).encrypt(plaintext),
+            Twofish::new(&[[1; 20], [0; 20]].concat()[..24]).encrypt(plaintext)
+        );
+    }
+
+    #[test]
+    fn key_length_is_validated() {
+        assert!(Twofish::try_from(&[][..]).is_err());
+        assert!(Twofish::try_from(&[0; 33][..]).is_err());
+    }
+
+    #[test]
+    fn tables_are_zeroized_on_drop() {
+        let mut cipher = std::mem::ManuallyDrop::new(Twofish::new(&[1; 16]));
+        unsafe { std::mem::ManuallyDrop::drop(&mut cipher) };
+        assert_eq!(cipher.k, [0; 40]);
+        assert_eq!(cipher.s, [[0; 256]; 4]);
+    }
+}
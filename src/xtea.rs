@@ -0,0 +1,117 @@
+use crate::key::{decode_base64, decode_hex, fixed_length, KeyError};
+use crate::zeroize::Zeroize;
+use crate::{Block, BlockCipher};
+
+const CYCLES: u32 = 32;
+const DELTA: u32 = 0x9e3779b9;
+
+/// XTEA by Needham and Wheeler, with a 128-bit key and 32 cycles of two Feistel rounds each.
+/// Words are read in big-endian order, as in most implementations.
+pub struct Xtea {
+    key: [u32; 4],
+}
+
+impl Xtea {
+    pub fn new(key: &[u8; 16]) -> Self {
+        Self {
+            key: std::array::from_fn(|i| u32::from_be_bytes(key[(4 * i)..][..4].try_into().unwrap())),
+        }
+    }
+
+    pub fn from_hex(hex: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_hex(hex)?.as_slice())
+    }
+
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        Self::try_from(decode_base64(base64)?.as_slice())
+    }
+
+    fn f(&self, x: u32, sum: u32, k: u32) -> u32 {
+        (((x << 4) ^ (x >> 5)).wrapping_add(x)) ^ sum.wrapping_add(self.key[k as usize & 3])
+    }
+}
+
+impl TryFrom<&[u8]> for Xtea {
+    type Error = KeyError;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        let mut key: [u8; 16] = fixed_length(key, "16 bytes")?;
+        let cipher = Self::new(&key);
+        key.zeroize();
+        Ok(cipher)
+    }
+}
+
+impl Drop for Xtea {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Xtea {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Xtea").finish_non_exhaustive()
+    }
+}
+
+impl BlockCipher<8> for Xtea {
+    fn encrypt(&self, plaintext: Block<8>) -> Block<8> {
+        let mut v0 = u32::from_be_bytes(plaintext[..4].try_into().unwrap());
+        let mut v1 = u32::from_be_bytes(plaintext[4..].try_into().unwrap());
+
+        let mut sum = 0_u32;
+        for _ in 0..CYCLES {
+            v0 = v0.wrapping_add(self.f(v1, sum, sum));
+            sum = sum.wrapping_add(DELTA);
+            v1 = v1.wrapping_add(self.f(v0, sum, sum >> 11));
+        }
+
+        let mut res = [0_u8; 8];
+        res[..4].copy_from_slice(&v0.to_be_bytes());
+        res[4..].copy_from_slice(&v1.to_be_bytes());
+        res
+    }
+
+    fn decrypt(&self, ciphertext: Block<8>) -> Block<8> {
+        let mut v0 = u32::from_be_bytes(ciphertext[..4].try_into().unwrap());
+        let mut v1 = u32::from_be_bytes(ciphertext[4..].try_into().unwrap());
+
+        let mut sum = DELTA.wrapping_mul(CYCLES);
+        for _ in 0..CYCLES {
+            v1 = v1.wrapping_sub(self.f(v0, sum, sum >> 11));
+            sum = sum.wrapping_sub(DELTA);
+            v0 = v0.wrapping_sub(self.f(v1, sum, sum));
+        }
+
+        let mut res = [0_u8; 8];
+        res[..4].copy_from_slice(&v0.to_be_bytes());
+        res[4..].copy_from_slice(&v1.to_be_bytes());
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_length_is_validated() {
+        assert!(matches!(
+            Xtea::try_from(&[0; 8][..]),
+            Err(KeyError::InvalidLength { expected: "16 bytes", actual: 8 })
+        ));
+        assert!(Xtea::try_from(&[0; 16][..]).is_ok());
+    }
+
+    #[test]
+    fn key_is_zeroized_on_drop() {
+        let mut cipher = std::mem::ManuallyDrop::new(Xtea::new(&[1; 16]));
+        unsafe { std::mem::ManuallyDrop::drop(&mut cipher) };
+        assert_eq!(cipher.key, [0; 4]);
+    }
+
+    #[test]
+    fn debug_output_is_redacted() {
+        assert_eq!(format!("{:?}", Xtea::new(&[0; 16])), "Xtea { .. }");
+    }
+}
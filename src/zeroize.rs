@@ -0,0 +1,133 @@
+use std::{
+    mem::size_of,
+    ptr,
+    sync::atomic::{compiler_fence, Ordering},
+};
+
+/// Overwrites secret material with zeros in a way that the compiler is not allowed to optimize away.
+pub trait Zeroize {
+    fn zeroize(&mut self);
+}
+
+/// Volatile writes followed by a compiler fence, so that the writes are neither removed nor
+/// reordered past the point where the memory is freed.
+///
+/// # Safety
+///
+/// `dst` must be valid for writes of `len` bytes.
+unsafe fn zero_bytes(dst: *mut u8, len: usize) {
+    for i in 0..len {
+        unsafe { ptr::write_volatile(dst.add(i), 0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+impl Zeroize for u8 {
+    fn zeroize(&mut self) {
+        unsafe { zero_bytes(self as *mut u8, size_of::<u8>()) }
+    }
+}
+
+impl Zeroize for u16 {
+    fn zeroize(&mut self) {
+        unsafe { zero_bytes(self as *mut u16 as *mut u8, size_of::<u16>()) }
+    }
+}
+
+impl Zeroize for u32 {
+    fn zeroize(&mut self) {
+        unsafe { zero_bytes(self as *mut u32 as *mut u8, size_of::<u32>()) }
+    }
+}
+
+impl Zeroize for u64 {
+    fn zeroize(&mut self) {
+        unsafe { zero_bytes(self as *mut u64 as *mut u8, size_of::<u64>()) }
+    }
+}
+
+impl Zeroize for u128 {
+    fn zeroize(&mut self) {
+        unsafe { zero_bytes(self as *mut u128 as *mut u8, size_of::<u128>()) }
+    }
+}
+
+impl<T: Zeroize, const N: usize> Zeroize for [T; N] {
+    fn zeroize(&mut self) {
+        for v in self.iter_mut() {
+            v.zeroize();
+        }
+    }
+}
+
+impl<T: Zeroize> Zeroize for Vec<T> {
+    /// Zeroizes the elements and the spare capacity (which might contain old elements), and
+    /// then empties the vector.
+    fn zeroize(&mut self) {
+        for v in self.iter_mut() {
+            v.zeroize();
+        }
+        self.clear();
+
+        let num_bytes = self.capacity() * size_of::<T>();
+        unsafe { zero_bytes(self.as_mut_ptr() as *mut u8, num_bytes) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::ManuallyDrop;
+
+    use super::*;
+
+    struct Secret([u32; 4]);
+
+    impl Drop for Secret {
+        fn drop(&mut self) {
+            self.0.zeroize();
+        }
+    }
+
+    #[test]
+    fn zeroize_integers() {
+        let mut a = 0xab_u8;
+        let mut b = 0xabcd_u16;
+        let mut c = 0xabcdef01_u32;
+        let mut d = 0xabcdef0123456789_u64;
+        let mut e = u128::MAX;
+
+        a.zeroize();
+        b.zeroize();
+        c.zeroize();
+        d.zeroize();
+        e.zeroize();
+
+        assert_eq!((a, b, c, d, e), (0, 0, 0, 0, 0));
+    }
+
+    #[test]
+    fn zeroize_nested_arrays() {
+        let mut arr = [[1_u16, 2], [3, 4], [5, 6]];
+        arr.zeroize();
+        assert_eq!(arr, [[0; 2]; 3]);
+    }
+
+    #[test]
+    fn zeroize_vec_clears_spare_capacity() {
+        let mut v: Vec<u8> = vec![0xff; 32];
+        v.truncate(4); // the removed elements are still in the allocation
+        v.zeroize();
+
+        assert!(v.is_empty());
+        let memory = unsafe { std::slice::from_raw_parts(v.as_ptr(), v.capacity()) };
+        assert_eq!(memory, &[0; 32]);
+    }
+
+    #[test]
+    fn memory_is_zeroed_after_drop() {
+        let mut secret = ManuallyDrop::new(Secret([1, 2, 3, 4]));
+        unsafe { ManuallyDrop::drop(&mut secret) };
+
+        assert_eq!(secret.0, [0; 4]);
+    }
+}
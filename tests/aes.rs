@@ -242,3 +242,70 @@ mod decryption {
         assert_eq!(plaintext, "AAAAAAAAAAAAAAAA".as_bytes());
     }
 }
+
+mod keys {
+    use cryptography::aes::{Aes, Key128, Key192, Key256};
+    use cryptography::key::KeyError;
+    use cryptography::*;
+
+    #[test]
+    fn key_from_hex_matches_key_from_words() {
+        let from_hex = Key128::from_hex("12345678901234567890123456789012").unwrap();
+        let from_words = Key128::from([0x1234567890123456_u64, 0x7890123456789012_u64]);
+
+        let plaintext = [b'A'; 16];
+        assert_eq!(
+            Aes::from(from_hex).encrypt(plaintext),
+            Aes::from(from_words).encrypt(plaintext)
+        );
+    }
+
+    #[test]
+    fn key_from_base64() {
+        let key = Key256::from_base64("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=").unwrap();
+        let cipher = Aes::from(key);
+
+        assert_eq!(
+            cipher.encrypt([b'A'; 16]),
+            0x7e0e7577ef9c30a6bf0b25e0621e827e_u128.to_be_bytes()
+        );
+    }
+
+    #[test]
+    fn key_with_wrong_length_is_rejected() {
+        assert_eq!(
+            Key128::try_from(&[0; 15][..]).err(),
+            Some(KeyError::InvalidLength {
+                expected: "16 bytes",
+                actual: 15
+            })
+        );
+        assert!(Key192::from_hex("00").is_err());
+        assert!(matches!(Key256::from_hex("zz"), Err(KeyError::InvalidHex(_))));
+        assert!(matches!(Key128::from_base64("!"), Err(KeyError::InvalidBase64(_))));
+    }
+
+    #[test]
+    fn new_dispatches_on_key_length() {
+        let plaintext = [b'A'; 16];
+
+        let cipher = Aes::new(&[0; 16]).unwrap();
+        assert_eq!(cipher.encrypt(plaintext), 0xb49cbf19d357e6e1f6845c30fd5b63e3_u128.to_be_bytes());
+
+        let cipher = Aes::new(&[0; 24]).unwrap();
+        assert_eq!(cipher.encrypt(plaintext), 0x485e404701da678874724d32da51d124_u128.to_be_bytes());
+
+        let cipher = Aes::new(&[0; 32]).unwrap();
+        assert_eq!(cipher.encrypt(plaintext), 0x7e0e7577ef9c30a6bf0b25e0621e827e_u128.to_be_bytes());
+    }
+
+    #[test]
+    fn new_rejects_other_key_lengths() {
+        for len in [0, 8, 15, 17, 31, 33, 64] {
+            assert!(matches!(
+                Aes::new(&vec![0; len]),
+                Err(KeyError::InvalidLength { actual, .. }) if actual == len
+            ));
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use cryptography::aes::{Key128, Key192, Key256};
+use cryptography::aria::Aria;
+use cryptography::BlockCipher;
+
+#[track_caller]
+fn check(cipher: Aria, plaintext: &str, ciphertext: &str) {
+    let plaintext = hex::decode(plaintext).unwrap().try_into().unwrap();
+    let ciphertext = hex::decode(ciphertext).unwrap().try_into().unwrap();
+    assert_eq!(cipher.encrypt(plaintext), ciphertext);
+    assert_eq!(cipher.decrypt(ciphertext), plaintext);
+}
+
+#[test]
+fn rfc_5794_examples() {
+    // RFC 5794, appendix A
+    let plaintext = "00112233445566778899aabbccddeeff";
+    check(
+        Key128::from_hex("000102030405060708090a0b0c0d0e0f").unwrap().into(),
+        plaintext,
+        "d718fbd6ab644c739da95f3be6451778",
+    );
+    check(
+        Key192::from_hex("000102030405060708090a0b0c0d0e0f1011121314151617").unwrap().into(),
+        plaintext,
+        "26449c1805dbe7aa25a468ce263a9e79",
+    );
+    check(
+        Key256::from_hex("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f")
+            .unwrap()
+            .into(),
+        plaintext,
+        "f92bd7c79fb72e2f2b8f80c1972d24fc",
+    );
+}
+
+#[test]
+fn iterated_encryption() {
+    // the expected values come from OpenSSL
+    #[track_caller]
+    fn check_iterated(key: &str, expected: &str) {
+        let cipher = Aria::from_hex(key).unwrap();
+        let mut block = [0; 16];
+        for _ in 0..1000 {
+            block = cipher.encrypt(block);
+        }
+        assert_eq!(hex::encode(block), expected);
+        for _ in 0..1000 {
+            block = cipher.decrypt(block);
+        }
+        assert_eq!(block, [0; 16]);
+    }
+
+    check_iterated("00000000000000000000000000000000", "b85de5ccec55cb304ca42e4b043ee9a1");
+    check_iterated(
+        "000102030405060708090a0b0c0d0e0f1011121314151617",
+        "e060ab2137be5b308f7f0443079464b9",
+    );
+    check_iterated(
+        "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        "1382160c25781bf41c4e9f723a6f3272",
+    );
+}
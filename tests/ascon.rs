@@ -0,0 +1,324 @@
+use cryptography::aead::AuthenticationError;
+use cryptography::ascon::{
+    ascon_hash256, ascon_xof128, permute, Ascon128, Ascon128a, AsconAead128, AsconHash256,
+    AsconXof128,
+};
+use cryptography::sha::Hash256;
+use hex::FromHex;
+
+const COUNTING: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+// Entries of the KAT files of the reference implementation: LWC_AEAD_KAT_128_128.txt of each AEAD
+// variant, LWC_HASH_KAT_256.txt and LWC_XOF_KAT_128_512.txt. The key and nonce are 000102...0f,
+// and the message and associated data are 000102... of every length up to 32 bytes, so that the
+// entry for a `p` byte message and `a` bytes of associated data is Count = 33 * p + a + 1. The
+// hash files have one entry per message length, Count = length + 1.
+
+/// Checks the encryption of a `plaintext_len` byte message with `associated_data_len` bytes of
+/// associated data.
+#[track_caller]
+fn check_aead(
+    encrypt: impl Fn(&[u8; 16], &[u8], &[u8]) -> Vec<u8>,
+    plaintext_len: u8,
+    associated_data_len: u8,
+    ciphertext_hex: &str,
+) {
+    let plaintext = (0..plaintext_len).collect::<Vec<u8>>();
+    let associated_data = (0..associated_data_len).collect::<Vec<u8>>();
+    assert_eq!(
+        hex::encode(encrypt(&COUNTING, &associated_data, &plaintext)),
+        ciphertext_hex
+    );
+}
+
+#[test]
+fn ascon_aead128_examples() {
+    let aead = AsconAead128::new(&COUNTING);
+    // Count = 1
+    assert_eq!(
+        hex::encode(aead.encrypt(&COUNTING, b"", b"")),
+        "4427d64b8e1e1451fc445960f0839bb0"
+    );
+    assert_eq!(
+        aead.decrypt(
+            &COUNTING,
+            b"",
+            &hex::decode("4427d64b8e1e1451fc445960f0839bb0").unwrap()
+        ),
+        Ok(vec![])
+    );
+    // Count = 2
+    assert_eq!(
+        hex::encode(aead.encrypt(&COUNTING, &[0], b"")),
+        "103ab79d913a0321287715a979bb8585"
+    );
+
+    let encrypt = |nonce: &_, ad: &_, pt: &_| aead.encrypt(nonce, ad, pt);
+    // Count = 577
+    check_aead(
+        encrypt,
+        17,
+        15,
+        "b03e607317a251b08b30f744b71965b0f1d5e576f7118a4e67fd4246766e7e7428",
+    );
+    // Count = 1089
+    check_aead(
+        encrypt,
+        32,
+        32,
+        "4c086d27a3b51a2333cfc7f22172a9bcad88b8d4d77e50622d788345fa7bee44\
+         68915d3f9422289f2349d6a3b4160397",
+    );
+}
+
+#[test]
+fn ascon_128_examples() {
+    let aead = Ascon128::new(&COUNTING);
+    // Count = 1
+    assert_eq!(
+        hex::encode(aead.encrypt(&COUNTING, b"", b"")),
+        "e355159f292911f794cb1432a0103a8a"
+    );
+    // Count = 2
+    assert_eq!(
+        hex::encode(aead.encrypt(&COUNTING, &[0], b"")),
+        "944df887cd4901614c5dedbc42fc0da0"
+    );
+
+    let encrypt = |nonce: &_, ad: &_, pt: &_| aead.encrypt(nonce, ad, pt);
+    // Count = 305
+    check_aead(
+        encrypt,
+        9,
+        7,
+        "2e5bbade9599ac9f2d7ea5a5c832e8f41072bd3400fcad0d64",
+    );
+    // Count = 546
+    check_aead(
+        encrypt,
+        16,
+        17,
+        "8684539a9fcff9f68a7a496010f129b5a34081410d25fbbc68b9216046750ae6",
+    );
+}
+
+#[test]
+fn ascon_128a_examples() {
+    let aead = Ascon128a::new(&COUNTING);
+    // Count = 1
+    assert_eq!(
+        hex::encode(aead.encrypt(&COUNTING, b"", b"")),
+        "7a834e6f09210957067b10fd831f0078"
+    );
+
+    let encrypt = |nonce: &_, ad: &_, pt: &_| aead.encrypt(nonce, ad, pt);
+    // Count = 578
+    check_aead(
+        encrypt,
+        17,
+        16,
+        "52499ac9c84323a4ae24eaeccf45c1379be1fb14b90aa907a8475a667367cd277d",
+    );
+    // Count = 1056
+    check_aead(
+        encrypt,
+        31,
+        32,
+        "a55236ac020dbda74ce6ccd10c68c4d8514450a382bc87c68946d86a921dd8d5\
+         12c20775e27c66bebbfe27460bc467",
+    );
+}
+
+#[test]
+fn round_trips_of_every_length() {
+    // message and associated data lengths around the 8 and 16 byte rates
+    let data = (0..40).collect::<Vec<u8>>();
+    for len in 0..data.len() {
+        let (associated_data, message) = (&data[..(len / 2)], &data[..len]);
+
+        let aead = AsconAead128::new(&COUNTING);
+        let ciphertext = aead.encrypt(&COUNTING, associated_data, message);
+        assert_eq!(ciphertext.len(), len + 16);
+        assert_eq!(
+            aead.decrypt(&COUNTING, associated_data, &ciphertext)
+                .unwrap(),
+            message
+        );
+
+        let aead = Ascon128::new(&COUNTING);
+        let ciphertext = aead.encrypt(&COUNTING, associated_data, message);
+        assert_eq!(
+            aead.decrypt(&COUNTING, associated_data, &ciphertext)
+                .unwrap(),
+            message
+        );
+
+        let aead = Ascon128a::new(&COUNTING);
+        let ciphertext = aead.encrypt(&COUNTING, associated_data, message);
+        assert_eq!(
+            aead.decrypt(&COUNTING, associated_data, &ciphertext)
+                .unwrap(),
+            message
+        );
+    }
+}
+
+#[test]
+fn variants_are_not_compatible() {
+    let message = b"attack at dawn";
+    let ciphertext = Ascon128a::new(&COUNTING).encrypt(&COUNTING, b"", message);
+    assert_eq!(
+        AsconAead128::new(&COUNTING).decrypt(&COUNTING, b"", &ciphertext),
+        Err(AuthenticationError)
+    );
+}
+
+#[test]
+fn modifications_are_detected() {
+    let aead = AsconAead128::from_hex("2bd6459f82c5b300952c49104881ff48").unwrap();
+    let nonce = [7; 16];
+    let message = b"a message that spans a few blocks of the sponge";
+    let ciphertext = aead.encrypt(&nonce, b"header", message);
+
+    for i in 0..ciphertext.len() {
+        let mut modified = ciphertext.clone();
+        modified[i] ^= 1;
+        assert_eq!(
+            aead.decrypt(&nonce, b"header", &modified),
+            Err(AuthenticationError),
+            "byte {i}"
+        );
+    }
+    assert_eq!(
+        aead.decrypt(&nonce, b"Header", &ciphertext),
+        Err(AuthenticationError)
+    );
+    assert_eq!(
+        aead.decrypt(&[8; 16], b"header", &ciphertext),
+        Err(AuthenticationError)
+    );
+}
+
+#[test]
+fn failed_decryption_leaves_buffer_unchanged() {
+    let aead = Ascon128::new(&COUNTING);
+    let mut buffer = b"in place".to_vec();
+    let tag = aead.encrypt_in_place_detached(&COUNTING, b"", &mut buffer);
+    let ciphertext = buffer.clone();
+
+    let mut wrong_tag = tag;
+    wrong_tag[0] ^= 1;
+    assert_eq!(
+        aead.decrypt_in_place_detached(&COUNTING, b"", &mut buffer, &wrong_tag),
+        Err(AuthenticationError)
+    );
+    assert_eq!(buffer, ciphertext);
+
+    aead.decrypt_in_place_detached(&COUNTING, b"", &mut buffer, &tag)
+        .unwrap();
+    assert_eq!(buffer, b"in place");
+}
+
+#[test]
+fn ascon_hash256_examples() {
+    // Count = 1
+    assert_eq!(
+        ascon_hash256(b""),
+        Hash256::from_hex("0b3be5850f2f6b98caf29f8fdea89b64a1fa70aa249b8f839bd53baa304d92b2")
+            .unwrap()
+    );
+
+    // Count = 2, 9, 10 and 18: messages shorter than, one, just over one and just over two
+    // blocks
+    let message = (0..17).collect::<Vec<u8>>();
+    assert_eq!(
+        hex::encode(ascon_hash256(&message[..1])),
+        "0728621035af3ed2bca03bf6fde900f9456f5330e4b5ee23e7f6a1e70291bc80"
+    );
+    assert_eq!(
+        hex::encode(ascon_hash256(&message[..8])),
+        "b88e497ae8e6fb641b87ef622eb8f2fca0ed95383f7ffebe167acf1099ba764f"
+    );
+    assert_eq!(
+        hex::encode(ascon_hash256(&message[..9])),
+        "94269c30e0296e1ec86655041841823efa1927f520fd58c8e9bce6197878c1a6"
+    );
+    assert_eq!(
+        hex::encode(ascon_hash256(&message)),
+        "f149e99dd0f429599bb89b8079bf3f4dca3f298efefcf9b1ea16fe84f9b8b6e2"
+    );
+}
+
+#[test]
+fn ascon_xof128_examples() {
+    // Count = 1, 10 and 25
+    let mut output = [0; 64];
+    ascon_xof128(b"", &mut output);
+    assert_eq!(
+        hex::encode(output),
+        "473d5e6164f58b39dfd84aacdb8ae42ec2d91fed33388ee0d960d9b3993295c6\
+         ad77855a5d3b13fe6ad9e6098988373af7d0956d05a8f1665d2c67d1a3ad10ff"
+    );
+
+    let message = (0..24).collect::<Vec<u8>>();
+    ascon_xof128(&message[..9], &mut output);
+    assert_eq!(
+        hex::encode(output),
+        "db3013bfbbd132dc1d3152fd955ed48f7cbb675e9ad2a2fecf92b74c957592e0\
+         c89959e81c16fd07ead9eeb8e40359c497aa20258b43d87ec69ad0bb0993fd38"
+    );
+    ascon_xof128(&message, &mut output);
+    assert_eq!(
+        hex::encode(output),
+        "25ad36b2f1712f42a285e13f2fc2ce5a7938e399f02b8b4468106854e6feb94c\
+         fb465da80bf523c18126d337021473d0c7e553282346f323657db53eebab7bea"
+    );
+}
+
+#[test]
+fn incremental_hashing_matches_one_shot() {
+    let message = (0..100).collect::<Vec<u8>>();
+    for split in [0, 1, 7, 8, 9, 50, 100] {
+        let mut hasher = AsconHash256::new();
+        hasher.update(&message[..split]);
+        hasher.update(&message[split..]);
+        assert_eq!(
+            hasher.finalize(),
+            ascon_hash256(&message),
+            "split = {split}"
+        );
+    }
+}
+
+#[test]
+fn xof_output_can_be_read_in_pieces() {
+    let mut expected = [0; 50];
+    ascon_xof128(b"abc", &mut expected);
+
+    let mut xof = AsconXof128::new();
+    xof.update(b"abc");
+    let mut reader = xof.finalize();
+    let mut output = [0; 50];
+    let (first, rest) = output.split_at_mut(13);
+    reader.read(first);
+    reader.read(rest);
+    assert_eq!(output, expected);
+
+    // a shorter output is a prefix of a longer one
+    let mut short = [0; 20];
+    ascon_xof128(b"abc", &mut short);
+    assert_eq!(short, expected[..20]);
+}
+
+#[test]
+fn permutation_is_reusable() {
+    // the hash is a sponge over the public permutation
+    let mut state = [0x0000080100cc0002, 0, 0, 0, 0];
+    permute(&mut state, 12);
+    state[0] ^= 0x01;
+    permute(&mut state, 12);
+
+    let mut expected = [0; 8];
+    expected.copy_from_slice(&<[u8; 32]>::from(ascon_hash256(b""))[..8]);
+    assert_eq!(state[0].to_le_bytes(), expected);
+}
@@ -0,0 +1,231 @@
+use cryptography::blake2::{
+    blake2b, blake2s, Blake2b, Blake2b512, Blake2bParams, Blake2s, Blake2s256, Blake2sParams,
+};
+use cryptography::sha::{Hash256, Hash512, HashValue};
+use hex::FromHex;
+
+#[test]
+fn rfc_7693_examples() {
+    assert_eq!(
+        blake2b::<64>(b"abc"),
+        Hash512::from_hex("ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923")
+            .unwrap(),
+    );
+    assert_eq!(
+        blake2s::<32>(b"abc"),
+        Hash256::from_hex("508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982")
+            .unwrap(),
+    );
+}
+
+#[test]
+fn empty_string() {
+    assert_eq!(
+        blake2b::<64>(b""),
+        Hash512::from_hex("786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce")
+            .unwrap(),
+    );
+    assert_eq!(
+        blake2s::<32>(b""),
+        Hash256::from_hex("69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9")
+            .unwrap(),
+    );
+}
+
+/// The pseudorandom input of the self-test in appendix E of RFC 7693.
+fn selftest_seq(len: usize, seed: u32) -> Vec<u8> {
+    let mut a = 0xdead4bad_u32.wrapping_mul(seed);
+    let mut b = 1_u32;
+    (0..len)
+        .map(|_| {
+            let t = a.wrapping_add(b);
+            a = b;
+            b = t;
+            (t >> 24) as u8
+        })
+        .collect()
+}
+
+fn selftest_blake2b<const N: usize>(outer: &mut Blake2b<32>, input_lengths: &[usize]) {
+    for &len in input_lengths {
+        let input = selftest_seq(len, len as u32);
+        outer.update(blake2b::<N>(&input).as_ref());
+
+        let mut keyed = Blake2b::<N>::with_key(&selftest_seq(N, N as u32)).unwrap();
+        keyed.update(&input);
+        outer.update(keyed.finalize().as_ref());
+    }
+}
+
+fn selftest_blake2s<const N: usize>(outer: &mut Blake2s<32>, input_lengths: &[usize]) {
+    for &len in input_lengths {
+        let input = selftest_seq(len, len as u32);
+        outer.update(blake2s::<N>(&input).as_ref());
+
+        let mut keyed = Blake2s::<N>::with_key(&selftest_seq(N, N as u32)).unwrap();
+        keyed.update(&input);
+        outer.update(keyed.finalize().as_ref());
+    }
+}
+
+#[test]
+fn rfc_7693_selftest() {
+    let lengths = [0, 3, 128, 129, 255, 1024];
+    let mut outer = Blake2b::<32>::new();
+    selftest_blake2b::<20>(&mut outer, &lengths);
+    selftest_blake2b::<32>(&mut outer, &lengths);
+    selftest_blake2b::<48>(&mut outer, &lengths);
+    selftest_blake2b::<64>(&mut outer, &lengths);
+    assert_eq!(
+        outer.finalize(),
+        Hash256::from_hex("c23a7800d98123bd10f506c61e29da5603d763b8bbad2e737f5e765a7bccd475")
+            .unwrap(),
+    );
+
+    let lengths = [0, 3, 64, 65, 255, 1024];
+    let mut outer = Blake2s::<32>::new();
+    selftest_blake2s::<16>(&mut outer, &lengths);
+    selftest_blake2s::<20>(&mut outer, &lengths);
+    selftest_blake2s::<28>(&mut outer, &lengths);
+    selftest_blake2s::<32>(&mut outer, &lengths);
+    assert_eq!(
+        outer.finalize(),
+        Hash256::from_hex("6a411f08ce25adcdfb02aba641451cec53c598b24f4fc787fbdc88797f4c1dfe")
+            .unwrap(),
+    );
+}
+
+#[test]
+fn keyed_known_answers() {
+    // the last entries of blake2b-kat.txt and blake2s-kat.txt from the reference implementation
+    let message: Vec<u8> = (0..=254).collect();
+    let key: Vec<u8> = (0..64).collect();
+
+    let mut mac = Blake2b512::with_key(&key).unwrap();
+    mac.update(&message);
+    assert_eq!(
+        mac.finalize(),
+        Hash512::from_hex("142709d62e28fcccd0af97fad0f8465b971e82201dc51070faa0372aa43e92484be1c1e73ba10906d5d1853db6a4106e0a7bf9800d373d6dee2d46d62ef2a461")
+            .unwrap(),
+    );
+
+    let mut mac = Blake2s256::with_key(&key[..32]).unwrap();
+    mac.update(&message);
+    assert_eq!(
+        mac.finalize(),
+        Hash256::from_hex("3fb735061abc519dfe979e54c1ee5bfad0a9d858b3315bad34bde999efd724dd")
+            .unwrap(),
+    );
+
+    // with an empty message the key block is the last block
+    assert_eq!(
+        Blake2b512::with_key(&key).unwrap().finalize(),
+        Hash512::from_hex("10ebb67700b1868efb4417987acf4690ae9d972fb7a590c2f02871799aaa4786b5e996e8f0f4eb981fc214b005f42d2ff4233499391653df7aefcbc13fc51568")
+            .unwrap(),
+    );
+}
+
+#[test]
+fn salt_and_personalization() {
+    let params = Blake2bParams {
+        salt: *b"0123456789abcdef",
+        personalization: *b"personalization!",
+        ..Default::default()
+    };
+    let mut hasher = Blake2b::<32>::with_params(&[], &params).unwrap();
+    hasher.update(b"abc");
+    assert_eq!(
+        hasher.finalize(),
+        Hash256::from_hex("dbe8233deda041edda3554928d2d7ce65159a46dd653254596459eb42ce16a24")
+            .unwrap(),
+    );
+
+    let params = Blake2sParams {
+        salt: *b"saltsalt",
+        personalization: *b"persname",
+        ..Default::default()
+    };
+    let mut hasher = Blake2s::<16>::with_params(b"key", &params).unwrap();
+    hasher.update(b"abc");
+    assert_eq!(
+        hasher.finalize(),
+        HashValue::<16>::from_hex("62d28a41e12f4e01d7171e95fc00f801").unwrap(),
+    );
+}
+
+#[test]
+fn tree_parameters() {
+    let params = Blake2bParams {
+        fanout: 2,
+        max_depth: 2,
+        leaf_length: 4096,
+        node_offset: 1,
+        node_depth: 0,
+        inner_length: 32,
+        last_node: true,
+        ..Default::default()
+    };
+    let mut hasher = Blake2b::<32>::with_params(&[], &params).unwrap();
+    hasher.update(b"leaf data");
+    assert_eq!(
+        hasher.finalize(),
+        Hash256::from_hex("8b799da4e242e4b8b88392a0631f2bf81c6844ffed13e5bf81cbbde68ed960a4")
+            .unwrap(),
+    );
+
+    let params = Blake2sParams {
+        fanout: 2,
+        max_depth: 2,
+        leaf_length: 4096,
+        node_offset: (1 << 47) + 5,
+        node_depth: 1,
+        inner_length: 32,
+        last_node: true,
+        ..Default::default()
+    };
+    let mut hasher = Blake2s::<32>::with_params(&[], &params).unwrap();
+    hasher.update(b"leaf data");
+    assert_eq!(
+        hasher.finalize(),
+        Hash256::from_hex("a5757dfa10421ff26675bf7817e643392159a07d8a1606b6c4c9224ca30e1452")
+            .unwrap(),
+    );
+}
+
+#[test]
+fn streaming_matches_one_shot() {
+    for len in [0, 1, 63, 64, 65, 127, 128, 129, 256, 300] {
+        let message: Vec<u8> = (0..len).map(|i| i as u8).collect();
+        for split in [0, 1.min(len), len / 2, len] {
+            let mut hasher = Blake2b512::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(
+                hasher.finalize(),
+                blake2b(&message),
+                "{len} bytes split at {split}"
+            );
+
+            let mut hasher = Blake2s256::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(
+                hasher.finalize(),
+                blake2s(&message),
+                "{len} bytes split at {split}"
+            );
+        }
+    }
+}
+
+#[test]
+fn reset_keeps_the_key() {
+    let mut mac = Blake2s256::with_key(b"secret").unwrap();
+    mac.update(b"first message");
+    mac.reset();
+    mac.update(b"abc");
+
+    let mut fresh = Blake2s256::with_key(b"secret").unwrap();
+    fresh.update(b"abc");
+    assert_eq!(mac.finalize(), fresh.finalize());
+}
@@ -0,0 +1,58 @@
+use cryptography::blowfish::Blowfish;
+use cryptography::BlockCipher;
+
+#[track_caller]
+fn check(key: &str, plaintext: u64, ciphertext: u64) {
+    let cipher = Blowfish::from_hex(key).unwrap();
+    let plaintext = plaintext.to_be_bytes();
+    let ciphertext = ciphertext.to_be_bytes();
+    assert_eq!(cipher.encrypt(plaintext), ciphertext, "key = {key}");
+    assert_eq!(cipher.decrypt(ciphertext), plaintext, "key = {key}");
+}
+
+// Test vectors by Eric Young, published on Schneier's Blowfish page
+
+#[test]
+fn ecb_examples() {
+    check("0000000000000000", 0x0000000000000000, 0x4ef997456198dd78);
+    check("ffffffffffffffff", 0xffffffffffffffff, 0x51866fd5b85ecb8a);
+    check("3000000000000000", 0x1000000000000001, 0x7d856f9a613063f2);
+    check("1111111111111111", 0x1111111111111111, 0x2466dd878b963c9d);
+    check("0123456789abcdef", 0x1111111111111111, 0x61f9c3802281b096);
+    check("fedcba9876543210", 0x0123456789abcdef, 0x0aceab0fc6a0a28d);
+}
+
+#[test]
+fn variable_key_length_examples() {
+    // the set_key test: prefixes of 1 to 24 bytes of the key encrypt the same block
+    let key = "f0e1d2c3b4a5968778695a4b3c2d1e0f0011223344556677";
+    let ciphertexts: [u64; 24] = [
+        0xf9ad597c49db005e,
+        0xe91d21c1d961a6d6,
+        0xe9c2b70a1bc65cf3,
+        0xbe1e639408640f05,
+        0xb39e44481bdb1e6e,
+        0x9457aa83b1928c0d,
+        0x8bb77032f960629d,
+        0xe87a244e2cc85e82,
+        0x15750e7a4f4ec577,
+        0x122ba70b3ab64ae0,
+        0x3a833c9affc537f6,
+        0x9409da87a90f6bf2,
+        0x884f80625060b8b4,
+        0x1f85031c19e11968,
+        0x79d9373a714ca34f,
+        0x93142887ee3be15c,
+        0x03429e838ce2d14b,
+        0xa4299e27469ff67b,
+        0xafd5aed1c1bc96a8,
+        0x10851c0e3858da9f,
+        0xe6f51ed79b9db21f,
+        0x64a6e14afd36b46f,
+        0x80c7d7d45a5479ad,
+        0x05044b62fa52d080,
+    ];
+    for (len, ciphertext) in (1..=24).zip(ciphertexts) {
+        check(&key[..2 * len], 0xfedcba9876543210, ciphertext);
+    }
+}
@@ -0,0 +1,62 @@
+use cryptography::aes::{Key128, Key192, Key256};
+use cryptography::camellia::Camellia;
+use cryptography::BlockCipher;
+
+#[track_caller]
+fn check(cipher: Camellia, plaintext: &str, ciphertext: &str) {
+    let plaintext = hex::decode(plaintext).unwrap().try_into().unwrap();
+    let ciphertext = hex::decode(ciphertext).unwrap().try_into().unwrap();
+    assert_eq!(cipher.encrypt(plaintext), ciphertext);
+    assert_eq!(cipher.decrypt(ciphertext), plaintext);
+}
+
+#[test]
+fn rfc_3713_examples() {
+    // RFC 3713, appendix A
+    let plaintext = "0123456789abcdeffedcba9876543210";
+    check(
+        Key128::from_hex("0123456789abcdeffedcba9876543210").unwrap().into(),
+        plaintext,
+        "67673138549669730857065648eabe43",
+    );
+    check(
+        Key192::from_hex("0123456789abcdeffedcba98765432100011223344556677").unwrap().into(),
+        plaintext,
+        "b4993401b3e996f84ee5cee7d79b09b9",
+    );
+    check(
+        Key256::from_hex("0123456789abcdeffedcba987654321000112233445566778899aabbccddeeff")
+            .unwrap()
+            .into(),
+        plaintext,
+        "9acc237dff16d76c20ef7c919e3a7509",
+    );
+}
+
+#[test]
+fn iterated_encryption() {
+    // the expected values come from OpenSSL
+    #[track_caller]
+    fn check_iterated(key: &str, expected: &str) {
+        let cipher = Camellia::from_hex(key).unwrap();
+        let mut block = [0; 16];
+        for _ in 0..1000 {
+            block = cipher.encrypt(block);
+        }
+        assert_eq!(hex::encode(block), expected);
+        for _ in 0..1000 {
+            block = cipher.decrypt(block);
+        }
+        assert_eq!(block, [0; 16]);
+    }
+
+    check_iterated("00000000000000000000000000000000", "adf5565e3e2c15f397f1773cbba184ce");
+    check_iterated(
+        "000102030405060708090a0b0c0d0e0f1011121314151617",
+        "21cb9fd30bb2ee3c9280414ffe19264a",
+    );
+    check_iterated(
+        "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        "088118e5badef7d6592b720462942626",
+    );
+}
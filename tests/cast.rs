@@ -0,0 +1,43 @@
+use cryptography::cast::Cast128;
+use cryptography::BlockCipher;
+
+#[track_caller]
+fn check(key: &str, plaintext: u64, ciphertext: u64) {
+    let cipher = Cast128::from_hex(key).unwrap();
+    let plaintext = plaintext.to_be_bytes();
+    let ciphertext = ciphertext.to_be_bytes();
+    assert_eq!(cipher.encrypt(plaintext), ciphertext, "key = {key}");
+    assert_eq!(cipher.decrypt(ciphertext), plaintext, "key = {key}");
+}
+
+#[test]
+fn single_plaintext_key_ciphertext_sets() {
+    // RFC 2144, appendix B.1
+    check("0123456712345678234567893456789a", 0x0123456789abcdef, 0x238b4fe5847e44b2);
+    check("01234567123456782345", 0x0123456789abcdef, 0xeb6a711a2c02271b);
+    check("0123456712", 0x0123456789abcdef, 0x7ac816d16e9b302e);
+}
+
+#[test]
+fn maintenance_test() {
+    // The maintenance test from RFC 2144, appendix B.2, shortened from 1 000 000 to 10 000
+    // iterations to keep the test fast in debug builds. The expected values come from OpenSSL.
+    let mut a = hex::decode("0123456712345678234567893456789a").unwrap();
+    let mut b = a.clone();
+
+    let encrypt_halves = |data: &mut [u8], key: &[u8]| {
+        let cipher = Cast128::new(key);
+        for half in data.chunks_exact_mut(8) {
+            let block = cipher.encrypt(half.try_into().unwrap());
+            half.copy_from_slice(&block);
+        }
+    };
+
+    for _ in 0..10_000 {
+        encrypt_halves(&mut a, &b);
+        encrypt_halves(&mut b, &a);
+    }
+
+    assert_eq!(hex::encode(a), "fd9f563662cc64d7f2fb6be67d23a0f8");
+    assert_eq!(hex::encode(b), "e8c3c8eae136a412228cf7414395373b");
+}
@@ -0,0 +1,213 @@
+use cryptography::aead::AuthenticationError;
+use cryptography::chacha::{ChaCha20, ChaCha20Poly1305, XChaCha20, XChaCha20Poly1305};
+use cryptography::StreamCipher;
+
+const SUNSCREEN: &[u8] = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+
+const DHOLE: &[u8] = b"The dhole (pronounced \"dole\") is also known as the Asiatic wild dog, red dog, and whistling dog. It is about the size of a German shepherd but looks more like a long-legged fox. This highly elusive and skilled jumper is classified with wolves, coyotes, jackals, and foxes in the taxonomic family Canidae.";
+
+fn key(first: u8) -> [u8; 32] {
+    std::array::from_fn(|i| first + i as u8)
+}
+
+#[test]
+fn chacha20_rfc8439_encryption_example() {
+    // RFC 8439, section 2.4.2
+    let nonce = hex::decode("000000000000004a00000000").unwrap();
+    let expected = "6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0b\
+                    f91b65c5524733ab8f593dabcd62b3571639d624e65152ab8f530c359f0861d8\
+                    07ca0dbf500d6a6156a38e088a22b65e52bc514d16ccf806818ce91ab7793736\
+                    5af90bbf74a35be6b40b8eedf2785e42874d";
+
+    let mut cipher = ChaCha20::try_new(&key(0), &nonce).unwrap();
+    cipher.seek(1);
+    assert_eq!(hex::encode(cipher.encrypt(SUNSCREEN)), expected);
+
+    let mut cipher = ChaCha20::try_new(&key(0), &nonce).unwrap();
+    cipher.seek(1);
+    assert_eq!(cipher.decrypt(&hex::decode(expected).unwrap()), SUNSCREEN);
+}
+
+#[test]
+fn chacha20_keystream_examples() {
+    #[track_caller]
+    fn check(key: [u8; 32], nonce: [u8; 12], counter: u32, skip: usize, expected_hex: &str) {
+        let mut cipher = ChaCha20::new(&key, &nonce);
+        cipher.seek(counter);
+        let key_stream = cipher
+            .skip(skip)
+            .take(expected_hex.len() / 2)
+            .collect::<Vec<_>>();
+        assert_eq!(hex::encode(key_stream), expected_hex);
+    }
+
+    let mut one = [0; 32];
+    one[31] = 1;
+    let mut two = [0; 12];
+    two[11] = 2;
+
+    // RFC 8439, appendix A.1, test vector #1
+    check(
+        [0; 32],
+        [0; 12],
+        0,
+        0,
+        "76b8e0ada0f13d90405d6ae55386bd28bdd219b8a08ded1aa836efcc8b770dc7\
+         da41597c5157488d7724e03fb8d84a376a43b8f41518a11cc387b669b2ee6586",
+    );
+    check(
+        one,
+        two,
+        0,
+        0,
+        "ecfa254f845f647473d3cb140da9e87606cb33066c447b87bc2666dde3fbb739\
+         a371c9ec7abcb4cfa9211f7d90f64c2d07f89e5cf9b93e330a6e4c08af5ba6d5",
+    );
+    // the last 32 bytes of 1000 bytes, which spans several batches of blocks
+    check(
+        key(0),
+        std::array::from_fn(|i| i as u8),
+        7,
+        968,
+        "efa4285d01a87bef304cc387f4cccae95df845d838f83caf37aec49d780f1329",
+    );
+}
+
+#[test]
+fn xchacha20_example() {
+    // plaintext, key and nonce from draft-irtf-cfrg-xchacha-03, appendix A.3
+    let nonce: [u8; 24] = std::array::from_fn(|i| 0x40 + i as u8);
+    let expected = "2f717aa097099ff56c6f473bfdd6139732a20b16ccd293f4b21fe553aad96ea6\
+                    81aa4b4b342059f112ab7c5038a5a85139c400a6107a339dd95b3505803c717a\
+                    956314d87b82913edb7618b4da8efc3b566705066c37e880a3d4922c263a6ae6\
+                    2075645d421ebf1c53bc943d4ee7363fe162c36ec91bcb168e85b7101814f95c\
+                    2fc091ec07abd400b71639fb91dae9d22438a2788571538861f7787c7dc3b1eb\
+                    62e76c479e6e66a3648313b257c5c402ef209e5bdf7c522ef3fcd7df527950fb\
+                    3339415b105fb0bdff19a3693c23eecb39baa3923858a76fd8b5dce614bab0aa\
+                    960caf022215d59921d812cc5fd1f74e2b4a061b7f9dbd2eaa0ea4b7bed114c9\
+                    6fd8c6bd95b164d5669fd79678ca9093ceab36d3c788aea6eae8dfa321ac9638\
+                    8d0ea5b19ab0d587292ad70bffaa2cdc";
+
+    let cipher = XChaCha20::new(&key(0x80), &nonce);
+    assert_eq!(hex::encode(cipher.encrypt(DHOLE)), expected);
+
+    let cipher = XChaCha20::try_new(&key(0x80), &nonce).unwrap();
+    assert_eq!(cipher.decrypt(&hex::decode(expected).unwrap()), DHOLE);
+}
+
+#[test]
+fn chacha20_can_encrypt_split_messages() {
+    let expected = ChaCha20::new(&key(3), &[9; 12]).encrypt(DHOLE);
+
+    let mut cipher = StreamCipher::new(ChaCha20::new(&key(3), &[9; 12]));
+    let mut output = Vec::new();
+    for part in DHOLE.chunks(37) {
+        output.extend(cipher.encrypt(part.iter().cloned()));
+    }
+    assert_eq!(output, expected);
+
+    let mut cipher = ChaCha20::new(&key(3), &[9; 12]);
+    let mut output = DHOLE.to_vec();
+    for part in output.chunks_mut(100) {
+        cipher.apply_keystream(part);
+    }
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn xchacha20_seeking_matches_skipping_blocks() {
+    let nonce = [5; 24];
+    let expected = XChaCha20::new(&key(1), &nonce)
+        .skip(3 * 64)
+        .take(64)
+        .collect::<Vec<_>>();
+
+    let mut cipher = XChaCha20::new(&key(1), &nonce);
+    cipher.seek(3);
+    let mut block = [0; 64];
+    cipher.apply_keystream(&mut block);
+    assert_eq!(block.to_vec(), expected);
+}
+
+#[test]
+fn chacha20_poly1305_rfc8439_example() {
+    // RFC 8439, section 2.8.2
+    let aead = ChaCha20Poly1305::new(&key(0x80));
+    let nonce = hex::decode("070000004041424344454647").unwrap().try_into().unwrap();
+    let aad = hex::decode("50515253c0c1c2c3c4c5c6c7").unwrap();
+    let expected = "d31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d6\
+                    3dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b36\
+                    92ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc\
+                    3ff4def08e4b7a9de576d26586cec64b6116\
+                    1ae10b594f09e26a7e902ecbd0600691";
+
+    let ciphertext = aead.encrypt(&nonce, &aad, SUNSCREEN);
+    assert_eq!(hex::encode(&ciphertext), expected);
+    assert_eq!(aead.decrypt(&nonce, &aad, &ciphertext).unwrap(), SUNSCREEN);
+}
+
+#[test]
+fn xchacha20_poly1305_example() {
+    // draft-irtf-cfrg-xchacha-03, appendix A.3.1
+    let aead = XChaCha20Poly1305::from_hex(hex::encode(key(0x80))).unwrap();
+    let nonce = std::array::from_fn(|i| 0x40 + i as u8);
+    let aad = hex::decode("50515253c0c1c2c3c4c5c6c7").unwrap();
+    let expected = "bd6d179d3e83d43b9576579493c0e939572a1700252bfaccbed2902c21396cbb\
+                    731c7f1b0b4aa6440bf3a82f4eda7e39ae64c6708c54c216cb96b72e1213b452\
+                    2f8c9ba40db5d945b11b69b982c1bb9e3f3fac2bc369488f76b2383565d3fff9\
+                    21f9664c97637da9768812f615c68b13b52e\
+                    c0875924c1c7987947deafd8780acf49";
+
+    let ciphertext = aead.encrypt(&nonce, &aad, SUNSCREEN);
+    assert_eq!(hex::encode(&ciphertext), expected);
+    assert_eq!(aead.decrypt(&nonce, &aad, &ciphertext).unwrap(), SUNSCREEN);
+}
+
+#[test]
+fn chacha20_poly1305_empty_message() {
+    let aead = ChaCha20Poly1305::new(&key(0));
+    let ciphertext = aead.encrypt(&[0; 12], b"", b"");
+    assert_eq!(hex::encode(&ciphertext), "10324f800a160bd9a1794255be7ec29d");
+    assert_eq!(aead.decrypt(&[0; 12], b"", &ciphertext), Ok(vec![]));
+}
+
+#[test]
+fn chacha20_poly1305_rejects_modifications() {
+    let aead = ChaCha20Poly1305::new(&key(0x80));
+    let nonce = [1; 12];
+    let ciphertext = aead.encrypt(&nonce, b"header", DHOLE);
+
+    for i in [0, 100, ciphertext.len() - 1] {
+        let mut modified = ciphertext.clone();
+        modified[i] ^= 1;
+        assert_eq!(aead.decrypt(&nonce, b"header", &modified), Err(AuthenticationError));
+    }
+    assert_eq!(aead.decrypt(&nonce, b"Header", &ciphertext), Err(AuthenticationError));
+    assert_eq!(aead.decrypt(&[2; 12], b"header", &ciphertext), Err(AuthenticationError));
+    assert_eq!(
+        aead.decrypt(&nonce, b"header", &ciphertext[..ciphertext.len() - 1]),
+        Err(AuthenticationError)
+    );
+}
+
+#[test]
+fn xchacha20_poly1305_detached_tag() {
+    let aead = XChaCha20Poly1305::new(&key(7));
+    let nonce = [3; 24];
+
+    let mut buffer = DHOLE.to_vec();
+    let tag = aead.encrypt_in_place_detached(&nonce, b"", &mut buffer);
+    assert_eq!([buffer.as_slice(), &tag].concat(), aead.encrypt(&nonce, b"", DHOLE));
+
+    let mut bad_tag = tag;
+    bad_tag[0] ^= 0x80;
+    let ciphertext = buffer.clone();
+    assert_eq!(
+        aead.decrypt_in_place_detached(&nonce, b"", &mut buffer, &bad_tag),
+        Err(AuthenticationError)
+    );
+    assert_eq!(buffer, ciphertext);
+
+    aead.decrypt_in_place_detached(&nonce, b"", &mut buffer, &tag).unwrap();
+    assert_eq!(buffer, DHOLE);
+}
@@ -0,0 +1,79 @@
+use cryptography::des::{Des, Tdea};
+use cryptography::BlockCipher;
+
+#[track_caller]
+fn check_block(cipher: &impl BlockCipher<8>, plaintext: u64, ciphertext: u64) {
+    let plaintext = plaintext.to_be_bytes();
+    let ciphertext = ciphertext.to_be_bytes();
+    assert_eq!(cipher.encrypt(plaintext), ciphertext);
+    assert_eq!(cipher.decrypt(ciphertext), plaintext);
+}
+
+#[test]
+fn des_classic_example() {
+    check_block(
+        &Des::new(0x133457799bbcdff1_u64.to_be_bytes()),
+        0x0123456789abcdef,
+        0x85e813540f0ab405,
+    );
+}
+
+#[test]
+fn des_fips_81_ecb_example() {
+    // FIPS 81, appendix B, table B1: "Now is the time for all "
+    let cipher = Des::from_hex("0123456789abcdef").unwrap();
+
+    check_block(&cipher, 0x4e6f772069732074, 0x3fa40e8a984d4815);
+    check_block(&cipher, 0x68652074696d6520, 0x6a271787ab8883f9);
+    check_block(&cipher, 0x666f7220616c6c20, 0x893d51ec4b563b53);
+}
+
+#[test]
+fn des_variable_plaintext_known_answers() {
+    // NIST SP 800-17, table A.1
+    let cipher = Des::new(0x0101010101010101_u64.to_be_bytes());
+
+    check_block(&cipher, 0x8000000000000000, 0x95f8a5e5dd31d900);
+    check_block(&cipher, 0x4000000000000000, 0xdd7f121ca5015619);
+    check_block(&cipher, 0x2000000000000000, 0x2e8653104f3834ea);
+    check_block(&cipher, 0x0000000000000001, 0x166b40b44aba4bd6);
+}
+
+#[test]
+fn des_variable_key_known_answers() {
+    // NIST SP 800-17, table A.2
+    check_block(&Des::new(0x8001010101010101_u64.to_be_bytes()), 0, 0x95a8d72813daa94d);
+    check_block(&Des::new(0x4001010101010101_u64.to_be_bytes()), 0, 0x0eec1487dd8c26d5);
+    check_block(&Des::new(0x0101010101010180_u64.to_be_bytes()), 0, 0x9cc62df43b6eed74);
+    check_block(&Des::new(0x0101010101010102_u64.to_be_bytes()), 0, 0x869efd7f9f265a09);
+}
+
+#[test]
+fn tdea_3key_example() {
+    // NIST SP 800-67 rev. 1, appendix B
+    let cipher = Tdea::from_hex("0123456789abcdef23456789abcdef01456789abcdef0123").unwrap();
+
+    let plaintext = b"The qufck brown fox jump";
+    let ciphertext = [
+        0xa826fd8ce53b855f_u64.to_be_bytes(),
+        0xcce21c8112256fe6_u64.to_be_bytes(),
+        0x68d5c05dd9b6b900_u64.to_be_bytes(),
+    ]
+    .concat();
+
+    for (p, c) in plaintext.chunks_exact(8).zip(ciphertext.chunks_exact(8)) {
+        let p = p.try_into().unwrap();
+        let c = c.try_into().unwrap();
+        assert_eq!(cipher.encrypt(p), c);
+        assert_eq!(cipher.decrypt(c), p);
+    }
+}
+
+#[test]
+fn tdea_2key_example() {
+    check_block(
+        &Tdea::from_hex("0123456789abcdef23456789abcdef01").unwrap(),
+        0x5468652071756663,
+        0xc44862f70cf2fbdc,
+    );
+}
@@ -0,0 +1,175 @@
+use cryptography::ascon::AsconHash256;
+use cryptography::blake2::{Blake2b512, Blake2s256};
+use cryptography::blake3::Blake3;
+use cryptography::sha::{Sha1, Sha224, Sha256, Sha384, Sha512, Sha512_224, Sha512_256};
+use cryptography::sha3::{Keccak256, Sha3_224, Sha3_256, Sha3_384, Sha3_512};
+use cryptography::{Digest, Md2, Md4, Md5, Ripemd160, Whirlpool};
+
+/// Hashes `message` in pieces of `piece_len` bytes through the trait alone.
+fn hash_in_pieces<D: Digest>(message: &[u8], piece_len: usize) -> D::Output {
+    let mut hasher = D::new();
+    for piece in message.chunks(piece_len) {
+        hasher.update(piece);
+    }
+    hasher.finalize()
+}
+
+#[track_caller]
+fn check<D: Digest>(name: &str, block_size: usize, abc_hex: &str) {
+    assert_eq!(D::NAME, name);
+    assert_eq!(D::BLOCK_SIZE, block_size, "{name}");
+
+    let abc = D::digest(b"abc");
+    assert_eq!(abc.as_ref().len(), D::OUTPUT_SIZE, "{name}");
+    assert_eq!(hex::encode(abc.as_ref()), abc_hex, "{name}");
+
+    let message = (0..=255)
+        .cycle()
+        .take(3 * block_size + 5)
+        .collect::<Vec<u8>>();
+    let expected = D::digest(&message);
+    for piece_len in [1, block_size - 1, block_size, block_size + 1] {
+        assert_eq!(hash_in_pieces::<D>(&message, piece_len), expected, "{name}");
+    }
+
+    let mut hasher = D::new();
+    hasher.update(&message);
+    let fork = hasher.clone();
+    hasher.reset();
+    hasher.update(b"abc");
+    assert_eq!(hasher.finalize(), abc, "{name}");
+    assert_eq!(fork.finalize(), expected, "{name}");
+}
+
+#[test]
+fn every_hash_implements_digest() {
+    check::<Md2>("MD2", 16, "da853b0d3f88d99b30283a69e6ded6bb");
+    check::<Md4>("MD4", 64, "a448017aaf21d8525fc10ae87aa6729d");
+    check::<Md5>("MD5", 64, "900150983cd24fb0d6963f7d28e17f72");
+    check::<Ripemd160>("RIPEMD-160", 64, "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc");
+    check::<Whirlpool>(
+        "Whirlpool",
+        64,
+        "4e2448a4c6f486bb16b6562c73b4020bf3043e3a731bce721ae1b303d97e6d4c7181eebdb6c57e277d0e34957114cbd6c797fc9d95d8b582d225292076d4eef5",
+    );
+    check::<Sha1>("SHA-1", 64, "a9993e364706816aba3e25717850c26c9cd0d89d");
+    check::<Sha224>(
+        "SHA-224",
+        64,
+        "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7",
+    );
+    check::<Sha256>(
+        "SHA-256",
+        64,
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+    );
+    check::<Sha384>(
+        "SHA-384",
+        128,
+        "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7",
+    );
+    check::<Sha512>(
+        "SHA-512",
+        128,
+        "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+    );
+    check::<Sha512_224>(
+        "SHA-512/224",
+        128,
+        "4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa",
+    );
+    check::<Sha512_256>(
+        "SHA-512/256",
+        128,
+        "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23",
+    );
+    check::<Sha3_224>(
+        "SHA3-224",
+        144,
+        "e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf",
+    );
+    check::<Sha3_256>(
+        "SHA3-256",
+        136,
+        "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532",
+    );
+    check::<Sha3_384>(
+        "SHA3-384",
+        104,
+        "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b298d88cea927ac7f539f1edf228376d25",
+    );
+    check::<Sha3_512>(
+        "SHA3-512",
+        72,
+        "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0",
+    );
+    check::<Keccak256>(
+        "Keccak-256",
+        136,
+        "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+    );
+    check::<Blake2b512>(
+        "BLAKE2b-512",
+        128,
+        "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923",
+    );
+    check::<Blake2s256>(
+        "BLAKE2s-256",
+        64,
+        "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982",
+    );
+    check::<Blake3>(
+        "BLAKE3",
+        64,
+        "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85",
+    );
+    check::<AsconHash256>(
+        "Ascon-Hash256",
+        8,
+        "45aa03431c3c829b3b066f33e844b0cc4d20a45af92d3dcfdf34f40fc20935cf",
+    );
+    // Count = 4 of LWC_HASH_KAT_256.txt
+    assert_eq!(
+        hex::encode(AsconHash256::digest(&[0, 1, 2])),
+        "265ab89a609f5a05dca57e83fbba700f9a2d2c4211ba4cc9f0a1a369e17b915c"
+    );
+}
+
+#[test]
+fn object_identifiers() {
+    #[track_caller]
+    fn check_oid<D: Digest>(expected_hex: &str) {
+        assert_eq!(hex::encode(D::OID.unwrap()), expected_hex, "{}", D::NAME);
+    }
+
+    check_oid::<Md2>("2a864886f70d0202");
+    check_oid::<Md4>("2a864886f70d0204");
+    check_oid::<Md5>("2a864886f70d0205");
+    check_oid::<Sha1>("2b0e03021a");
+    check_oid::<Sha224>("608648016503040204");
+    check_oid::<Sha256>("608648016503040201");
+    check_oid::<Sha384>("608648016503040202");
+    check_oid::<Ripemd160>("2b24030201");
+    check_oid::<Whirlpool>("28cf06030037");
+    check_oid::<Sha512>("608648016503040203");
+    check_oid::<Sha512_224>("608648016503040205");
+    check_oid::<Sha512_256>("608648016503040206");
+    check_oid::<Sha3_224>("608648016503040207");
+    check_oid::<Sha3_256>("608648016503040208");
+    check_oid::<Sha3_384>("608648016503040209");
+    check_oid::<Sha3_512>("60864801650304020a");
+    check_oid::<Blake2b512>("2b060104018d3a0c020110");
+    check_oid::<Blake2s256>("2b060104018d3a0c020208");
+    assert_eq!(AsconHash256::OID, None);
+    assert_eq!(Keccak256::OID, None);
+    assert_eq!(Blake3::OID, None);
+}
+
+#[test]
+fn md_functions_return_hash_values() {
+    assert_eq!(cryptography::md5(b"abc"), Md5::digest(b"abc"));
+    assert_eq!(cryptography::md4(b"abc"), Md4::digest(b"abc"));
+    assert_eq!(cryptography::md2(b"abc"), Md2::digest(b"abc"));
+    assert_eq!(cryptography::ripemd160(b"abc"), Ripemd160::digest(b"abc"));
+    assert_eq!(cryptography::whirlpool(b"abc"), Whirlpool::digest(b"abc"));
+}
@@ -0,0 +1,248 @@
+use cryptography::hmac::{hmac, Hmac};
+use cryptography::sha::{Sha1, Sha224, Sha256, Sha384, Sha512};
+use cryptography::sha3::Sha3_256;
+use cryptography::{Digest, Md5};
+
+/// The key 0x0102...19 of the fourth test case of both RFCs.
+const COUNTING_KEY: [u8; 25] = [
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+];
+
+#[track_caller]
+fn check<D: Digest>(key: &[u8], message: &[u8], tag_hex: &str) {
+    assert_eq!(
+        hex::encode(hmac::<D>(key, message)),
+        tag_hex,
+        "HMAC-{}",
+        D::NAME
+    );
+
+    let mut mac = Hmac::<D>::new(key);
+    for piece in message.chunks(7) {
+        mac.update(piece);
+    }
+    let mut tag = hex::decode(tag_hex).unwrap();
+    assert!(mac.clone().verify(&tag), "HMAC-{}", D::NAME);
+    tag[0] ^= 1;
+    assert!(!mac.verify(&tag), "HMAC-{}", D::NAME);
+}
+
+/// Checks a tag that is truncated to `tag_hex.len() / 2` bytes.
+#[track_caller]
+fn check_truncated<D: Digest>(key: &[u8], message: &[u8], tag_hex: &str) {
+    let tag = hex::decode(tag_hex).unwrap();
+    let mut mac = Hmac::<D>::new(key);
+    mac.update(message);
+
+    let mut output = vec![0; tag.len()];
+    mac.clone().finalize_truncated(&mut output);
+    assert_eq!(hex::encode(&output), tag_hex, "HMAC-{}", D::NAME);
+    assert!(mac.clone().verify_truncated(&tag), "HMAC-{}", D::NAME);
+    assert!(!mac.verify(&tag), "HMAC-{}", D::NAME);
+}
+
+#[test]
+fn rfc2202_md5() {
+    check::<Md5>(&[0x0b; 16], b"Hi There", "9294727a3638bb1c13f48ef8158bfc9d");
+    check::<Md5>(
+        b"Jefe",
+        b"what do ya want for nothing?",
+        "750c783e6ab0b503eaa86e310a5db738",
+    );
+    check::<Md5>(&[0xaa; 16], &[0xdd; 50], "56be34521d144c88dbb8c733f0e8b3f6");
+    check::<Md5>(
+        &COUNTING_KEY,
+        &[0xcd; 50],
+        "697eaf0aca3a3aea3a75164746ffaa79",
+    );
+    check::<Md5>(
+        &[0x0c; 16],
+        b"Test With Truncation",
+        "56461ef2342edc00f9bab995690efd4c",
+    );
+    check_truncated::<Md5>(
+        &[0x0c; 16],
+        b"Test With Truncation",
+        "56461ef2342edc00f9bab995",
+    );
+    check::<Md5>(
+        &[0xaa; 80],
+        b"Test Using Larger Than Block-Size Key - Hash Key First",
+        "6b1ab7fe4bd7bf8f0b62e6ce61b9d0cd",
+    );
+    check::<Md5>(
+        &[0xaa; 80],
+        b"Test Using Larger Than Block-Size Key and Larger Than One Block-Size Data",
+        "6f630fad67cda0ee1fb1f562db3aa53e",
+    );
+}
+
+#[test]
+fn rfc2202_sha1() {
+    check::<Sha1>(
+        &[0x0b; 20],
+        b"Hi There",
+        "b617318655057264e28bc0b6fb378c8ef146be00",
+    );
+    check::<Sha1>(
+        b"Jefe",
+        b"what do ya want for nothing?",
+        "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79",
+    );
+    check::<Sha1>(
+        &[0xaa; 20],
+        &[0xdd; 50],
+        "125d7342b9ac11cd91a39af48aa17b4f63f175d3",
+    );
+    check::<Sha1>(
+        &COUNTING_KEY,
+        &[0xcd; 50],
+        "4c9007f4026250c6bc8414f9bf50c86c2d7235da",
+    );
+    check::<Sha1>(
+        &[0x0c; 20],
+        b"Test With Truncation",
+        "4c1a03424b55e07fe7f27be1d58bb9324a9a5a04",
+    );
+    check_truncated::<Sha1>(
+        &[0x0c; 20],
+        b"Test With Truncation",
+        "4c1a03424b55e07fe7f27be1",
+    );
+    check::<Sha1>(
+        &[0xaa; 80],
+        b"Test Using Larger Than Block-Size Key - Hash Key First",
+        "aa4ae5e15272d00e95705637ce8a3b55ed402112",
+    );
+    check::<Sha1>(
+        &[0xaa; 80],
+        b"Test Using Larger Than Block-Size Key and Larger Than One Block-Size Data",
+        "e8e99d0f45237d786d6bbaa7965c7808bbff1a91",
+    );
+}
+
+/// Checks one test case of RFC 4231 with every hash of the SHA-2 family.
+#[track_caller]
+fn check_sha2(key: &[u8], message: &[u8], tags_hex: [&str; 4]) {
+    check::<Sha224>(key, message, tags_hex[0]);
+    check::<Sha256>(key, message, tags_hex[1]);
+    check::<Sha384>(key, message, tags_hex[2]);
+    check::<Sha512>(key, message, tags_hex[3]);
+}
+
+#[test]
+fn rfc4231_test_case_1() {
+    check_sha2(
+        &[0x0b; 20],
+        b"Hi There",
+        [
+            "896fb1128abbdf196832107cd49df33f47b4b1169912ba4f53684b22",
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+            "afd03944d84895626b0825f4ab46907f15f9dadbe4101ec682aa034c7cebc59c\
+             faea9ea9076ede7f4af152e8b2fa9cb6",
+            "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cde\
+             daa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854",
+        ],
+    );
+}
+
+#[test]
+fn rfc4231_test_case_2() {
+    check_sha2(
+        b"Jefe",
+        b"what do ya want for nothing?",
+        [
+            "a30e01098bc6dbbf45690f3a7e9e6d0f8bbea2a39e6148008fd05e44",
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            "af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e\
+             8e2240ca5e69e2c78b3239ecfab21649",
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+             9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+        ],
+    );
+}
+
+#[test]
+fn rfc4231_test_case_3() {
+    check_sha2(
+        &[0xaa; 20],
+        &[0xdd; 50],
+        [
+            "7fb3cb3588c6c1f6ffa9694d7d6ad2649365b0c1f65d69d1ec8333ea",
+            "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+            "88062608d3e6ad8a0aa2ace014c8a86f0aa635d947ac9febe83ef4e55966144b\
+             2a5ab39dc13814b94e3ab6e101a34f27",
+            "fa73b0089d56a284efb0f0756c890be9b1b5dbdd8ee81a3655f83e33b2279d39\
+             bf3e848279a722c806b485a47e67c807b946a337bee8942674278859e13292fb",
+        ],
+    );
+}
+
+#[test]
+fn rfc4231_test_case_4() {
+    check_sha2(
+        &COUNTING_KEY,
+        &[0xcd; 50],
+        [
+            "6c11506874013cac6a2abc1bb382627cec6a90d86efc012de7afec5a",
+            "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b",
+            "3e8a69b7783c25851933ab6290af6ca77a9981480850009cc5577c6e1f573b4e\
+             6801dd23c4a7d679ccf8a386c674cffb",
+            "b0ba465637458c6990e5a8c5f61d4af7e576d97ff94b872de76f8050361ee3db\
+             a91ca5c11aa25eb4d679275cc5788063a5f19741120c4f2de2adebeb10a298dd",
+        ],
+    );
+}
+
+#[test]
+fn rfc4231_test_case_5_truncation() {
+    let key = &[0x0c; 20];
+    let message = b"Test With Truncation";
+    check_truncated::<Sha224>(key, message, "0e2aea68a90c8d37c988bcdb9fca6fa8");
+    check_truncated::<Sha256>(key, message, "a3b6167473100ee06e0c796c2955552b");
+    check_truncated::<Sha384>(key, message, "3abf34c3503b2a23a46efc619baef897");
+    check_truncated::<Sha512>(key, message, "415fad6271580a531d4179bc891d87a6");
+}
+
+#[test]
+fn rfc4231_test_case_6() {
+    check_sha2(
+        &[0xaa; 131],
+        b"Test Using Larger Than Block-Size Key - Hash Key First",
+        [
+            "95e9a0db962095adaebe9b2d6f0dbce2d499f112f2d2b7273fa6870e",
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+            "4ece084485813e9088d2c63a041bc5b44f9ef1012a2b588f3cd11f05033ac4c6\
+             0c2ef6ab4030fe8296248df163f44952",
+            "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f352\
+             6b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+        ],
+    );
+}
+
+#[test]
+fn rfc4231_test_case_7() {
+    check_sha2(
+        &[0xaa; 131],
+        b"This is a test using a larger than block-size key and a larger than block-size data. \
+          The key needs to be hashed before being used by the HMAC algorithm.",
+        [
+            "3a854166ac5d9f023f54d517d0b39dbd946770db9c2b95c9f6f565d1",
+            "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+            "6617178e941f020d351e2f254e8fd32c602420feb0b8fb9adccebb82461e99c5\
+             a678cc31e799176d3860e6110c46523e",
+            "e37b6a775dc87dbaa4dfa9f96e5e3ffddebd71f8867289865df5a32d20cdc944\
+             b6022cac3c4982b10d5eeb55c3e4de15134676fb6de0446065c97440fa8c6a58",
+        ],
+    );
+}
+
+#[test]
+fn other_hashes_use_their_own_block_size() {
+    // SHA3-256 pads the key to its rate of 136 bytes
+    check::<Sha3_256>(
+        b"key",
+        b"The quick brown fox jumps over the lazy dog",
+        "8c6e0683409427f8931711b10ca92a506eb1fafa48fadd66d76126f47ac2c333",
+    );
+}
@@ -0,0 +1,41 @@
+use cryptography::idea::Idea;
+use cryptography::BlockCipher;
+
+#[track_caller]
+fn check(key: &str, plaintext: u64, ciphertext: u64) {
+    let cipher = Idea::from_hex(key).unwrap();
+    let plaintext = plaintext.to_be_bytes();
+    let ciphertext = ciphertext.to_be_bytes();
+    assert_eq!(cipher.encrypt(plaintext), ciphertext, "key = {key}");
+    assert_eq!(cipher.decrypt(ciphertext), plaintext, "key = {key}");
+}
+
+#[test]
+fn original_example() {
+    // the example from Lai's thesis
+    check("00010002000300040005000600070008", 0x0000000100020003, 0x11fbed2b01986de5);
+}
+
+#[test]
+fn ecb_examples() {
+    // NESSIE, set 1 and set 2, vector 0
+    check("80000000000000000000000000000000", 0x0000000000000000, 0xb1f5f7f87901370f);
+    check("00000000000000000000000000000000", 0x8000000000000000, 0x8001000180008000);
+    // the expected value comes from OpenSSL
+    check("2bd6459f82c5b300952c49104881ff48", 0xea024714ad5c4d84, 0xc8fb51d3516627a8);
+}
+
+#[test]
+fn iterated_encryption() {
+    // the expected value comes from OpenSSL
+    let cipher = Idea::from_hex("00010002000300040005000600070008").unwrap();
+    let mut block = 0x0000000100020003_u64.to_be_bytes();
+    for _ in 0..1000 {
+        block = cipher.encrypt(block);
+    }
+    assert_eq!(block, 0x66a4d8ad7cf07185_u64.to_be_bytes());
+    for _ in 0..1000 {
+        block = cipher.decrypt(block);
+    }
+    assert_eq!(block, 0x0000000100020003_u64.to_be_bytes());
+}
@@ -1,13 +1,52 @@
 use cryptography::md2;
 use cryptography::md4;
 use cryptography::md5;
+use cryptography::ripemd160;
+use cryptography::sha::{sha256, Hash128, Hash160, Hash512};
+use cryptography::whirlpool;
+use cryptography::{Digest, Md2, Md4, Md5, Ripemd160, Whirlpool};
+use hex::FromHex;
 
 #[track_caller]
-fn check_hash<'a>(hash_fn: impl FnOnce(&'a [u8]) -> [u8; 16], input: &'a [u8], expected_hash: u128) {
-    let expected_hash = expected_hash.to_be_bytes();
+fn check_hash<'a>(hash_fn: impl FnOnce(&'a [u8]) -> Hash128, input: &'a [u8], expected_hash: u128) {
+    let expected_hash = Hash128::from(expected_hash.to_be_bytes());
     assert_eq!(hash_fn(input), expected_hash, "input = {:?}", input);
 }
 
+#[track_caller]
+fn check_ripemd160(input: &[u8], expected_hex: &str) {
+    let expected_hash = Hash160::from_hex(expected_hex).unwrap();
+    assert_eq!(ripemd160(input), expected_hash, "input = {:?}", input);
+}
+
+#[track_caller]
+fn check_whirlpool(input: &[u8], expected_hex: &str) {
+    let expected_hash = Hash512::from_hex(expected_hex).unwrap();
+    assert_eq!(whirlpool(input), expected_hash, "input = {:?}", input);
+}
+
+/// Hashes a large message both through the streaming hasher, in pieces that are not aligned
+/// with the blocks, and through the iterator-based `hash_fn`.
+#[track_caller]
+fn check_large_message<'a, D: Digest<Output = Hash128>>(
+    hash_fn: impl FnOnce(&'a [u8]) -> Hash128,
+    message: &'a [u8],
+    expected_hex: &str,
+) {
+    let expected_hash = Hash128::from_hex(expected_hex).unwrap();
+
+    let mut hasher = D::new();
+    for piece in message.chunks(1000) {
+        hasher.update(piece);
+    }
+    assert_eq!(hasher.finalize(), expected_hash, "{}", D::NAME);
+    assert_eq!(hash_fn(message), expected_hash, "{}", D::NAME);
+}
+
+fn large_message(mebibytes: usize) -> Vec<u8> {
+    (0..mebibytes << 20).map(|i| (i * 7 % 251) as u8).collect()
+}
+
 #[test]
 fn md2_examples() {
     check_hash(md2, b"", 0x_8350e5a3e24c153df2275c9f80692773_u128);
@@ -54,3 +93,126 @@ fn md5_examples() {
         0x_57edf4a22be3c955ac49da2e2107b67a_u128,
     );
 }
+
+#[test]
+fn md2_large_message() {
+    check_large_message::<Md2>(md2, &large_message(2), "c8898e52f0f7445184e329f3874639d3");
+}
+
+#[test]
+fn md4_large_message() {
+    check_large_message::<Md4>(md4, &large_message(16), "54b1eb60469e7d59527c1526d722ab71");
+}
+
+#[test]
+fn md5_large_message() {
+    check_large_message::<Md5>(md5, &large_message(16), "e8c9eeba22509b1e3e92d3db5724a15a");
+}
+
+#[test]
+fn ripemd160_examples() {
+    check_ripemd160(b"", "9c1185a5c5e9fc54612808977ee8f548b2258d31");
+    check_ripemd160(b"a", "0bdc9d2d256b3ee9daae347be6f4dc835a467ffe");
+    check_ripemd160(b"abc", "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc");
+    check_ripemd160(
+        b"message digest",
+        "5d0689ef49d2fae572b881b123a85ffa21595f36",
+    );
+    check_ripemd160(
+        b"abcdefghijklmnopqrstuvwxyz",
+        "f71c27109c692c1b56bbdceb5b9d2865b3708dbc",
+    );
+    check_ripemd160(
+        b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+        "12a053384a9c0c88e405a06c27dcf49ada62eb2b",
+    );
+    check_ripemd160(
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+        "b0e20b6e3116640286ed3a87a5713079b21f5189",
+    );
+    check_ripemd160(
+        b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+        "9b752e45573d4b39f4dbd3323cab82bf63326bfb",
+    );
+    check_ripemd160(
+        &[b'a'; 1_000_000],
+        "52783243c1697bdbe16d37f97f68f08325dc1528",
+    );
+}
+
+#[test]
+fn hash160_of_bitcoin_public_key() {
+    // the compressed public key of the private key 1
+    let public_key =
+        Vec::from_hex("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+            .unwrap();
+    assert_eq!(
+        ripemd160(sha256(&public_key).as_ref()),
+        Hash160::from_hex("751e76e8199196d454941c45d1b3a323f1433bd6").unwrap(),
+    );
+}
+
+#[test]
+fn whirlpool_examples() {
+    check_whirlpool(
+        b"",
+        "19fa61d75522a4669b44e39c1d2e1726c530232130d407f89afee0964997f7a7\
+         3e83be698b288febcf88e3e03c4f0757ea8964e59b63d93708b138cc42a66eb3",
+    );
+    check_whirlpool(
+        b"a",
+        "8aca2602792aec6f11a67206531fb7d7f0dff59413145e6973c45001d0087b42\
+         d11bc645413aeff63a42391a39145a591a92200d560195e53b478584fdae231a",
+    );
+    check_whirlpool(
+        b"abc",
+        "4e2448a4c6f486bb16b6562c73b4020bf3043e3a731bce721ae1b303d97e6d4c\
+         7181eebdb6c57e277d0e34957114cbd6c797fc9d95d8b582d225292076d4eef5",
+    );
+    check_whirlpool(
+        b"message digest",
+        "378c84a4126e2dc6e56dcc7458377aac838d00032230f53ce1f5700c0ffb4d3b\
+         8421557659ef55c106b4b52ac5a4aaa692ed920052838f3362e86dbd37a8903e",
+    );
+    check_whirlpool(
+        b"abcdefghijklmnopqrstuvwxyz",
+        "f1d754662636ffe92c82ebb9212a484a8d38631ead4238f5442ee13b8054e41b\
+         08bf2a9251c30b6a0b8aae86177ab4a6f68f673e7207865d5d9819a3dba4eb3b",
+    );
+    check_whirlpool(
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+        "dc37e008cf9ee69bf11f00ed9aba26901dd7c28cdec066cc6af42e40f82f3a1e\
+         08eba26629129d8fb7cb57211b9281a65517cc879d7b962142c65f5a7af01467",
+    );
+    check_whirlpool(
+        b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+        "466ef18babb0154d25b9d38a6414f5c08784372bccb204d6549c4afadb601429\
+         4d5bd8df2a6c44e538cd047b2681a51a2c60481e88c5a20b2c2a80cf3a9a083b",
+    );
+    check_whirlpool(
+        b"The quick brown fox jumps over the lazy dog",
+        "b97de512e91e3828b40d2b0fdce9ceb3c4a71f9bea8d88e75c4fa854df36725f\
+         d2b52eb6544edcacd6f8beddfea403cb55ae31f03ad62a5ef54e42ee82c3fb35",
+    );
+    check_whirlpool(
+        &[b'a'; 1_000_000],
+        "0c99005beb57eff50a7cf005560ddf5d29057fd86b20bfd62deca0f1ccea4af5\
+         1fc15490eddc47af32bb2b66c34ff9ad8c6008ad677f77126953b226e4ed8b01",
+    );
+}
+
+#[test]
+fn streaming_matches_one_shot_around_the_length_field() {
+    // Whirlpool's length field takes half a block, so padding spills over much earlier
+    let message = (0..=255).cycle().take(200).collect::<Vec<u8>>();
+    for len in [0, 1, 31, 32, 33, 55, 56, 63, 64, 65, 95, 96, 128, 200] {
+        let mut ripemd = Ripemd160::new();
+        let mut whirl = Whirlpool::new();
+        for piece in message[..len].chunks(7) {
+            ripemd.update(piece);
+            whirl.update(piece);
+        }
+        assert_eq!(ripemd.finalize(), ripemd160(&message[..len]), "len = {len}");
+        assert_eq!(whirl.finalize(), whirlpool(&message[..len]), "len = {len}");
+    }
+}
@@ -0,0 +1,58 @@
+use cryptography::poly1305::{authenticate, Poly1305};
+
+#[track_caller]
+fn check(key_hex: &str, message_hex: &str, tag_hex: &str) {
+    let key: [u8; 32] = hex::decode(key_hex).unwrap().try_into().unwrap();
+    let message = hex::decode(message_hex).unwrap();
+
+    assert_eq!(hex::encode(authenticate(&key, &message)), tag_hex);
+
+    let mut mac = Poly1305::new(&key);
+    mac.update(&message);
+    assert!(mac.verify(&hex::decode(tag_hex).unwrap()));
+}
+
+#[test]
+fn rfc8439_example() {
+    check(
+        "85d6be7857556d337f4452fe42d506a80103808afb0db2fd4abff6af4149f51b",
+        &hex::encode("Cryptographic Forum Research Group"),
+        "a8061dc1305136c6c22b8baf0c0127a9",
+    );
+}
+
+#[test]
+fn rfc8439_all_zero_example() {
+    // RFC 8439, appendix A.3, test vector #1
+    check(&"00".repeat(32), &"00".repeat(64), "00000000000000000000000000000000");
+}
+
+/// RFC 8439, appendix A.3, test vectors #6 to #11, which exercise the final reduction
+#[test]
+fn rfc8439_reduction_edge_cases() {
+    let two = format!("02{}", "00".repeat(31));
+    let one = format!("01{}", "00".repeat(31));
+
+    check(&two, &"ff".repeat(16), "03000000000000000000000000000000");
+    check(
+        &format!("02{}{}", "00".repeat(15), "ff".repeat(16)),
+        &format!("02{}", "00".repeat(15)),
+        "03000000000000000000000000000000",
+    );
+    check(
+        &one,
+        &format!("{}f0{}11{}", "ff".repeat(16), "ff".repeat(15), "00".repeat(15)),
+        "05000000000000000000000000000000",
+    );
+    check(
+        &one,
+        &format!("{}fb{}{}", "ff".repeat(16), "fe".repeat(15), "01".repeat(16)),
+        "00000000000000000000000000000000",
+    );
+    check(&two, &format!("fd{}", "ff".repeat(15)), "faffffffffffffffffffffffffffffff");
+    check(
+        &one,
+        &format!("fd{}{}", "ff".repeat(15), "00".repeat(16)),
+        "fdffffffffffffffffffffffffffffff",
+    );
+}
@@ -1,4 +1,4 @@
-use cryptography::{rc2, rc4, rc5, BlockCipher, BlockEncryption, EcbMode};
+use cryptography::{rc2, rc4, rc4a, rc5, rc6, spritz, BlockCipher, BlockEncryption, EcbMode};
 
 #[track_caller]
 fn check_block_cipher<const N: usize, C: BlockCipher<N>>(make_cipher: impl Fn() -> C, plaintext: &[u8], ciphertext: &[u8]) {
@@ -80,3 +80,171 @@ fn rc5_ecb_examples() {
         ],
     );
 }
+
+#[test]
+fn rc5_word_size_examples() {
+    // test vectors from draft-krovetz-rc6-rc5-vectors-00
+    fn bytes<const N: usize>(hex: &str) -> [u8; N] {
+        hex::decode(hex).unwrap().try_into().unwrap()
+    }
+
+    let cipher = rc5::<u16>::with_word_size(&bytes::<8>("0001020304050607"), 16);
+    assert_eq!(cipher.encrypt(bytes("00010203")), bytes("23a8d72e"));
+    assert_eq!(cipher.decrypt(bytes("23a8d72e")), bytes("00010203"));
+
+    let cipher = rc5::new(&bytes::<16>("000102030405060708090a0b0c0d0e0f"), 20);
+    assert_eq!(cipher.encrypt(bytes("0001020304050607")), bytes("2a0edc0e9431ff73"));
+    assert_eq!(cipher.decrypt(bytes("2a0edc0e9431ff73")), bytes("0001020304050607"));
+
+    let cipher = rc5::<u64>::with_word_size(
+        &bytes::<24>("000102030405060708090a0b0c0d0e0f1011121314151617"),
+        24,
+    );
+    assert_eq!(
+        cipher.encrypt(bytes("000102030405060708090a0b0c0d0e0f")),
+        bytes("a46772820edbce0235abea32ae7178da")
+    );
+    assert_eq!(
+        cipher.decrypt(bytes("a46772820edbce0235abea32ae7178da")),
+        bytes("000102030405060708090a0b0c0d0e0f")
+    );
+}
+
+#[test]
+fn rc5_64_ecb_round_trip() {
+    let plaintext = b"RC5 with 64-bit words has 128-bit blocks";
+
+    let mut ciphertext = Vec::new();
+    BlockEncryption::encrypt(rc5::<u64>::with_word_size(b"key", 16), EcbMode, plaintext, |b| {
+        ciphertext.push(b)
+    });
+    assert_eq!(ciphertext.len(), 48);
+
+    let mut decrypted = Vec::new();
+    BlockEncryption::decrypt(rc5::<u64>::with_word_size(b"key", 16), EcbMode, &ciphertext, |b| {
+        decrypted.push(b)
+    });
+    assert_eq!(decrypted, plaintext);
+}
+
+#[test]
+fn rc6_examples() {
+    // test vectors from the RC6 submission
+    #[track_caller]
+    fn check(key_hex: &str, plaintext_hex: &str, ciphertext_hex: &str) {
+        let cipher = rc6::from_hex(key_hex).unwrap();
+        let plaintext = <[u8; 16]>::try_from(hex::decode(plaintext_hex).unwrap()).unwrap();
+        let ciphertext = <[u8; 16]>::try_from(hex::decode(ciphertext_hex).unwrap()).unwrap();
+
+        assert_eq!(cipher.encrypt(plaintext), ciphertext);
+        assert_eq!(cipher.decrypt(ciphertext), plaintext);
+    }
+
+    let zero = "00000000000000000000000000000000";
+    let plaintext = "02132435465768798a9bacbdcedfe0f1";
+
+    check(zero, zero, "8fc3a53656b1f778c129df4e9848a41e");
+    check(
+        "0123456789abcdef0112233445566778",
+        plaintext,
+        "524e192f4715c6231f51f6367ea43f18",
+    );
+    check(&"00".repeat(24), zero, "6cd61bcb190b30384e8a3f168690ae82");
+    check(
+        "0123456789abcdef0112233445566778899aabbccddeeff0",
+        plaintext,
+        "688329d019e505041e52e92af95291d4",
+    );
+    check(&"00".repeat(32), zero, "8f5fbd0510d15fa893fa3fda6e857ec2");
+    check(
+        "0123456789abcdef0112233445566778899aabbccddeeff01032547698badcfe",
+        plaintext,
+        "c8241816f0d7e48920ad16a1674e5d48",
+    );
+}
+
+#[test]
+fn rc6_ecb_round_trip() {
+    let key = b"0123456789abcdef";
+    let plaintext = b"RC6 was an AES finalist";
+
+    let mut ciphertext = Vec::new();
+    BlockEncryption::encrypt(rc6::new(key, 20), EcbMode, plaintext, |b| ciphertext.push(b));
+    assert_eq!(ciphertext.len(), 32);
+
+    let mut decrypted = Vec::new();
+    BlockEncryption::decrypt(rc6::new(key, 20), EcbMode, &ciphertext, |b| decrypted.push(b));
+    assert_eq!(decrypted, plaintext);
+}
+
+#[test]
+fn spritz_mac_depends_on_key_message_and_length() {
+    // the paper has no MAC vectors, these were computed with a separate implementation that
+    // reproduces its stream and hash vectors
+    let mut tag = [0; 16];
+    spritz::mac(b"key", b"message", &mut tag);
+    assert_eq!(hex::encode(tag), "a9797b298fa9474c25327362fa2fd4ef");
+    let mut tag32 = [0; 32];
+    spritz::mac(b"arcfour", b"spam", &mut tag32);
+    assert_eq!(
+        hex::encode(tag32),
+        "08f17a6417a43f1f402d01208af361a5299d3af49fc525f534f2eb1f465c1475"
+    );
+
+    let mut other = [0; 16];
+    spritz::mac(b"kez", b"message", &mut other);
+    assert_ne!(tag, other);
+    spritz::mac(b"key", b"messagf", &mut other);
+    assert_ne!(tag, other);
+    spritz::mac(b"keym", b"essage", &mut other);
+    assert_ne!(tag, other);
+
+    let mut longer = [0; 17];
+    spritz::mac(b"key", b"message", &mut longer);
+    assert_ne!(&longer[..16], &tag);
+
+    let mut again = [0; 16];
+    spritz::mac(b"key", b"message", &mut again);
+    assert_eq!(tag, again);
+}
+
+#[test]
+fn spritz_absorb_and_squeeze_in_parts() {
+    let mut whole = spritz::new();
+    whole.absorb(b"some longer input that is absorbed at once");
+    let mut expected = [0; 40];
+    whole.squeeze(&mut expected);
+
+    let mut parts = spritz::new();
+    parts.absorb(b"some longer input ");
+    parts.absorb(b"that is absorbed at once");
+    let mut output = [0; 40];
+    parts.squeeze(&mut output[..15]);
+    parts.squeeze(&mut output[15..]);
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn spritz_iv_changes_key_stream() {
+    let plain = spritz::with_key(b"key").take(16).collect::<Vec<_>>();
+    let with_iv = spritz::with_key_and_iv(b"key", b"iv").take(16).collect::<Vec<_>>();
+    assert_ne!(plain, with_iv);
+
+    let ciphertext = spritz::with_key_and_iv(b"key", b"iv").encrypt(b"attack at dawn");
+    assert_eq!(spritz::with_key_and_iv(b"key", b"iv").decrypt(&ciphertext), b"attack at dawn");
+}
+
+#[test]
+fn rc4_drop_examples() {
+    let ciphertext = rc4::with_drop(b"Key", 3072).encrypt(b"Plaintext");
+    assert_eq!(rc4::with_drop(b"Key", 3072).decrypt(&ciphertext), b"Plaintext");
+    assert_ne!(ciphertext, rc4::new(b"Key").encrypt(b"Plaintext"));
+}
+
+#[test]
+fn rc4a_round_trip() {
+    assert_eq!(
+        rc4a::new(b"Key", b"Secret").decrypt(&rc4a::new(b"Key", b"Secret").encrypt(b"Plaintext")),
+        b"Plaintext"
+    );
+}
@@ -0,0 +1,184 @@
+use cryptography::aead::AuthenticationError;
+use cryptography::salsa::{hsalsa20, SecretBox, Salsa20, XSalsa20};
+use cryptography::StreamCipher;
+
+#[track_caller]
+fn check_key_stream(mut cipher: Salsa20, skip: usize, expected_hex: &str) {
+    let mut key_stream = vec![0; skip + expected_hex.len() / 2];
+    cipher.apply_keystream(&mut key_stream);
+    assert_eq!(hex::encode(&key_stream[skip..]), expected_hex);
+}
+
+#[test]
+fn salsa20_estream_examples() {
+    // eSTREAM verified test vectors, set 1, vector 0
+    check_key_stream(
+        Salsa20::try_new(&hex::decode("80000000000000000000000000000000").unwrap(), &[0; 8], 20)
+            .unwrap(),
+        0,
+        "4dfa5e481da23ea09a31022050859936da52fcee218005164f267cb65f5cfd7f\
+         2b4f97e0ff16924a52df269515110a07f9e460bc65ef95da58f740b7d1dbb0aa",
+    );
+
+    let mut key = [0; 32];
+    key[0] = 0x80;
+    check_key_stream(
+        Salsa20::new(&key, &[0; 8]),
+        0,
+        "e3be8fdd8beca2e3ea8ef9475b29a6e7003951e1097a5c38d23b7a5fad9f6844\
+         b22c97559e2723c7cbbd3fe4fc8d9a0744652a83e72a9c461876af4d7ef1a117",
+    );
+
+    let mut nonce = [0; 8];
+    nonce[0] = 0x80;
+    check_key_stream(
+        Salsa20::new(&[0; 32], &nonce),
+        0,
+        "2aba3dc45b4947007b14c851cd694456b303ad59a465662803006705673d6c3e\
+         29f1d3510dfc0405463c03414e0e07e359f1f1816c68b2434a19d3eee0464873",
+    );
+}
+
+#[test]
+fn salsa20_long_key_stream() {
+    let key = hex::decode("0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20")
+        .unwrap();
+    let nonce = hex::decode("0301040105090206").unwrap();
+    let expected = "6ebcbdbf76fccc64ab05542bee8a67cbc28fa2e141fbefbb3a2f9b221909c8d7\
+                    d4295258cb539770dd24d7ac3443769ffa27a50e60644264dc8b6b612683372e\
+                    085d0a12bf240b189ce2b78289862b56fdc9fcffc33bef9325a2e81b98fb3fb9\
+                    aa04cf434615ceffeb985c1cb08d8440e90b1d56ddeaea16d9e15affff1f698c\
+                    483c7a466af1fe062574adfd2b06a62b4d98440719ea776385c470349a7ed696\
+                    9583463ed5d26b8fefccb205da0f5bfa98c77812fe756b09eacc282aa42f4baf\
+                    a79633189046e2b20f35b3e0e54aa3b929e23c0f47dc7bcd4f928b2a9764be7d\
+                    4b8a50f980a50b35ad8087375e0c556ecbe6a7161e8653ce9391e1e6710ed4f1";
+
+    check_key_stream(Salsa20::try_new(&key, &nonce, 20).unwrap(), 0, expected);
+
+    let key_stream = Salsa20::try_new(&key, &nonce, 20)
+        .unwrap()
+        .take(256)
+        .collect::<Vec<_>>();
+    assert_eq!(hex::encode(key_stream), expected);
+
+    let mut cipher = Salsa20::try_new(&key, &nonce, 20).unwrap();
+    cipher.seek(3);
+    check_key_stream(cipher, 0, &expected[384..]);
+}
+
+#[test]
+fn salsa20_reduced_round_examples() {
+    let mut key = [0; 32];
+    key[0] = 0x80;
+    check_key_stream(
+        Salsa20::with_rounds(&key, &[0; 8], 8),
+        0,
+        "b1f599e9b0d96df436ae31f5ef589565b92d245db5a1d4c7a78e5e8d0146f8a4\
+         9d326c1a3bf50c052c9c8f114dc74972c4469591e31c9ed11927aa9871f38583",
+    );
+    check_key_stream(
+        Salsa20::with_rounds(&key, &[0; 8], 12),
+        0,
+        "afe411ed1c4e07e4d0cde3b33e31ec190fa4cc796a58bafb848ead8d07d02cd2\
+         d4b6f9f30cb0b57007e3733895cc8d1060107975acaeeb689b6cf614ab64a3d6",
+    );
+
+    let key = std::array::from_fn(|i| i as u8 + 1);
+    let nonce = [3, 1, 4, 1, 5, 9, 2, 6];
+    check_key_stream(
+        Salsa20::with_rounds(&key, &nonce, 8),
+        64,
+        "36bac56e784abba61601d0f82281328daba8d5b27fa27b253e0d6fadcd4dacc3\
+         4cef4e813861f056962a6c9a67ce48b11b542d4511de6215ed980e96b9b0dfef",
+    );
+    check_key_stream(
+        Salsa20::with_rounds(&key, &nonce, 12),
+        64,
+        "3c41ea2a202d1f0129c97fc329d3db39012b49646a0578f0e38383055f0648b8\
+         add2cdf39c7e0846dd89fe6cd42652e35aca634e8b51dff86b1402b3c8a31647",
+    );
+}
+
+#[test]
+fn hsalsa20_example() {
+    // NaCl tests/core1.c: the key derived from a Curve25519 shared secret
+    let shared = hex::decode("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742")
+        .unwrap();
+    let key = hsalsa20(shared.as_slice().try_into().unwrap(), &[0; 16]);
+    assert_eq!(
+        hex::encode(key),
+        "1b27556473e985d462cd51197a9a46c76009549eac6474f206c4ee0844f68389"
+    );
+}
+
+#[test]
+fn xsalsa20_examples() {
+    let key = *b"this is 32-byte key for xsalsa20";
+    let nonce = *b"24-byte nonce for xsalsa";
+
+    let key_stream = XSalsa20::new(&key, &nonce).take(64).collect::<Vec<_>>();
+    assert_eq!(
+        hex::encode(key_stream),
+        "4848297feb1fb52fb66d81609bd547fabcbe7026edc8b5e5e449d088bfa69c08\
+         8f5d8da1d791267c2c195a7f8cae9c4b4050d08ce6d3a151ec265f3a58e47648"
+    );
+
+    let ciphertext = XSalsa20::new(&key, &nonce).encrypt(b"Hello world!");
+    assert_eq!(hex::encode(&ciphertext), "002d4513843fc240c401e541");
+
+    let mut cipher = StreamCipher::new(XSalsa20::try_new(&key, &nonce).unwrap());
+    let mut output = Vec::new();
+    output.extend(cipher.encrypt(ciphertext[..5].iter().cloned()));
+    output.extend(cipher.encrypt(ciphertext[5..].iter().cloned()));
+    assert_eq!(output, b"Hello world!");
+}
+
+const NACL_KEY: &str = "1b27556473e985d462cd51197a9a46c76009549eac6474f206c4ee0844f68389";
+const NACL_NONCE: &str = "69696ee955b62b73cd62bda875fc73d68219e0036b7a0b37";
+const NACL_PLAINTEXT: &str = "\
+    be075fc53c81f2d5cf141316ebeb0c7b5228c52a4c62cbd44b66849b64244ffce5ecbaaf33bd751a\
+    1ac728d45e6c61296cdc3c01233561f41db66cce314adb310e3be8250c46f06dceea3a7fa1348057\
+    e2f6556ad6b1318a024a838f21af1fde048977eb48f59ffd4924ca1c60902e52f0a089bc76897040\
+    e082f937763848645e0705";
+const NACL_SEALED: &str = "\
+    f3ffc7703f9400e52a7dfb4b3d3305d98e993b9f48681273c29650ba32fc76ce48332ea7164d96a4\
+    476fb8c531a1186ac0dfc17c98dce87b4da7f011ec48c97271d2c20f9b928fe2270d6fb863d51738\
+    b48eeee314a7cc8ab932164548e526ae90224368517acfeabd6bb3732bc0e9da99832b61ca01b6de\
+    56244a9e88d5f9b37973f622a43d14a6599b1f654cb45a74e355a5";
+
+#[test]
+fn secretbox_nacl_example() {
+    // NaCl tests/secretbox.c and tests/secretbox2.c
+    let secret_box = SecretBox::from_hex(NACL_KEY).unwrap();
+    let nonce = hex::decode(NACL_NONCE).unwrap().try_into().unwrap();
+    let plaintext = hex::decode(NACL_PLAINTEXT).unwrap();
+
+    let sealed = secret_box.seal(&nonce, &plaintext);
+    assert_eq!(hex::encode(&sealed), NACL_SEALED);
+    assert_eq!(secret_box.open(&nonce, &sealed), Ok(plaintext));
+}
+
+#[test]
+fn secretbox_rejects_modifications() {
+    let secret_box = SecretBox::from_hex(NACL_KEY).unwrap();
+    let nonce: [u8; 24] = hex::decode(NACL_NONCE).unwrap().try_into().unwrap();
+    let sealed = hex::decode(NACL_SEALED).unwrap();
+
+    for i in [0, 15, 16, sealed.len() - 1] {
+        let mut modified = sealed.clone();
+        modified[i] ^= 0xaa;
+        assert_eq!(secret_box.open(&nonce, &modified), Err(AuthenticationError));
+    }
+
+    let mut other_nonce = nonce;
+    other_nonce[23] ^= 1;
+    assert_eq!(secret_box.open(&other_nonce, &sealed), Err(AuthenticationError));
+}
+
+#[test]
+fn secretbox_empty_message() {
+    let secret_box = SecretBox::new(&[7; 32]);
+    let sealed = secret_box.seal(&[1; 24], b"");
+    assert_eq!(sealed.len(), 16);
+    assert_eq!(secret_box.open(&[1; 24], &sealed), Ok(vec![]));
+}
@@ -0,0 +1,81 @@
+use cryptography::serpent::Serpent;
+use cryptography::BlockCipher;
+
+#[track_caller]
+fn check(key: &str, plaintext: &str, ciphertext: &str) {
+    let cipher = Serpent::from_hex(key).unwrap();
+    let plaintext = hex::decode(plaintext).unwrap().try_into().unwrap();
+    let ciphertext = hex::decode(ciphertext).unwrap().try_into().unwrap();
+    assert_eq!(cipher.encrypt(plaintext), ciphertext, "key = {key}");
+    assert_eq!(cipher.decrypt(ciphertext), plaintext, "key = {key}");
+}
+
+#[track_caller]
+fn check_iterated(key: &str, iterations: usize, expected: &str) {
+    let cipher = Serpent::from_hex(key).unwrap();
+    let mut block = [0; 16];
+    for _ in 0..iterations {
+        block = cipher.encrypt(block);
+    }
+    assert_eq!(hex::encode(block), expected, "key = {key}");
+}
+
+// Test vectors from NESSIE (Serpent-128-128, Serpent-192-128 and Serpent-256-128)
+
+const ZERO_BLOCK: &str = "00000000000000000000000000000000";
+const ZERO_KEY_128: &str = "00000000000000000000000000000000";
+const ZERO_KEY_192: &str = "000000000000000000000000000000000000000000000000";
+const ZERO_KEY_256: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[test]
+fn set_1_vector_0() {
+    check("80000000000000000000000000000000", ZERO_BLOCK, "264e5481eff42a4606abda06c0bfda3d");
+    check(
+        "800000000000000000000000000000000000000000000000",
+        ZERO_BLOCK,
+        "9e274ead9b737bb21efcfca548602689",
+    );
+    check(
+        "8000000000000000000000000000000000000000000000000000000000000000",
+        ZERO_BLOCK,
+        "a223aa1288463c0e2be38ebd825616c0",
+    );
+}
+
+#[test]
+fn set_2_vector_0() {
+    let plaintext = "80000000000000000000000000000000";
+    check(ZERO_KEY_128, plaintext, "a3b35de7c358ddd82644678c64b8bcbb");
+    check(ZERO_KEY_192, plaintext, "23f5f432ad687e0d4574c16459618abb");
+    check(ZERO_KEY_256, plaintext, "8314675e8ad5c3ecd83d852bcf7f566e");
+}
+
+#[test]
+fn set_3_vector_0() {
+    check(ZERO_KEY_128, ZERO_BLOCK, "3620b17ae6a993d09618b8768266bae9");
+    check(ZERO_KEY_192, ZERO_BLOCK, "a583ef976a292b406bbd5dc8256b0442");
+    check(ZERO_KEY_256, ZERO_BLOCK, "49672ba898d98df95019180445491089");
+}
+
+#[test]
+fn set_3_vector_0_iterated_1000_times() {
+    check_iterated(ZERO_KEY_128, 1000, "03158a530ed8835d808ebd795d5c918d");
+    check_iterated(ZERO_KEY_192, 1000, "85cc556df866ed82e230a57829dd803a");
+    check_iterated(ZERO_KEY_256, 1000, "d72ec2b7b93fbb567cefbab3fab43fb4");
+}
+
+#[test]
+fn set_4_vector_0() {
+    let plaintext = "00112233445566778899aabbccddeeff";
+    check("000102030405060708090a0b0c0d0e0f", plaintext, "563e2cf8740a27c164804560391e9b27");
+    check(
+        "000102030405060708090a0b0c0d0e0f1011121314151617",
+        plaintext,
+        "6ab816c82de53b93005008afa2246a02",
+    );
+    check(
+        "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        plaintext,
+        "2868b7a2d28ecd5e4fdefac3c4330074",
+    );
+}
@@ -1,4 +1,4 @@
-use cryptography::sha::{sha1, Hash160};
+use cryptography::sha::{sha1, Hash160, Sha1};
 use hex::FromHex;
 
 #[test]
@@ -33,3 +33,55 @@ fn large_message() {
         Hash160::from_hex("bf6db7112b56812702e99d48a7b1dab62d09b3f6").unwrap(),
     );
 }
+
+#[test]
+fn streaming_matches_one_shot_at_chunk_boundaries() {
+    let message = (0..=255).cycle().take(200).collect::<Vec<u8>>();
+    for len in [0, 1, 55, 56, 63, 64, 65, 119, 120, 128, 200] {
+        let message = &message[..len];
+        for split in [0, 1.min(len), len / 2, len.saturating_sub(1), len] {
+            let mut hasher = Sha1::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hasher.finalize(), sha1(message), "len = {len}, split = {split}");
+        }
+    }
+}
+
+#[test]
+fn million_a_one_byte_at_a_time() {
+    let mut hasher = Sha1::new();
+    for _ in 0..1_000_000 {
+        hasher.update(b"a");
+    }
+    assert_eq!(
+        hasher.finalize(),
+        Hash160::from_hex("34aa973cd4c4daa4f61eeb2bdbad27316534016f").unwrap(),
+    );
+}
+
+#[test]
+fn cloned_hasher_forks_the_state() {
+    let mut prefix = Sha1::new();
+    prefix.update(&[b'A'; 5000]);
+    let mut full = prefix.clone();
+    full.update(&[b'A'; 5000]);
+
+    assert_eq!(prefix.finalize(), sha1(&[b'A'; 5000]));
+    assert_eq!(
+        full.finalize(),
+        Hash160::from_hex("bf6db7112b56812702e99d48a7b1dab62d09b3f6").unwrap(),
+    );
+}
+
+#[test]
+fn reset_starts_over() {
+    let mut hasher = Sha1::new();
+    hasher.update(b"something else");
+    hasher.reset();
+    hasher.update(b"A");
+    assert_eq!(
+        hasher.finalize(),
+        Hash160::from_hex("6dcd4ce23d88e2ee9568ba546c007c63d9131c1b").unwrap(),
+    );
+}
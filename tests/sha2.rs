@@ -1,4 +1,8 @@
-use cryptography::sha::{sha224, sha256, sha384, sha512, Hash224, Hash256, Hash384, Hash512};
+use cryptography::sha::{
+    sha224, sha256, sha384, sha512, sha512_224, sha512_256, sha512_t, sha512_t_initial_hash,
+    Hash224, Hash256, Hash384, Hash512, Sha224, Sha256, Sha384, Sha512, Sha512T, Sha512_224,
+    Sha512_256,
+};
 use hex::FromHex;
 
 #[test]
@@ -45,3 +49,175 @@ fn empty_string_512() {
             .unwrap(),
     );
 }
+
+/// The two-block message of the FIPS 180-4 examples for SHA-384 and SHA-512.
+const TWO_BLOCK_MESSAGE: &[u8] = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+
+#[test]
+fn fips_examples_512_224() {
+    assert_eq!(
+        sha512_224(b"abc"),
+        Hash224::from_hex("4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa").unwrap(),
+    );
+    assert_eq!(
+        sha512_224(TWO_BLOCK_MESSAGE),
+        Hash224::from_hex("23fec5bb94d60b23308192640b0c453335d664734fe40e7268674af9").unwrap(),
+    );
+    assert_eq!(
+        sha512_224(b""),
+        Hash224::from_hex("6ed0dd02806fa89e25de060c19d3ac86cabb87d6a0ddd05c333b84f4").unwrap(),
+    );
+}
+
+#[test]
+fn fips_examples_512_256() {
+    assert_eq!(
+        sha512_256(b"abc"),
+        Hash256::from_hex("53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23")
+            .unwrap(),
+    );
+    assert_eq!(
+        sha512_256(TWO_BLOCK_MESSAGE),
+        Hash256::from_hex("3928e184fb8690f840da3988121d31be65cb9d3ef83ee6146feac861e19b563a")
+            .unwrap(),
+    );
+}
+
+#[test]
+fn sha512_t_initial_hashes() {
+    // FIPS 180-4, section 5.3.6.1 and 5.3.6.2
+    assert_eq!(
+        sha512_t_initial_hash(224),
+        Some([
+            0x8c3d37c819544da2, 0x73e1996689dcd4d6, 0x1dfab7ae32ff9c82, 0x679dd514582f9fcf,
+            0x0f6d2b697bd44da8, 0x77e36f7304c48942, 0x3f9d85a86a1d36c8, 0x1112e6ad91d692a1,
+        ])
+    );
+    assert_eq!(
+        sha512_t_initial_hash(256),
+        Some([
+            0x22312194fc2bf72c, 0x9f555fa3c84c64c2, 0x2393b86b6f53b151, 0x963877195940eabd,
+            0x96283ee2a88effe3, 0xbe5e1e2553863992, 0x2b0199fc2c85b8aa, 0x0eb72ddc81c52ca2,
+        ])
+    );
+    // other lengths give unrelated initial hashes
+    assert_ne!(sha512_t_initial_hash(192), sha512_t_initial_hash(224));
+
+    for t in [0, 384, 512, 1024] {
+        assert_eq!(sha512_t_initial_hash(t), None, "t = {t}");
+        assert!(Sha512T::try_new(t).is_none(), "t = {t}");
+    }
+}
+
+#[test]
+fn sha512_t_of_any_length() {
+    // the named truncations are special cases
+    assert_eq!(sha512_t(224, b"abc"), <[u8; 28]>::from(sha512_224(b"abc")));
+    assert_eq!(sha512_t(256, b"abc"), <[u8; 32]>::from(sha512_256(b"abc")));
+
+    // computed with a separate implementation that gives the same SHA-512/224 and SHA-512/256
+    assert_eq!(
+        hex::encode(sha512_t(192, b"abc")),
+        "6c4cb5b80909c1f4858dd872ababebce67bc9a3ea8e9866c"
+    );
+    assert_eq!(hex::encode(sha512_t(8, b"abc")), "c5");
+    // the last byte is cut off after 4 bits
+    assert_eq!(hex::encode(sha512_t(12, b"abc")), "1ac0");
+
+    let mut hasher = Sha512T::new(192);
+    hasher.update(b"a");
+    hasher.update(b"bc");
+    assert_eq!(hasher.finalize(), sha512_t(192, b"abc"));
+}
+
+#[test]
+fn streaming_matches_one_shot_at_chunk_boundaries() {
+    let message = (0..=255).cycle().take(300).collect::<Vec<u8>>();
+    // lengths around where the padding needs an extra chunk, and around whole chunks
+    for len in [0, 1, 55, 56, 63, 64, 65, 111, 112, 127, 128, 129, 191, 192, 256, 300] {
+        let message = &message[..len];
+        for split in [0, 1.min(len), len / 2, len.saturating_sub(1), len] {
+            let (a, b) = message.split_at(split);
+
+            let mut hasher = Sha224::new();
+            hasher.update(a);
+            hasher.update(b);
+            assert_eq!(hasher.finalize(), sha224(message), "len = {len}, split = {split}");
+
+            let mut hasher = Sha256::new();
+            hasher.update(a);
+            hasher.update(b);
+            assert_eq!(hasher.finalize(), sha256(message), "len = {len}, split = {split}");
+
+            let mut hasher = Sha384::new();
+            hasher.update(a);
+            hasher.update(b);
+            assert_eq!(hasher.finalize(), sha384(message), "len = {len}, split = {split}");
+
+            let mut hasher = Sha512::new();
+            hasher.update(a);
+            hasher.update(b);
+            assert_eq!(hasher.finalize(), sha512(message), "len = {len}, split = {split}");
+
+            let mut hasher = Sha512_224::new();
+            hasher.update(a);
+            hasher.update(b);
+            assert_eq!(hasher.finalize(), sha512_224(message), "len = {len}, split = {split}");
+
+            let mut hasher = Sha512_256::new();
+            hasher.update(a);
+            hasher.update(b);
+            assert_eq!(hasher.finalize(), sha512_256(message), "len = {len}, split = {split}");
+        }
+    }
+}
+
+#[test]
+fn million_a_in_uneven_pieces() {
+    let mut sha256_hasher = Sha256::new();
+    let mut sha512_hasher = Sha512::new();
+    let mut remaining = 1_000_000;
+    for piece in [1, 63, 64, 65, 127, 128, 129, 997].into_iter().cycle() {
+        let n = piece.min(remaining);
+        sha256_hasher.update(&vec![b'a'; n]);
+        sha512_hasher.update(&vec![b'a'; n]);
+        remaining -= n;
+        if remaining == 0 {
+            break;
+        }
+    }
+
+    assert_eq!(
+        sha256_hasher.finalize(),
+        Hash256::from_hex("cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0")
+            .unwrap(),
+    );
+    assert_eq!(
+        sha512_hasher.finalize(),
+        Hash512::from_hex("e718483d0ce769644e2e42c7bc15b4638e1f98b13b2044285632a803afa973ebde0ff244877ea60a4cb0432ce577c31beb009c5c2c49aa2e4eadb217ad8cc09b")
+            .unwrap(),
+    );
+}
+
+#[test]
+fn cloned_hasher_forks_the_state() {
+    let mut prefix = Sha384::new();
+    prefix.update(b"The quick brown fox ");
+
+    let mut dog = prefix.clone();
+    dog.update(b"jumps over the lazy dog");
+    let mut cat = prefix;
+    cat.update(b"jumps over the lazy cat");
+
+    assert_eq!(dog.finalize(), sha384(b"The quick brown fox jumps over the lazy dog"));
+    assert_eq!(cat.finalize(), sha384(b"The quick brown fox jumps over the lazy cat"));
+}
+
+#[test]
+fn reset_starts_over() {
+    let mut hasher = Sha256::new();
+    hasher.update(&[0; 100]);
+    hasher.reset();
+    hasher.update(b"abc");
+    assert_eq!(hasher.finalize(), sha256(b"abc"));
+}
@@ -0,0 +1,227 @@
+use cryptography::sha::{Hash224, Hash256, Hash384, Hash512};
+use cryptography::sha3::{
+    keccak224, keccak256, keccak384, keccak512, sha3_224, sha3_256, sha3_384, sha3_512, shake128,
+    shake256, Keccak256, Sha3_256, Sha3_512, Shake128, Shake256,
+};
+use hex::FromHex;
+
+/// The 1600-bit message of the NIST examples, 200 bytes of 0xa3.
+const A3_MESSAGE: [u8; 200] = [0xa3; 200];
+
+#[test]
+fn empty_string() {
+    assert_eq!(
+        sha3_224(b""),
+        Hash224::from_hex("6b4e03423667dbb73b6e15454f0eb1abd4597f9a1b078e3f5b5a6bc7").unwrap(),
+    );
+    assert_eq!(
+        sha3_256(b""),
+        Hash256::from_hex("a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a")
+            .unwrap(),
+    );
+    assert_eq!(
+        sha3_384(b""),
+        Hash384::from_hex("0c63a75b845e4f7d01107d852e4c2485c51a50aaaa94fc61995e71bbee983a2ac3713831264adb47fb6bd1e058d5f004")
+            .unwrap(),
+    );
+    assert_eq!(
+        sha3_512(b""),
+        Hash512::from_hex("a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a615b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26")
+            .unwrap(),
+    );
+}
+
+#[test]
+fn nist_examples_abc() {
+    assert_eq!(
+        sha3_224(b"abc"),
+        Hash224::from_hex("e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf").unwrap(),
+    );
+    assert_eq!(
+        sha3_256(b"abc"),
+        Hash256::from_hex("3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532")
+            .unwrap(),
+    );
+    assert_eq!(
+        sha3_384(b"abc"),
+        Hash384::from_hex("ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b298d88cea927ac7f539f1edf228376d25")
+            .unwrap(),
+    );
+    assert_eq!(
+        sha3_512(b"abc"),
+        Hash512::from_hex("b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0")
+            .unwrap(),
+    );
+}
+
+#[test]
+fn nist_examples_1600_bits() {
+    assert_eq!(
+        sha3_224(&A3_MESSAGE),
+        Hash224::from_hex("9376816aba503f72f96ce7eb65ac095deee3be4bf9bbc2a1cb7e11e0").unwrap(),
+    );
+    assert_eq!(
+        sha3_256(&A3_MESSAGE),
+        Hash256::from_hex("79f38adec5c20307a98ef76e8324afbfd46cfd81b22e3973c65fa1bd9de31787")
+            .unwrap(),
+    );
+    assert_eq!(
+        sha3_384(&A3_MESSAGE),
+        Hash384::from_hex("1881de2ca7e41ef95dc4732b8f5f002b189cc1e42b74168ed1732649ce1dbcdd76197a31fd55ee989f2d7050dd473e8f")
+            .unwrap(),
+    );
+    assert_eq!(
+        sha3_512(&A3_MESSAGE),
+        Hash512::from_hex("e76dfad22084a8b1467fcf2ffa58361bec7628edf5f3fdc0e4805dc48caeeca81b7c13c30adf52a3659584739a2df46be589c51ca1a4a8416df6545a1ce8ba00")
+            .unwrap(),
+    );
+}
+
+#[test]
+fn padding_at_rate_boundary() {
+    // 135 bytes leave room for a single byte of padding, 136 bytes need a whole padding block
+    assert_eq!(
+        sha3_256(&[0; 135]),
+        Hash256::from_hex("7d080d7ba978a75c8a7d1f9be566c859084509c9c2b4928435c225d5777d98e3")
+            .unwrap(),
+    );
+    assert_eq!(
+        sha3_256(&[0; 136]),
+        Hash256::from_hex("e772c9cf9eb9c991cdfcf125001b454fdbc0a95f188d1b4c844aa032ad6e075e")
+            .unwrap(),
+    );
+}
+
+#[test]
+fn keccak_original_padding() {
+    assert_eq!(
+        keccak256(b""),
+        Hash256::from_hex("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+            .unwrap(),
+    );
+    assert_eq!(
+        keccak224(b"abc"),
+        Hash224::from_hex("c30411768506ebe1c2871b1ee2e87d38df342317300a9b97a95ec6a8").unwrap(),
+    );
+    assert_eq!(
+        keccak256(b"abc"),
+        Hash256::from_hex("4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45")
+            .unwrap(),
+    );
+    assert_eq!(
+        keccak384(b"abc"),
+        Hash384::from_hex("f7df1165f033337be098e7d288ad6a2f74409d7a60b49c36642218de161b1f99f8c681e4afaf31a34db29fb763e3c28e")
+            .unwrap(),
+    );
+    assert_eq!(
+        keccak512(b"abc"),
+        Hash512::from_hex("18587dc2ea106b9a1563e32b3312421ca164c7f1f07bc922a9c83d77cea3a1e5d0c69910739025372dc14ac9642629379540c17e2a65b19d77aa511a9d00bb96")
+            .unwrap(),
+    );
+}
+
+#[test]
+fn shake_empty_string() {
+    let mut output = [0; 32];
+    shake128(b"", &mut output);
+    assert_eq!(
+        hex::encode(output),
+        "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26"
+    );
+
+    let mut output = [0; 64];
+    shake256(b"", &mut output);
+    assert_eq!(
+        hex::encode(output),
+        "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762fd75dc4ddd8c0f200cb05019d67b592f6fc821c49479ab48640292eacb3b7c4be"
+    );
+}
+
+#[test]
+fn shake_long_output() {
+    // the last 32 bytes of the 4096-bit outputs in the NIST examples
+    let mut output = [0; 512];
+    shake128(&A3_MESSAGE, &mut output);
+    assert_eq!(
+        hex::encode(&output[480..]),
+        "44c9fb359fd56ac0a9a75a743cff6862f17d7259ab075216c0699511643b6439"
+    );
+
+    shake256(&A3_MESSAGE, &mut output);
+    assert_eq!(
+        hex::encode(&output[480..]),
+        "6a1a9d7846436e4dca5728b6f760eef0ca92bf0be5615e96959d767197a0beeb"
+    );
+}
+
+#[test]
+fn shake_reads_in_pieces() {
+    let mut expected = [0; 400];
+    shake128(b"abc", &mut expected);
+
+    let mut xof = Shake128::new();
+    xof.update(b"abc");
+    let mut reader = xof.finalize();
+    let mut output = [0; 400];
+    // pieces that end before, at and after the rate of 168 bytes
+    for range in [0..1, 1..168, 168..200, 200..400] {
+        reader.read(&mut output[range]);
+    }
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn streaming_matches_one_shot() {
+    for len in [0, 1, 71, 72, 73, 135, 136, 137, 300] {
+        let message: Vec<u8> = (0..len).map(|i| i as u8).collect();
+        for split in [0, 1.min(len), len / 2, len] {
+            let mut hasher = Sha3_256::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(
+                hasher.finalize(),
+                sha3_256(&message),
+                "{len} bytes split at {split}"
+            );
+
+            let mut hasher = Sha3_512::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(
+                hasher.finalize(),
+                sha3_512(&message),
+                "{len} bytes split at {split}"
+            );
+
+            let mut hasher = Keccak256::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(
+                hasher.finalize(),
+                keccak256(&message),
+                "{len} bytes split at {split}"
+            );
+
+            let mut xof = Shake256::new();
+            xof.update(&message[..split]);
+            xof.update(&message[split..]);
+            let (mut a, mut b) = ([0; 100], [0; 100]);
+            xof.finalize().read(&mut a);
+            shake256(&message, &mut b);
+            assert_eq!(a, b, "{len} bytes split at {split}");
+        }
+    }
+}
+
+#[test]
+fn reset_and_clone() {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"ab");
+    let fork = hasher.clone();
+    hasher.update(b"c");
+    assert_eq!(hasher.clone().finalize(), sha3_256(b"abc"));
+    assert_eq!(fork.finalize(), sha3_256(b"ab"));
+
+    hasher.reset();
+    assert_eq!(hasher.finalize(), sha3_256(b""));
+}
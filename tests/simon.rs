@@ -0,0 +1,64 @@
+use cryptography::simon::Simon;
+use cryptography::BlockCipher;
+
+/// The key, plaintext and ciphertext are written as words with the most significant word first,
+/// as in the paper. In memory, the words are stored in reverse order in little-endian byte order,
+/// so the byte order of the whole value is reversed.
+#[track_caller]
+fn check<const N: usize>(key: &str, plaintext: &str, ciphertext: &str) {
+    let reversed = |s: &str| hex::decode(s.replace(' ', "")).unwrap().into_iter().rev().collect::<Vec<_>>();
+
+    let cipher = Simon::<N>::new(&reversed(key));
+    let plaintext: [u8; N] = reversed(plaintext).try_into().unwrap();
+    let ciphertext: [u8; N] = reversed(ciphertext).try_into().unwrap();
+    assert_eq!(cipher.encrypt(plaintext), ciphertext, "key = {key}");
+    assert_eq!(cipher.decrypt(ciphertext), plaintext, "key = {key}");
+}
+
+// Test vectors from "The SIMON and SPECK Families of Lightweight Block Ciphers", appendix B
+
+#[test]
+fn simon_32() {
+    check::<4>("1918 1110 0908 0100", "6565 6877", "c69b e9bb");
+}
+
+#[test]
+fn simon_48() {
+    check::<6>("121110 0a0908 020100", "612067 6e696c", "dae5ac 292cac");
+    check::<6>("1a1918 121110 0a0908 020100", "726963 20646e", "6e06a5 acf156");
+}
+
+#[test]
+fn simon_64() {
+    check::<8>("13121110 0b0a0908 03020100", "6f722067 6e696c63", "5ca2e27f 111a8fc8");
+    check::<8>("1b1a1918 13121110 0b0a0908 03020100", "656b696c 20646e75", "44c8fc20 b9dfa07a");
+}
+
+#[test]
+fn simon_96() {
+    check::<12>("0d0c0b0a0908 050403020100", "2072616c6c69 702065687420", "602807a462b4 69063d8ff082");
+    check::<12>(
+        "151413121110 0d0c0b0a0908 050403020100",
+        "746168742074 73756420666f",
+        "ecad1c6c451e 3f59c5db1ae9",
+    );
+}
+
+#[test]
+fn simon_128() {
+    check::<16>(
+        "0f0e0d0c0b0a0908 0706050403020100",
+        "6373656420737265 6c6c657661727420",
+        "49681b1e1e54fe3f 65aa832af84e0bbc",
+    );
+    check::<16>(
+        "1716151413121110 0f0e0d0c0b0a0908 0706050403020100",
+        "206572656874206e 6568772065626972",
+        "c4ac61effcdc0d4f 6c9c8d6e2597b85b",
+    );
+    check::<16>(
+        "1f1e1d1c1b1a1918 1716151413121110 0f0e0d0c0b0a0908 0706050403020100",
+        "74206e69206d6f6f 6d69732061207369",
+        "8d2b5579afc8a3a0 3bf72a87efe7b868",
+    );
+}
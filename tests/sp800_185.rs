@@ -0,0 +1,252 @@
+use cryptography::sha3::{
+    cshake128, cshake256, kmac128, kmac256, parallel_hash128, parallel_hash256, shake128,
+    tuple_hash128, tuple_hash256, CShake128, Kmac128, Kmac256, ParallelHash128, TupleHash128,
+};
+
+/// The key of the KMAC samples.
+const KEY: [u8; 32] = [
+    0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0x4a, 0x4b, 0x4c, 0x4d, 0x4e, 0x4f,
+    0x50, 0x51, 0x52, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, 0x59, 0x5a, 0x5b, 0x5c, 0x5d, 0x5e, 0x5f,
+];
+
+/// The long message of the samples, the bytes 00 to c7.
+fn long_message() -> Vec<u8> {
+    (0..200).collect()
+}
+
+#[track_caller]
+fn check(f: impl FnOnce(&mut [u8]), expected_hex: &str) {
+    let mut output = vec![0; expected_hex.len() / 2];
+    f(&mut output);
+    assert_eq!(hex::encode(output), expected_hex);
+}
+
+#[test]
+fn cshake_samples() {
+    check(
+        |out| cshake128(&[0, 1, 2, 3], b"", b"Email Signature", out),
+        "c1c36925b6409a04f1b504fcbca9d82b4017277cb5ed2b2065fc1d3814d5aaf5",
+    );
+    check(
+        |out| cshake128(&long_message(), b"", b"Email Signature", out),
+        "c5221d50e4f822d96a2e8881a961420f294b7b24fe3d2094baed2c6524cc166b",
+    );
+    check(
+        |out| cshake256(&[0, 1, 2, 3], b"", b"Email Signature", out),
+        "d008828e2b80ac9d2218ffee1d070c48b8e4c87bff32c9699d5b6896eee0edd164020e2be0560858d9c00c037e34a96937c561a74c412bb4c746469527281c8c",
+    );
+    check(
+        |out| cshake256(&long_message(), b"", b"Email Signature", out),
+        "07dc27b11e51fbac75bc7b3c1d983e8b4b85fb1defaf218912ac86430273091727f42b17ed1df63e8ec118f04b23633c1dfb1574c8fb55cb45da8e25afb092bb",
+    );
+}
+
+#[test]
+fn cshake_without_strings_is_shake() {
+    let (mut a, mut b) = ([0; 300], [0; 300]);
+    let mut xof = CShake128::new(b"", b"");
+    xof.update(b"abc");
+    xof.finalize().read(&mut a);
+    shake128(b"abc", &mut b);
+    assert_eq!(a, b);
+
+    // a function name alone is enough to change the output
+    cshake128(b"abc", b"KMAC", b"", &mut a);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn kmac_samples() {
+    check(
+        |out| kmac128(&KEY, &[0, 1, 2, 3], b"", out),
+        "e5780b0d3ea6f7d3a429c5706aa43a00fadbd7d49628839e3187243f456ee14e",
+    );
+    check(
+        |out| kmac128(&KEY, &[0, 1, 2, 3], b"My Tagged Application", out),
+        "3b1fba963cd8b0b59e8c1a6d71888b7143651af8ba0a7070c0979e2811324aa5",
+    );
+    check(
+        |out| kmac128(&KEY, &long_message(), b"My Tagged Application", out),
+        "1f5b4e6cca02209e0dcb5ca635b89a15e271ecc760071dfd805faa38f9729230",
+    );
+    check(
+        |out| kmac256(&KEY, &[0, 1, 2, 3], b"My Tagged Application", out),
+        "20c570c31346f703c9ac36c61c03cb64c3970d0cfc787e9b79599d273a68d2f7f69d4cc3de9d104a351689f27cf6f5951f0103f33f4f24871024d9c27773a8dd",
+    );
+    check(
+        |out| kmac256(&KEY, &long_message(), b"", out),
+        "75358cf39e41494e949707927cee0af20a3ff553904c86b08f21cc414bcfd691589d27cf5e15369cbbff8b9a4c2eb17800855d0235ff635da82533ec6b759b69",
+    );
+    check(
+        |out| kmac256(&KEY, &long_message(), b"My Tagged Application", out),
+        "b58618f71f92e1d56c1b8c55ddd7cd188b97b4ca4d99831eb2699a837da2e4d970fbacfde50033aea585f1a2708510c32d07880801bd182898fe476876fc8965",
+    );
+}
+
+#[test]
+fn kmac_xof_samples() {
+    #[track_caller]
+    fn check_xof128(message: &[u8], customization: &[u8], expected_hex: &str) {
+        let mut mac = Kmac128::new(&KEY, customization);
+        mac.update(message);
+        check(|out| mac.finalize_xof().read(out), expected_hex);
+    }
+
+    #[track_caller]
+    fn check_xof256(message: &[u8], customization: &[u8], expected_hex: &str) {
+        let mut mac = Kmac256::new(&KEY, customization);
+        mac.update(message);
+        check(|out| mac.finalize_xof().read(out), expected_hex);
+    }
+
+    check_xof128(
+        &[0, 1, 2, 3],
+        b"",
+        "cd83740bbd92ccc8cf032b1481a0f4460e7ca9dd12b08a0c4031178bacd6ec35",
+    );
+    check_xof128(
+        &[0, 1, 2, 3],
+        b"My Tagged Application",
+        "31a44527b4ed9f5c6101d11de6d26f0620aa5c341def41299657fe9df1a3b16c",
+    );
+    check_xof128(
+        &long_message(),
+        b"My Tagged Application",
+        "47026c7cd793084aa0283c253ef658490c0db61438b8326fe9bddf281b83ae0f",
+    );
+    check_xof256(
+        &[0, 1, 2, 3],
+        b"My Tagged Application",
+        "1755133f1534752aad0748f2c706fb5c784512cab835cd15676b16c0c6647fa96faa7af634a0bf8ff6df39374fa00fad9a39e322a7c92065a64eb1fb0801eb2b",
+    );
+    check_xof256(
+        &long_message(),
+        b"",
+        "ff7b171f1e8a2b24683eed37830ee797538ba8dc563f6da1e667391a75edc02ca633079f81ce12a25f45615ec89972031d18337331d24ceb8f8ca8e6a19fd98b",
+    );
+    check_xof256(
+        &long_message(),
+        b"My Tagged Application",
+        "d5be731c954ed7732846bb59dbe3a8e30f83e77a4bff4459f2f1c2b4ecebb8ce67ba01c62e8ab8578d2d499bd1bb276768781190020a306a97de281dcc30305d",
+    );
+}
+
+#[test]
+fn kmac_verify() {
+    let mut tag = [0; 32];
+    kmac128(&KEY, b"message", b"", &mut tag);
+
+    let mut mac = Kmac128::new(&KEY, b"");
+    mac.update(b"message");
+    assert!(mac.clone().verify(&tag));
+    // a prefix of the tag is not the tag of the shorter length
+    assert!(!mac.clone().verify(&tag[..16]));
+    assert!(!mac.clone().verify(&[]));
+
+    // tags of at least 10 bytes are accepted, shorter ones are rejected even if they are correct
+    let mut short_tag = [0; 10];
+    mac.clone().finalize(&mut short_tag);
+    assert!(mac.clone().verify(&short_tag));
+    let mut too_short_tag = [0; 9];
+    mac.clone().finalize(&mut too_short_tag);
+    assert!(!mac.clone().verify(&too_short_tag));
+    let mut one_byte_tag = [0; 1];
+    mac.clone().finalize(&mut one_byte_tag);
+    assert!(!mac.clone().verify(&one_byte_tag));
+
+    tag[31] ^= 1;
+    assert!(!mac.verify(&tag));
+}
+
+#[test]
+fn tuple_hash_samples() {
+    let te3: &[u8] = &[0x00, 0x01, 0x02];
+    let te6: &[u8] = &[0x10, 0x11, 0x12, 0x13, 0x14, 0x15];
+    let te9: &[u8] = &[0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28];
+
+    check(
+        |out| tuple_hash128(&[te3, te6], b"", out),
+        "c5d8786c1afb9b82111ab34b65b2c0048fa64e6d48e263264ce1707d3ffc8ed1",
+    );
+    check(
+        |out| tuple_hash128(&[te3, te6], b"My Tuple App", out),
+        "75cdb20ff4db1154e841d758e24160c54bae86eb8c13e7f5f40eb35588e96dfb",
+    );
+    check(
+        |out| tuple_hash128(&[te3, te6, te9], b"My Tuple App", out),
+        "e60f202c89a2631eda8d4c588ca5fd07f39e5151998deccf973adb3804bb6e84",
+    );
+    check(
+        |out| tuple_hash256(&[te3, te6], b"", out),
+        "cfb7058caca5e668f81a12a20a2195ce97a925f1dba3e7449a56f82201ec607311ac2696b1ab5ea2352df1423bde7bd4bb78c9aed1a853c78672f9eb23bbe194",
+    );
+    check(
+        |out| tuple_hash256(&[te3, te6], b"My Tuple App", out),
+        "147c2191d5ed7efd98dbd96d7ab5a11692576f5fe2a5065f3e33de6bba9f3aa1c4e9a068a289c61c95aab30aee1e410b0b607de3620e24a4e3bf9852a1d4367e",
+    );
+    check(
+        |out| tuple_hash256(&[te3, te6, te9], b"My Tuple App", out),
+        "45000be63f9b6bfd89f54717670f69a9bc763591a4f05c50d68891a744bcc6e7d6d5b5e82c018da999ed35b0bb49c9678e526abd8e85c13ed254021db9e790ce",
+    );
+}
+
+#[test]
+fn tuple_hash_separates_items() {
+    let mut a = [0; 32];
+    let mut b = [0; 32];
+    tuple_hash128(&[b"ab", b"c"], b"", &mut a);
+    tuple_hash128(&[b"a", b"bc"], b"", &mut b);
+    assert_ne!(a, b);
+
+    let mut hasher = TupleHash128::new(b"");
+    hasher.push(b"a");
+    hasher.push(b"bc");
+    hasher.finalize(&mut a);
+    assert_eq!(a, b);
+}
+
+/// The message of the ParallelHash samples, three blocks of 8 bytes.
+const PARALLEL_MESSAGE: [u8; 24] = [
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+    0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
+];
+
+#[test]
+fn parallel_hash_samples() {
+    check(
+        |out| parallel_hash128(&PARALLEL_MESSAGE, 8, b"", out),
+        "ba8dc1d1d979331d3f813603c67f72609ab5e44b94a0b8f9af46514454a2b4f5",
+    );
+    check(
+        |out| parallel_hash128(&PARALLEL_MESSAGE, 8, b"Parallel Data", out),
+        "fc484dcb3f84dceedc353438151bee58157d6efed0445a81f165e495795b7206",
+    );
+    check(
+        |out| parallel_hash256(&PARALLEL_MESSAGE, 8, b"", out),
+        "bc1ef124da34495e948ead207dd9842235da432d2bbc54b4c110e64c451105531b7f2a3e0ce055c02805e7c2de1fb746af97a1dd01f43b824e31b87612410429",
+    );
+    check(
+        |out| parallel_hash256(&PARALLEL_MESSAGE, 8, b"Parallel Data", out),
+        "cdf15289b54f6212b4bc270528b49526006dd9b54e2b6add1ef6900dda3963bb33a72491f236969ca8afaea29c682d47a393c065b38e29fae651a2091c833110",
+    );
+}
+
+#[test]
+fn parallel_hash_streaming() {
+    let message: Vec<u8> = (0..1000).map(|i| i as u8).collect();
+    for block_size in [1, 7, 64, 1000, 4096] {
+        let mut expected = [0; 32];
+        parallel_hash128(&message, block_size, b"", &mut expected);
+        for split in [1, 13, 500, 999] {
+            let mut hasher = ParallelHash128::new(block_size, b"");
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            let mut output = [0; 32];
+            hasher.finalize(&mut output);
+            assert_eq!(
+                output, expected,
+                "block size {block_size}, split at {split}"
+            );
+        }
+    }
+}
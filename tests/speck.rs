@@ -0,0 +1,64 @@
+use cryptography::speck::Speck;
+use cryptography::BlockCipher;
+
+/// The key, plaintext and ciphertext are written as words with the most significant word first,
+/// as in the paper. In memory, the words are stored in reverse order in little-endian byte order,
+/// so the byte order of the whole value is reversed.
+#[track_caller]
+fn check<const N: usize>(key: &str, plaintext: &str, ciphertext: &str) {
+    let reversed = |s: &str| hex::decode(s.replace(' ', "")).unwrap().into_iter().rev().collect::<Vec<_>>();
+
+    let cipher = Speck::<N>::new(&reversed(key));
+    let plaintext: [u8; N] = reversed(plaintext).try_into().unwrap();
+    let ciphertext: [u8; N] = reversed(ciphertext).try_into().unwrap();
+    assert_eq!(cipher.encrypt(plaintext), ciphertext, "key = {key}");
+    assert_eq!(cipher.decrypt(ciphertext), plaintext, "key = {key}");
+}
+
+// Test vectors from "The SIMON and SPECK Families of Lightweight Block Ciphers", appendix C
+
+#[test]
+fn speck_32() {
+    check::<4>("1918 1110 0908 0100", "6574 694c", "a868 42f2");
+}
+
+#[test]
+fn speck_48() {
+    check::<6>("121110 0a0908 020100", "20796c 6c6172", "c049a5 385adc");
+    check::<6>("1a1918 121110 0a0908 020100", "6d2073 696874", "735e10 b6445d");
+}
+
+#[test]
+fn speck_64() {
+    check::<8>("13121110 0b0a0908 03020100", "74614620 736e6165", "9f7952ec 4175946c");
+    check::<8>("1b1a1918 13121110 0b0a0908 03020100", "3b726574 7475432d", "8c6fa548 454e028b");
+}
+
+#[test]
+fn speck_96() {
+    check::<12>("0d0c0b0a0908 050403020100", "65776f68202c 656761737520", "9e4d09ab7178 62bdde8f79aa");
+    check::<12>(
+        "151413121110 0d0c0b0a0908 050403020100",
+        "656d6974206e 69202c726576",
+        "2bf31072228a 7ae440252ee6",
+    );
+}
+
+#[test]
+fn speck_128() {
+    check::<16>(
+        "0f0e0d0c0b0a0908 0706050403020100",
+        "6c61766975716520 7469206564616d20",
+        "a65d985179783265 7860fedf5c570d18",
+    );
+    check::<16>(
+        "1716151413121110 0f0e0d0c0b0a0908 0706050403020100",
+        "7261482066656968 43206f7420746e65",
+        "1be4cf3a13135566 f9bc185de03c1886",
+    );
+    check::<16>(
+        "1f1e1d1c1b1a1918 1716151413121110 0f0e0d0c0b0a0908 0706050403020100",
+        "65736f6874206e49 202e72656e6f6f70",
+        "4109010405c0f53e 4eeeb48d9c188f43",
+    );
+}
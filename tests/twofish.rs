@@ -0,0 +1,81 @@
+use cryptography::twofish::Twofish;
+use cryptography::BlockCipher;
+
+/// Runs the iterated known answer test from the Twofish submission (ECB_TBL.TXT), where each
+/// ciphertext becomes the next plaintext and the previous plaintext becomes part of the next key.
+#[track_caller]
+fn check_iterated<const K: usize>(expected: &[(usize, u128)]) {
+    let mut key = [0_u8; K];
+    let mut plaintext = [0_u8; 16];
+
+    for i in 1..=49 {
+        let cipher = Twofish::new(&key);
+        let ciphertext = cipher.encrypt(plaintext);
+        assert_eq!(cipher.decrypt(ciphertext), plaintext);
+
+        if let Some(&(_, c)) = expected.iter().find(|(n, _)| *n == i) {
+            assert_eq!(ciphertext, c.to_be_bytes(), "i = {i}");
+        }
+
+        let (l, r) = key.split_at_mut(16);
+        r.copy_from_slice(&l[..(K - 16)]);
+        l.copy_from_slice(&plaintext);
+        plaintext = ciphertext;
+    }
+}
+
+#[test]
+fn iterated_128_bit_key() {
+    check_iterated::<16>(&[
+        (1, 0x9F589F5CF6122C32B6BFEC2F2AE8C35A),
+        (2, 0xD491DB16E7B1C39E86CB086B789F5419),
+        (3, 0x019F9809DE1711858FAAC3A3BA20FBC3),
+        (4, 0x6363977DE839486297E661C6C9D668EB),
+        (5, 0x816D5BD0FAE35342BF2A7412C246F752),
+        (48, 0x6B459286F3FFD28D49F15B1581B08E42),
+    ]);
+}
+
+#[test]
+fn iterated_192_bit_key() {
+    check_iterated::<24>(&[
+        (1, 0xEFA71F788965BD4453F860178FC19101),
+        (2, 0x88B2B2706B105E36B446BB6D731A1E88),
+        (3, 0x39DA69D6BA4997D585B6DC073CA341B2),
+        (4, 0x182B02D81497EA45F9DAACDC29193A65),
+        (5, 0x7AFF7A70CA2FF28AC31DD8AE5DAAAB63),
+        (48, 0xF0AB73301125FA21EF70BE5385FB76B6),
+    ]);
+}
+
+#[test]
+fn iterated_256_bit_key() {
+    check_iterated::<32>(&[
+        (1, 0x57FF739D4DC92C1BD7FC01700CC8216F),
+        (2, 0xD43BB7556EA32E46F2A282B7D45B4E0D),
+        (3, 0x90AFE91BB288544F2C32DC239B2635E6),
+        (4, 0x6CB4561C40BF0A9705931CB6D408E7FA),
+        (5, 0x3059D6D61753B958D92F4781C8640E58),
+        (48, 0x431058F4DBC7F734DA4F02F04CC4F459),
+    ]);
+}
+
+#[test]
+fn intermediate_value_examples() {
+    let plaintext = [0; 16];
+
+    let cipher = Twofish::from_hex("0123456789ABCDEFFEDCBA98765432100011223344556677").unwrap();
+    assert_eq!(
+        cipher.encrypt(plaintext),
+        0xCFD1D2E5A9BE9CDF501F13B892BD2248_u128.to_be_bytes()
+    );
+
+    let cipher = Twofish::from_hex(
+        "0123456789ABCDEFFEDCBA987654321000112233445566778899AABBCCDDEEFF",
+    )
+    .unwrap();
+    assert_eq!(
+        cipher.encrypt(plaintext),
+        0x37527BE0052334B89F0CFCCAE87CFA20_u128.to_be_bytes()
+    );
+}
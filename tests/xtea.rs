@@ -0,0 +1,18 @@
+use cryptography::xtea::Xtea;
+use cryptography::BlockCipher;
+
+#[track_caller]
+fn check(key: &str, plaintext: u64, ciphertext: u64) {
+    let cipher = Xtea::from_hex(key).unwrap();
+    let plaintext = plaintext.to_be_bytes();
+    let ciphertext = ciphertext.to_be_bytes();
+    assert_eq!(cipher.encrypt(plaintext), ciphertext, "key = {key}");
+    assert_eq!(cipher.decrypt(ciphertext), plaintext, "key = {key}");
+}
+
+#[test]
+fn ecb_examples() {
+    check("000102030405060708090a0b0c0d0e0f", 0x4142434445464748, 0x497df3d072612cb5);
+    check("00000000000000000000000000000000", 0x4142434445464748, 0xa0390589f8b8efa5);
+    check("00000000000000000000000000000000", 0x0000000000000000, 0xdee9d4d8f7131ed9);
+}